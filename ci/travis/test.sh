@@ -1,9 +1,14 @@
 #!/bin/bash
 set -ex
 
+# tokio needs a newer compiler than the minimum nightly
+if [ "$TRAVIS_JOB_NAME" != "Minimum nightly" ]; then
+  FEATURES="$FEATURES tokio"
+fi
+
 # run `cargo test` only if testing against cpython.
 if ! [[ $FEATURES == *"pypy"* ]]; then
-  cargo test --features "$FEATURES num-bigint num-complex"
+  cargo test --features "$FEATURES num-bigint num-complex serde anyhow chrono"
   ( cd pyo3-derive-backend; cargo test )
 else
   # check that pypy at least builds
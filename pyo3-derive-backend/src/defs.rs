@@ -60,6 +60,11 @@ pub const OBJECT: Proto = Proto {
             pyres: true,
             proto: "pyo3::class::basic::PyObjectBytesProtocol",
         },
+        MethodProto::Unary {
+            name: "__fspath__",
+            pyres: true,
+            proto: "pyo3::class::basic::PyObjectFspathProtocol",
+        },
         MethodProto::Unary {
             name: "__bool__",
             pyres: false,
@@ -71,6 +76,18 @@ pub const OBJECT: Proto = Proto {
             pyres: true,
             proto: "pyo3::class::basic::PyObjectRichcmpProtocol",
         },
+        MethodProto::Ternary {
+            name: "__init__",
+            arg1: "Args",
+            arg2: "Kwargs",
+            pyres: false,
+            proto: "pyo3::class::basic::PyObjectInitProtocol",
+        },
+        MethodProto::Unary {
+            name: "__del__",
+            pyres: false,
+            proto: "pyo3::class::basic::PyObjectDelProtocol",
+        },
     ],
     py_methods: &[
         PyMethod {
@@ -81,6 +98,10 @@ pub const OBJECT: Proto = Proto {
             name: "__bytes__",
             proto: "pyo3::class::basic::BytesProtocolImpl",
         },
+        PyMethod {
+            name: "__fspath__",
+            proto: "pyo3::class::basic::FspathProtocolImpl",
+        },
         PyMethod {
             name: "__unicode__",
             proto: "pyo3::class::basic::UnicodeProtocolImpl",
@@ -205,37 +226,35 @@ pub const DESCR: Proto = Proto {
             name: "__set__",
             arg1: "Inst",
             arg2: "Value",
-            pyres: true,
+            pyres: false,
             proto: "pyo3::class::descr::PyDescrSetProtocol",
         },
         MethodProto::Binary {
-            name: "__det__",
+            name: "__delete__",
             arg: "Inst",
             pyres: false,
-            proto: "pyo3::class::descr::PyDescrDelProtocol",
+            proto: "pyo3::class::descr::PyDescrDeleteProtocol",
         },
-        MethodProto::Binary {
+        MethodProto::Ternary {
             name: "__set_name__",
-            arg: "Inst",
+            arg1: "Owner",
+            arg2: "Name",
             pyres: false,
             proto: "pyo3::class::descr::PyDescrSetNameProtocol",
         },
     ],
-    py_methods: &[
-        PyMethod {
-            name: "__del__",
-            proto: "pyo3::class::context::PyDescrDelProtocolImpl",
-        },
-        PyMethod {
-            name: "__set_name__",
-            proto: "pyo3::class::context::PyDescrNameProtocolImpl",
-        },
-    ],
+    py_methods: &[PyMethod {
+        name: "__set_name__",
+        proto: "pyo3::class::descr::PyDescrSetNameProtocolImpl",
+    }],
 };
 
 pub const ITER: Proto = Proto {
     name: "Iter",
-    py_methods: &[],
+    py_methods: &[PyMethod {
+        name: "__length_hint__",
+        proto: "pyo3::class::iter::PyIterLengthHintProtocolImpl",
+    }],
     methods: &[
         MethodProto::Unary {
             name: "__iter__",
@@ -247,6 +266,11 @@ pub const ITER: Proto = Proto {
             pyres: true,
             proto: "pyo3::class::iter::PyIterNextProtocol",
         },
+        MethodProto::Unary {
+            name: "__length_hint__",
+            pyres: false,
+            proto: "pyo3::class::iter::PyIterLengthHintProtocol",
+        },
     ],
 };
 
@@ -369,7 +369,7 @@ pub fn impl_method_proto(
 
 // TODO: better arg ty detection
 fn get_arg_ty(sig: &syn::Signature, idx: usize) -> syn::Type {
-    let mut ty = match sig.inputs[idx] {
+    let ty = match sig.inputs[idx] {
         syn::FnArg::Typed(ref cap) => {
             match *cap.ty {
                 syn::Type::Path(ref ty) => {
@@ -379,7 +379,9 @@ fn get_arg_ty(sig: &syn::Signature, idx: usize) -> syn::Type {
                         if let syn::PathArguments::AngleBracketed(ref data) = seg.arguments {
                             if let Some(pair) = data.args.last() {
                                 match pair {
-                                    syn::GenericArgument::Type(ref ty) => return ty.clone(),
+                                    syn::GenericArgument::Type(ref ty) => {
+                                        return add_lifetime(ty.clone())
+                                    }
                                     _ => panic!("Option only accepted for concrete types"),
                                 }
                             };
@@ -393,11 +395,14 @@ fn get_arg_ty(sig: &syn::Signature, idx: usize) -> syn::Type {
         _ => panic!("fn arg type is not supported"),
     };
 
-    // Add a lifetime if there is none
+    add_lifetime(ty)
+}
+
+/// Adds the `'p` lifetime to a reference type if there is none
+fn add_lifetime(mut ty: syn::Type) -> syn::Type {
     if let syn::Type::Reference(ref mut r) = ty {
         r.lifetime.get_or_insert(syn::parse_quote! {'p});
     }
-
     ty
 }
 
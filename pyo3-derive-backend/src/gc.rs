@@ -0,0 +1,70 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+//! Code generation for `#[derive(PyGCTraverse)]`
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+/// Implements `PyGCTraverse` by forwarding to every field of the struct, and `PyGCProtocol`
+/// on top of it, so that no `#[pyproto]` block is needed.
+pub fn build_py_gc_traverse(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &ast.data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ast,
+                "#[derive(PyGCTraverse)] can only be used on structs",
+            ))
+        }
+    };
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &ast.generics,
+            "#[derive(PyGCTraverse)] cannot be used on structs with generic parameters",
+        ));
+    }
+
+    let members: Vec<syn::Member> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index {
+                index: i as u32,
+                span: Span::call_site(),
+            }),
+        })
+        .collect();
+
+    let cls = &ast.ident;
+    Ok(quote! {
+        impl pyo3::class::gc::PyGCTraverse for #cls {
+            fn traverse(
+                &self,
+                visit: pyo3::class::gc::PyVisit,
+            ) -> Result<(), pyo3::class::gc::PyTraverseError> {
+                #(pyo3::class::gc::PyGCTraverse::traverse(&self.#members, visit)?;)*
+                Ok(())
+            }
+
+            fn clear(&mut self) {
+                #(pyo3::class::gc::PyGCTraverse::clear(&mut self.#members);)*
+            }
+        }
+
+        impl<'p> pyo3::class::gc::PyGCProtocol<'p> for #cls {
+            fn __traverse__(
+                &'p self,
+                visit: pyo3::class::gc::PyVisit,
+            ) -> Result<(), pyo3::class::gc::PyTraverseError> {
+                pyo3::class::gc::PyGCTraverse::traverse(self, visit)
+            }
+
+            fn __clear__(&'p mut self) {
+                pyo3::class::gc::PyGCTraverse::clear(self)
+            }
+        }
+
+        impl<'p> pyo3::class::gc::PyGCTraverseProtocol<'p> for #cls {}
+        impl<'p> pyo3::class::gc::PyGCClearProtocol<'p> for #cls {}
+    })
+}
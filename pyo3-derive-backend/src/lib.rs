@@ -5,6 +5,7 @@
 
 mod defs;
 mod func;
+mod gc;
 mod method;
 mod module;
 mod pyclass;
@@ -14,7 +15,8 @@ mod pymethod;
 mod pyproto;
 mod utils;
 
-pub use module::{add_fn_to_module, process_functions_in_module, py_init};
+pub use gc::build_py_gc_traverse;
+pub use module::{add_fn_to_module, process_functions_in_module, py_init, PyModuleAttr};
 pub use pyclass::{build_py_class, PyClassArgs};
 pub use pyfunction::{build_py_function, PyFunctionAttr};
 pub use pyimpl::{build_py_methods, impl_methods};
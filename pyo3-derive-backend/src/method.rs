@@ -18,6 +18,9 @@ pub struct FnArg<'a> {
     pub optional: Option<&'a syn::Type>,
     pub py: bool,
     pub reference: bool,
+    pub kw_only: bool,
+    pub var_args: bool,
+    pub var_kwargs: bool,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -26,9 +29,11 @@ pub enum FnType {
     Setter,
     Fn,
     FnNew,
+    FnInit,
     FnCall,
     FnClass,
     FnStatic,
+    ClassAttribute,
     PySelf(syn::TypeReference),
 }
 
@@ -44,6 +49,8 @@ pub struct FnSpec<'a> {
     pub args: Vec<FnArg<'a>>,
     pub output: syn::Type,
     pub doc: syn::LitStr,
+    // Whether the method gets mutable access to `self`, which frozen classes forbid
+    pub mut_self: bool,
 }
 
 pub fn get_return_info(output: &syn::ReturnType) -> syn::Type {
@@ -67,15 +74,25 @@ impl<'a> FnSpec<'a> {
             mut python_name,
         } = parse_method_attributes(meth_attrs, allow_custom_name)?;
 
+        // Like in Python, `__class_getitem__` and `__init_subclass__` are implicitly class methods
+        if fn_type == FnType::Fn && (name == "__class_getitem__" || name == "__init_subclass__") {
+            fn_type = FnType::FnClass;
+        }
+
         let mut has_self = false;
+        let mut mut_self = false;
         let mut arguments = Vec::new();
         for input in sig.inputs.iter() {
             match input {
-                syn::FnArg::Receiver(_) => {
+                syn::FnArg::Receiver(recv) => {
                     has_self = true;
+                    mut_self = recv.reference.is_some() && recv.mutability.is_some();
                 }
                 syn::FnArg::Typed(syn::PatType {
-                    ref pat, ref ty, ..
+                    ref attrs,
+                    ref pat,
+                    ref ty,
+                    ..
                 }) => {
                     // skip first argument (cls)
                     if fn_type == FnType::FnClass && !has_self {
@@ -98,22 +115,44 @@ impl<'a> FnSpec<'a> {
                     let py = crate::utils::if_type_is_python(ty);
 
                     let opt = check_arg_ty_and_optional(name, ty);
-                    arguments.push(FnArg {
-                        name: ident,
-                        by_ref,
-                        mutability,
-                        // mode: mode,
-                        ty,
-                        optional: opt,
-                        py,
-                        reference: is_ref(name, ty),
-                    });
+                    let arg_attrs = utils::parse_arg_attrs(attrs)?;
+                    push_fn_arg(
+                        &mut arguments,
+                        FnArg {
+                            name: ident,
+                            by_ref,
+                            mutability,
+                            // mode: mode,
+                            ty,
+                            optional: opt,
+                            py,
+                            reference: is_ref(name, ty),
+                            kw_only: arg_attrs.kw_only,
+                            var_args: arg_attrs.var_args,
+                            var_kwargs: arg_attrs.var_kwargs,
+                        },
+                        pat,
+                    )?;
                 }
             }
         }
 
         let ty = get_return_info(&sig.output);
 
+        if fn_type == FnType::FnInit && !has_self {
+            return Err(syn::Error::new_spanned(
+                name,
+                "#[init] method needs a `self` receiver",
+            ));
+        }
+
+        if fn_type == FnType::ClassAttribute && (has_self || !arguments.is_empty()) {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "#[classattr] can only be used on functions without arguments",
+            ));
+        }
+
         if fn_type == FnType::Fn && !has_self {
             if arguments.is_empty() {
                 return Err(syn::Error::new_spanned(
@@ -125,6 +164,7 @@ impl<'a> FnSpec<'a> {
                 syn::Type::Reference(r) => replace_self(r)?,
                 x => return Err(syn::Error::new_spanned(x, "Invalid type as custom self")),
             };
+            mut_self = tp.mutability.is_some();
             fn_type = FnType::PySelf(tp);
         }
 
@@ -165,7 +205,11 @@ impl<'a> FnSpec<'a> {
                 "text_signature not allowed on __new__; if you want to add a signature on \
                  __new__, put it on the struct definition instead",
             )?,
-            FnType::FnCall | FnType::Getter | FnType::Setter => {
+            FnType::FnInit
+            | FnType::FnCall
+            | FnType::Getter
+            | FnType::Setter
+            | FnType::ClassAttribute => {
                 parse_erroneous_text_signature("text_signature not allowed with this attribute")?
             }
         };
@@ -180,10 +224,14 @@ impl<'a> FnSpec<'a> {
             args: arguments,
             output: ty,
             doc,
+            mut_self,
         })
     }
 
     pub fn is_args(&self, name: &syn::Ident) -> bool {
+        if self.args.iter().any(|arg| arg.var_args && arg.name == name) {
+            return true;
+        }
         for s in self.attrs.iter() {
             if let Argument::VarArgs(ref path) = s {
                 return path.is_ident(name);
@@ -193,6 +241,9 @@ impl<'a> FnSpec<'a> {
     }
 
     pub fn accept_args(&self) -> bool {
+        if self.args.iter().any(|arg| arg.var_args) {
+            return true;
+        }
         for s in self.attrs.iter() {
             match *s {
                 Argument::VarArgs(_) => return true,
@@ -204,6 +255,13 @@ impl<'a> FnSpec<'a> {
     }
 
     pub fn is_kwargs(&self, name: &syn::Ident) -> bool {
+        if self
+            .args
+            .iter()
+            .any(|arg| arg.var_kwargs && arg.name == name)
+        {
+            return true;
+        }
         for s in self.attrs.iter() {
             if let Argument::KeywordArgs(ref path) = s {
                 return path.is_ident(name);
@@ -213,6 +271,9 @@ impl<'a> FnSpec<'a> {
     }
 
     pub fn accept_kwargs(&self) -> bool {
+        if self.args.iter().any(|arg| arg.var_kwargs) {
+            return true;
+        }
         for s in self.attrs.iter() {
             if let Argument::KeywordArgs(_) = s {
                 return true;
@@ -245,6 +306,9 @@ impl<'a> FnSpec<'a> {
     }
 
     pub fn is_kw_only(&self, name: &syn::Ident) -> bool {
+        if self.args.iter().any(|arg| arg.kw_only && arg.name == name) {
+            return true;
+        }
         for s in self.attrs.iter() {
             if let Argument::Kwarg(ref path, _) = s {
                 if path.is_ident(name) {
@@ -256,6 +320,40 @@ impl<'a> FnSpec<'a> {
     }
 }
 
+/// Appends `arg` to the arguments of a function, checking that it is in a valid position.
+///
+/// Like in Python, the arguments after the one marked with `#[pyo3(args)]` are keyword-only
+/// and no argument can follow the one marked with `#[pyo3(kwargs)]`.
+pub fn push_fn_arg<'a>(
+    arguments: &mut Vec<FnArg<'a>>,
+    mut arg: FnArg<'a>,
+    tokens: &impl ToTokens,
+) -> syn::Result<()> {
+    if !arg.py && arguments.iter().any(|arg| arg.var_kwargs) {
+        return Err(syn::Error::new_spanned(
+            tokens,
+            "no argument can follow the #[pyo3(kwargs)] argument",
+        ));
+    }
+    if arg.var_args && arguments.iter().any(|arg| arg.var_args) {
+        return Err(syn::Error::new_spanned(
+            tokens,
+            "only one argument can be marked with #[pyo3(args)]",
+        ));
+    }
+    if !arg.py && !arg.var_kwargs && arguments.iter().any(|arg| arg.var_args) {
+        arg.kw_only = true;
+    }
+    if !arg.kw_only && !arg.py && !arg.var_kwargs && arguments.iter().any(|arg| arg.kw_only) {
+        return Err(syn::Error::new_spanned(
+            tokens,
+            "positional argument follows keyword-only argument",
+        ));
+    }
+    arguments.push(arg);
+    Ok(())
+}
+
 pub fn is_ref(name: &syn::Ident, ty: &syn::Type) -> bool {
     match ty {
         syn::Type::Reference(_) => return true,
@@ -364,16 +462,15 @@ fn parse_method_attributes(
                 if name.is_ident("new") || name.is_ident("__new__") {
                     res = Some(FnType::FnNew)
                 } else if name.is_ident("init") || name.is_ident("__init__") {
-                    return Err(syn::Error::new_spanned(
-                        name,
-                        "#[init] is disabled from PyO3 0.9.0",
-                    ));
+                    res = Some(FnType::FnInit)
                 } else if name.is_ident("call") || name.is_ident("__call__") {
                     res = Some(FnType::FnCall)
                 } else if name.is_ident("classmethod") {
                     res = Some(FnType::FnClass)
                 } else if name.is_ident("staticmethod") {
                     res = Some(FnType::FnStatic)
+                } else if name.is_ident("classattr") {
+                    res = Some(FnType::ClassAttribute)
                 } else if name.is_ident("setter") || name.is_ident("getter") {
                     if let syn::AttrStyle::Inner(_) = attr.style {
                         return Err(syn::Error::new_spanned(
@@ -404,10 +501,7 @@ fn parse_method_attributes(
                 if path.is_ident("new") {
                     res = Some(FnType::FnNew)
                 } else if path.is_ident("init") {
-                    return Err(syn::Error::new_spanned(
-                        path,
-                        "#[init] is disabled from PyO3 0.9.0",
-                    ));
+                    res = Some(FnType::FnInit)
                 } else if path.is_ident("call") {
                     res = Some(FnType::FnCall)
                 } else if path.is_ident("setter") || path.is_ident("getter") {
@@ -494,7 +588,7 @@ fn parse_method_name_attribute(
     // Reject some invalid combinations
     if let Some(name) = &name {
         match ty {
-            FnType::FnNew | FnType::FnCall | FnType::Getter | FnType::Setter => {
+            FnType::FnNew | FnType::FnInit | FnType::FnCall | FnType::Getter | FnType::Setter => {
                 return Err(syn::Error::new_spanned(
                     name,
                     "name not allowed with this attribute",
@@ -507,6 +601,7 @@ fn parse_method_name_attribute(
     // Thanks to check above we can be sure that this generates the right python name
     Ok(match ty {
         FnType::FnNew => Some(syn::Ident::new("__new__", proc_macro2::Span::call_site())),
+        FnType::FnInit => Some(syn::Ident::new("__init__", proc_macro2::Span::call_site())),
         FnType::FnCall => Some(syn::Ident::new("__call__", proc_macro2::Span::call_site())),
         FnType::Getter | FnType::Setter => property_name,
         _ => name,
@@ -2,20 +2,72 @@
 //! Code generation for the function that initializes a python module and adds classes and function.
 
 use crate::method;
-use crate::pyfunction;
-use crate::pyfunction::PyFunctionAttr;
+use crate::pyfunction::{AsyncRuntime, PyFunctionAttr};
 use crate::pymethod;
 use crate::pymethod::get_arg_names;
 use crate::utils;
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
 use syn::Ident;
 
+/// The arguments of the `#[pymodule]` attribute: `#[pymodule(name, parent = "parent_name")]`
+#[derive(Default)]
+pub struct PyModuleAttr {
+    pub name: Option<Ident>,
+    pub parent: Option<syn::LitStr>,
+}
+
+impl syn::parse::Parse for PyModuleAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut attr = PyModuleAttr::default();
+        let items = Punctuated::<syn::NestedMeta, syn::Token![,]>::parse_terminated(input)?;
+        for item in items.iter() {
+            match item {
+                syn::NestedMeta::Meta(syn::Meta::Path(path))
+                    if attr.name.is_none() && path.get_ident().is_some() =>
+                {
+                    attr.name = path.get_ident().cloned();
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(lit),
+                    ..
+                })) if attr.parent.is_none() && path.is_ident("parent") => {
+                    attr.parent = Some(lit.clone());
+                }
+                _ => return Err(syn::Error::new_spanned(item, "Unsupported parameter")),
+            }
+        }
+        Ok(attr)
+    }
+}
+
 /// Generates the function that is called by the python interpreter to initialize the native
 /// module
-pub fn py_init(fnname: &Ident, name: &Ident, doc: syn::LitStr) -> TokenStream {
+pub fn py_init(
+    fnname: &Ident,
+    name: &Ident,
+    doc: syn::LitStr,
+    parent: Option<&syn::LitStr>,
+) -> TokenStream {
     let cb_name = Ident::new(&format!("PyInit_{}", name), Span::call_site());
 
+    // Submodules are added to their parent when it is initialized
+    let registration = parent.map(|parent| {
+        quote! {
+            pyo3::inventory::submit! {
+                #![crate = pyo3] {
+                    pyo3::derive_utils::ModuleRegistration {
+                        parent: #parent,
+                        name: stringify!(#name),
+                        init: #cb_name,
+                    }
+                }
+            }
+        }
+    });
+
     quote! {
         #[no_mangle]
         #[allow(non_snake_case)]
@@ -24,6 +76,8 @@ pub fn py_init(fnname: &Ident, name: &Ident, doc: syn::LitStr) -> TokenStream {
         pub unsafe extern "C" fn #cb_name() -> *mut pyo3::ffi::PyObject {
             pyo3::derive_utils::make_module(concat!(stringify!(#name), "\0"), #doc, #fnname)
         }
+
+        #registration
     }
 }
 
@@ -54,8 +108,11 @@ pub fn process_functions_in_module(func: &mut syn::ItemFn) {
 }
 
 /// Transforms a rust fn arg parsed with syn into a method::FnArg
-fn wrap_fn_argument<'a>(input: &'a syn::FnArg, name: &'a Ident) -> Option<method::FnArg<'a>> {
-    match input {
+fn wrap_fn_argument<'a>(
+    input: &'a syn::FnArg,
+    name: &'a Ident,
+) -> syn::Result<Option<method::FnArg<'a>>> {
+    Ok(match input {
         syn::FnArg::Receiver(_) => None,
         syn::FnArg::Typed(ref cap) => {
             let (mutability, by_ref, ident) = match *cap.pat {
@@ -65,6 +122,7 @@ fn wrap_fn_argument<'a>(input: &'a syn::FnArg, name: &'a Ident) -> Option<method
 
             let py = crate::utils::if_type_is_python(&cap.ty);
             let opt = method::check_arg_ty_and_optional(&name, &cap.ty);
+            let arg_attrs = utils::parse_arg_attrs(&cap.attrs)?;
             Some(method::FnArg {
                 name: ident,
                 mutability,
@@ -73,19 +131,22 @@ fn wrap_fn_argument<'a>(input: &'a syn::FnArg, name: &'a Ident) -> Option<method
                 optional: opt,
                 py,
                 reference: method::is_ref(&name, &cap.ty),
+                kw_only: arg_attrs.kw_only,
+                var_args: arg_attrs.var_args,
+                var_kwargs: arg_attrs.var_kwargs,
             })
         }
-    }
+    })
 }
 
 /// Extracts the data from the #[pyfn(...)] attribute of a function
 fn extract_pyfn_attrs(
     attrs: &mut Vec<syn::Attribute>,
-) -> Option<(syn::Path, Ident, Vec<pyfunction::Argument>)> {
+) -> Option<(syn::Path, Ident, PyFunctionAttr)> {
     let mut new_attrs = Vec::new();
     let mut fnname = None;
     let mut modname = None;
-    let mut fn_attrs = Vec::new();
+    let mut fn_attrs = PyFunctionAttr::default();
 
     for attr in attrs.iter() {
         match attr.parse_meta() {
@@ -108,9 +169,7 @@ fn extract_pyfn_attrs(
                     }
                     // Read additional arguments
                     if list.nested.len() >= 3 {
-                        fn_attrs = PyFunctionAttr::from_meta(&meta[2..meta.len()])
-                            .unwrap()
-                            .arguments;
+                        fn_attrs = PyFunctionAttr::from_meta(&meta[2..meta.len()]).unwrap();
                     }
                 } else {
                     panic!("can not parse 'pyfn' params {:?}", attr);
@@ -135,13 +194,46 @@ fn function_wrapper_ident(name: &Ident) -> Ident {
 pub fn add_fn_to_module(
     func: &mut syn::ItemFn,
     python_name: Ident,
-    pyfn_attrs: Vec<pyfunction::Argument>,
+    pyfn_attrs: PyFunctionAttr,
 ) -> TokenStream {
+    let is_async = func.sig.asyncness.is_some();
+    if is_async && pyfn_attrs.allow_threads {
+        return syn::Error::new_spanned(
+            func.sig.asyncness,
+            "an async function can't be called with `allow_threads`",
+        )
+        .to_compile_error();
+    }
+    if !is_async && pyfn_attrs.async_runtime.is_some() {
+        return syn::Error::new_spanned(&func.sig, "`async_runtime` requires an async function")
+            .to_compile_error();
+    }
+
     let mut arguments = Vec::new();
 
     for input in func.sig.inputs.iter() {
-        if let Some(fn_arg) = wrap_fn_argument(input, &func.sig.ident) {
-            arguments.push(fn_arg);
+        match wrap_fn_argument(input, &func.sig.ident) {
+            Ok(Some(fn_arg)) => {
+                if let Err(err) = method::push_fn_arg(&mut arguments, fn_arg, input) {
+                    return err.to_compile_error();
+                }
+            }
+            Ok(None) => {}
+            Err(err) => return err.to_compile_error(),
+        }
+    }
+
+    // A `Python` token proves that the GIL is held, so it can't be passed to a function that
+    // runs with the GIL released, or to a future that is awaited after the call has returned
+    if pyfn_attrs.allow_threads || is_async {
+        if let Some(arg) = arguments.iter().find(|arg| arg.py) {
+            let msg = if is_async {
+                "an async function can't take a `Python` argument"
+            } else {
+                "a function with `allow_threads` can't take a `Python` argument, \
+                 because it runs with the GIL released"
+            };
+            return syn::Error::new_spanned(arg.ty, msg).to_compile_error();
         }
     }
 
@@ -162,17 +254,23 @@ pub fn add_fn_to_module(
         tp: method::FnType::Fn,
         name: &function_wrapper_ident,
         python_name,
-        attrs: pyfn_attrs,
+        attrs: pyfn_attrs.arguments,
         args: arguments,
         output: ty,
         doc,
+        mut_self: false,
     };
 
     let doc = &spec.doc;
 
     let python_name = &spec.python_name;
 
-    let wrapper = function_c_wrapper(&func.sig.ident, &spec);
+    let call = FunctionCall {
+        allow_threads: pyfn_attrs.allow_threads,
+        is_async,
+        async_runtime: pyfn_attrs.async_runtime,
+    };
+    let wrapper = function_c_wrapper(&func.sig.ident, &spec, call);
 
     let tokens = quote! {
         fn #function_wrapper_ident(py: pyo3::Python) -> pyo3::PyObject {
@@ -199,14 +297,42 @@ pub fn add_fn_to_module(
         }
     };
 
+    utils::remove_arg_attrs(&mut func.sig);
     tokens
 }
 
+/// How the wrapper calls the function
+struct FunctionCall {
+    allow_threads: bool,
+    is_async: bool,
+    async_runtime: Option<AsyncRuntime>,
+}
+
 /// Generate static function wrapper (PyCFunction, PyCFunctionWithKeywords)
-fn function_c_wrapper(name: &Ident, spec: &method::FnSpec<'_>) -> TokenStream {
+fn function_c_wrapper(name: &Ident, spec: &method::FnSpec<'_>, call: FunctionCall) -> TokenStream {
     let names: Vec<Ident> = get_arg_names(&spec);
-    let cb = quote! {
-        #name(#(#names),*)
+    let cb = if call.allow_threads {
+        // The arguments are moved into the closure, so they must be `Send`
+        quote! {
+            _py.allow_threads(move || #name(#(#names),*))
+        }
+    } else if let Some(AsyncRuntime::Tokio) = call.async_runtime {
+        quote! {
+            pyo3::class::pyasync::PyCoroutine::new(
+                pyo3::async_runtime::tokio::spawn(#name(#(#names),*))
+            )
+        }
+    } else if call.is_async {
+        // The future is polled by the Python coroutine that awaits the result
+        quote! {
+            pyo3::class::pyasync::PyCoroutine::new(
+                pyo3::derive_utils::PyResultFuture::new(#name(#(#names),*))
+            )
+        }
+    } else {
+        quote! {
+            #name(#(#names),*)
+        }
     };
 
     let body = pymethod::impl_arg_params(spec, cb);
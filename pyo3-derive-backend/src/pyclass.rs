@@ -18,6 +18,15 @@ pub struct PyClassArgs {
     pub base: syn::TypePath,
     pub has_extends: bool,
     pub module: Option<syn::LitStr>,
+    pub debug: bool,
+    pub display: bool,
+    pub hash: bool,
+    pub eq: bool,
+    pub ord: bool,
+    pub copy: bool,
+    pub pickleable: bool,
+    pub vectorcall: bool,
+    pub slots: Vec<syn::LitStr>,
 }
 
 impl Parse for PyClassArgs {
@@ -43,6 +52,15 @@ impl Default for PyClassArgs {
             flags: vec![parse_quote! { 0 }],
             base: parse_quote! { pyo3::types::PyAny },
             has_extends: false,
+            debug: false,
+            display: false,
+            hash: false,
+            eq: false,
+            ord: false,
+            copy: false,
+            pickleable: false,
+            vectorcall: false,
+            slots: Vec::new(),
         }
     }
 }
@@ -114,6 +132,30 @@ impl PyClassArgs {
                     ));
                 }
             },
+            "slots" => match *assign.right {
+                syn::Expr::Array(ref array) => {
+                    for elem in array.elems.iter() {
+                        match elem {
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(ref lit),
+                                ..
+                            }) => self.slots.push(lit.clone()),
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    elem,
+                                    "Slot names must be string literals",
+                                ));
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        *assign.right.clone(),
+                        "Wrong format for slots",
+                    ));
+                }
+            },
             _ => {
                 return Err(syn::Error::new_spanned(
                     *assign.left.clone(),
@@ -141,6 +183,50 @@ impl PyClassArgs {
             "dict" => {
                 parse_quote! {pyo3::type_flags::DICT}
             }
+            "frozen" => {
+                parse_quote! {pyo3::type_flags::FROZEN}
+            }
+            "mapping" => {
+                parse_quote! {pyo3::type_flags::MAPPING}
+            }
+            "sequence" => {
+                parse_quote! {pyo3::type_flags::SEQUENCE}
+            }
+            "mapping_mixin" => {
+                parse_quote! {pyo3::type_flags::MAPPING_MIXIN}
+            }
+            "debug" => {
+                self.debug = true;
+                return Ok(());
+            }
+            "display" => {
+                self.display = true;
+                return Ok(());
+            }
+            "hash" => {
+                self.hash = true;
+                return Ok(());
+            }
+            "eq" => {
+                self.eq = true;
+                return Ok(());
+            }
+            "ord" => {
+                self.ord = true;
+                return Ok(());
+            }
+            "copy" => {
+                self.copy = true;
+                return Ok(());
+            }
+            "pickleable" => {
+                self.pickleable = true;
+                return Ok(());
+            }
+            "vectorcall" => {
+                self.vectorcall = true;
+                return Ok(());
+            }
             _ => {
                 return Err(syn::Error::new_spanned(
                     exp.path.clone(),
@@ -161,11 +247,25 @@ pub fn build_py_class(class: &mut syn::ItemStruct, attr: &PyClassArgs) -> syn::R
     )?;
     let doc = utils::get_doc(&class.attrs, text_signature, true)?;
     let mut descriptors = Vec::new();
+    let mut field_names = Vec::new();
 
     check_generics(class)?;
+    let frozen = attr.flags.iter().any(|flag| match flag {
+        syn::Expr::Path(epath) => epath.path == parse_quote! { pyo3::type_flags::FROZEN },
+        _ => false,
+    });
     if let syn::Fields::Named(ref mut fields) = class.fields {
         for field in fields.named.iter_mut() {
+            if let Some(ident) = &field.ident {
+                field_names.push(ident.unraw().to_string());
+            }
             let field_descs = parse_descriptors(field)?;
+            if frozen && field_descs.contains(&FnType::Setter) {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "#[pyo3(set)] can't be used in a #[pyclass(frozen)]",
+                ));
+            }
             if !field_descs.is_empty() {
                 descriptors.push((field.clone(), field_descs));
             }
@@ -177,7 +277,7 @@ pub fn build_py_class(class: &mut syn::ItemStruct, attr: &PyClassArgs) -> syn::R
         ));
     }
 
-    impl_class(&class.ident, &attr, doc, descriptors)
+    impl_class(&class.ident, &attr, doc, descriptors, field_names)
 }
 
 /// Parses `#[pyo3(get, set)]`
@@ -259,6 +359,7 @@ fn impl_class(
     attr: &PyClassArgs,
     doc: syn::LitStr,
     descriptors: Vec<(syn::Field, Vec<FnType>)>,
+    field_names: Vec<String>,
 ) -> syn::Result<TokenStream> {
     let cls_name = get_class_python_name(cls, attr).to_string();
 
@@ -302,6 +403,9 @@ fn impl_class(
     let mut has_weakref = false;
     let mut has_dict = false;
     let mut has_gc = false;
+    let mut has_frozen = false;
+    let mut has_mapping = false;
+    let mut has_sequence = false;
     for f in attr.flags.iter() {
         if let syn::Expr::Path(ref epath) = f {
             if epath.path == parse_quote! { pyo3::type_flags::WEAKREF } {
@@ -310,9 +414,21 @@ fn impl_class(
                 has_dict = true;
             } else if epath.path == parse_quote! { pyo3::type_flags::GC } {
                 has_gc = true;
+            } else if epath.path == parse_quote! { pyo3::type_flags::FROZEN } {
+                has_frozen = true;
+            } else if epath.path == parse_quote! { pyo3::type_flags::MAPPING } {
+                has_mapping = true;
+            } else if epath.path == parse_quote! { pyo3::type_flags::SEQUENCE } {
+                has_sequence = true;
             }
         }
     }
+    if has_mapping && has_sequence {
+        return Err(syn::Error::new_spanned(
+            cls,
+            "#[pyclass] can't be both a `mapping` and a `sequence`",
+        ));
+    }
 
     let weakref = if has_weakref {
         quote! { type WeakRef = pyo3::pyclass_slots::PyClassWeakRefSlot; }
@@ -324,6 +440,53 @@ fn impl_class(
     } else {
         quote! { type Dict = pyo3::pyclass_slots::PyClassDummySlot; }
     };
+    let slots = if attr.slots.is_empty() {
+        quote! { type Slots = pyo3::pyclass_slots::PyClassDummySlot; }
+    } else {
+        if has_dict {
+            return Err(syn::Error::new_spanned(
+                &attr.slots[0],
+                "#[pyclass(slots = [...])] can't be combined with `dict`",
+            ));
+        }
+        if attr.slots.len() > 32 {
+            return Err(syn::Error::new_spanned(
+                &attr.slots[32],
+                "#[pyclass] supports at most 32 slots",
+            ));
+        }
+        let len = attr.slots.len();
+        let names = &attr.slots;
+        quote! {
+            type Slots = pyo3::pyclass_slots::PyClassSlotArray<[*mut pyo3::ffi::PyObject; #len]>;
+            const SLOT_NAMES: &'static [&'static str] = &[#(#names),*];
+        }
+    };
+    let vectorcall = if attr.vectorcall {
+        quote! { type Vectorcall = pyo3::pyclass_slots::PyClassVectorcallSlot<Self>; }
+    } else {
+        quote! { type Vectorcall = pyo3::pyclass_slots::PyClassDummySlot; }
+    };
+    // `hash`, `eq` and `ord` only set their slots, so they can be combined with a
+    // `#[pyproto] impl PyObjectProtocol` block
+    let mut derived_slots = TokenStream::new();
+    if attr.hash {
+        derived_slots.extend(quote! {
+            const DERIVED_HASH: Option<pyo3::ffi::hashfunc> =
+                Some(pyo3::derive_utils::hash_slot::<Self>);
+        });
+    }
+    if attr.ord {
+        derived_slots.extend(quote! {
+            const DERIVED_RICHCMP: Option<pyo3::ffi::richcmpfunc> =
+                Some(pyo3::derive_utils::richcmp_ord_slot::<Self>);
+        });
+    } else if attr.eq {
+        derived_slots.extend(quote! {
+            const DERIVED_RICHCMP: Option<pyo3::ffi::richcmpfunc> =
+                Some(pyo3::derive_utils::richcmp_eq_slot::<Self>);
+        });
+    }
     let module = if let Some(m) = &attr.module {
         quote! { Some(#m) }
     } else {
@@ -346,7 +509,25 @@ fn impl_class(
         quote! {}
     };
 
+    // Frozen classes must be `Send + Sync`, which the supertraits of `PyFrozenClass` enforce
+    let frozen_impl = if has_frozen {
+        quote! { impl pyo3::PyFrozenClass for #cls {} }
+    } else {
+        quote! { impl pyo3::pyclass::PyMutableClass for #cls {} }
+    };
+
     let inventory_impl = impl_inventory(&cls);
+    let object_protocol_impl = impl_object_protocol(&cls, attr);
+    let copy_impl = if attr.copy {
+        impl_copy(&cls, attr)?
+    } else {
+        quote! {}
+    };
+    let pickle_impl = if attr.pickleable {
+        impl_pickle(&cls)?
+    } else {
+        quote! {}
+    };
 
     let base = &attr.base;
     let flags = &attr.flags;
@@ -373,8 +554,9 @@ fn impl_class(
         unsafe impl pyo3::type_object::PyTypeInfo for #cls {
             type Type = #cls;
             type BaseType = #base;
-            type ConcreteLayout = pyo3::pyclass::PyClassShell<Self>;
+            type ConcreteLayout = pyo3::pyclass::PyCell<Self>;
             type Initializer = pyo3::pyclass_init::PyClassInitializer<Self>;
+            type AsRefTarget = pyo3::PyCell<Self>;
 
             const NAME: &'static str = #cls_name;
             const MODULE: Option<&'static str> = #module;
@@ -392,6 +574,15 @@ fn impl_class(
         impl pyo3::PyClass for #cls {
             #dict
             #weakref
+            #slots
+            #vectorcall
+            #derived_slots
+        }
+
+        impl pyo3::PyClassInfo for #cls {
+            fn field_names() -> &'static [&'static str] {
+                &[#(#field_names),*]
+            }
         }
 
         impl pyo3::conversion::FromPyObjectImpl for #cls {
@@ -414,9 +605,62 @@ fn impl_class(
 
         #gc_impl
 
+        #frozen_impl
+
+        #object_protocol_impl
+
+        #copy_impl
+
+        #pickle_impl
+
     })
 }
 
+/// Generates `__repr__` from `Debug` and `__str__` from `Display` for `#[pyclass(debug)]` and
+/// `#[pyclass(display)]`
+fn impl_object_protocol(cls: &syn::Ident, attr: &PyClassArgs) -> TokenStream {
+    if !attr.debug && !attr.display {
+        return quote! {};
+    }
+
+    let mut methods = TokenStream::new();
+    let mut protos = TokenStream::new();
+    if attr.debug {
+        methods.extend(quote! {
+            fn __repr__(&'p self) -> <#cls as pyo3::class::basic::PyObjectReprProtocol<'p>>::Result {
+                Ok(format!("{:?}", self))
+            }
+        });
+        protos.extend(quote! {
+            impl<'p> pyo3::class::basic::PyObjectReprProtocol<'p> for #cls {
+                type Success = String;
+                type Result = pyo3::PyResult<String>;
+            }
+        });
+    }
+    if attr.display {
+        methods.extend(quote! {
+            fn __str__(&'p self) -> <#cls as pyo3::class::basic::PyObjectStrProtocol<'p>>::Result {
+                Ok(format!("{}", self))
+            }
+        });
+        protos.extend(quote! {
+            impl<'p> pyo3::class::basic::PyObjectStrProtocol<'p> for #cls {
+                type Success = String;
+                type Result = pyo3::PyResult<String>;
+            }
+        });
+    }
+
+    quote! {
+        impl<'p> pyo3::class::basic::PyObjectProtocol<'p> for #cls {
+            #methods
+        }
+
+        #protos
+    }
+}
+
 fn impl_descriptors(
     cls: &syn::Type,
     descriptors: Vec<(syn::Field, Vec<FnType>)>,
@@ -478,6 +722,7 @@ fn impl_descriptors(
                                 args: Vec::new(),
                                 output: parse_quote!(PyResult<#field_ty>),
                                 doc,
+                                mut_self: false,
                             };
                             Ok(impl_py_getter_def(&spec, &impl_wrap_getter(&cls, &spec)?))
                         }
@@ -499,9 +744,13 @@ fn impl_descriptors(
                                     optional: None,
                                     py: true,
                                     reference: false,
+                                    kw_only: false,
+                                    var_args: false,
+                                    var_kwargs: false,
                                 }],
                                 output: parse_quote!(PyResult<()>),
                                 doc,
+                                mut_self: true,
                             };
                             Ok(impl_py_setter_def(&spec, &impl_wrap_setter(&cls, &spec)?))
                         }
@@ -524,6 +773,70 @@ fn impl_descriptors(
     })
 }
 
+/// Generates `__copy__` and `__deepcopy__` from `Clone` for `#[pyclass(copy)]`
+fn impl_copy(cls: &syn::Ident, attr: &PyClassArgs) -> syn::Result<TokenStream> {
+    if attr.has_extends {
+        return Err(syn::Error::new_spanned(
+            cls,
+            "#[pyclass(copy)] can not be used together with extends",
+        ));
+    }
+
+    let mut copy_impl: syn::ItemImpl = parse_quote! {
+        impl #cls {
+            #[name = "__copy__"]
+            fn __pyo3_copy(&self) -> #cls {
+                std::clone::Clone::clone(self)
+            }
+
+            #[name = "__deepcopy__"]
+            fn __pyo3_deepcopy(&self, _memo: &pyo3::types::PyAny) -> #cls {
+                std::clone::Clone::clone(self)
+            }
+        }
+    };
+    let methods = crate::pyimpl::build_py_methods(&mut copy_impl)?;
+
+    Ok(quote! {
+        #copy_impl
+
+        #methods
+    })
+}
+
+/// Generates `__reduce__` for `#[pyclass(pickleable)]`, which recreates the object by calling
+/// the class with the result of `__getnewargs__` (if any) and then restores the result of
+/// `__getstate__` with `__setstate__`
+fn impl_pickle(cls: &syn::Ident) -> syn::Result<TokenStream> {
+    let mut pickle_impl: syn::ItemImpl = parse_quote! {
+        impl #cls {
+            #[name = "__reduce__"]
+            fn __pyo3_reduce(
+                slf: &pyo3::PyCell<Self>,
+                py: pyo3::Python,
+            ) -> pyo3::PyResult<pyo3::PyObject> {
+                use pyo3::{AsPyPointer, ObjectProtocol, ToPyObject};
+
+                let obj: &pyo3::types::PyAny = unsafe { py.from_borrowed_ptr(slf.as_ptr()) };
+                let args = if obj.hasattr("__getnewargs__")? {
+                    obj.call_method0("__getnewargs__")?
+                } else {
+                    pyo3::types::PyTuple::empty(py).as_ref()
+                };
+                let state = obj.call_method0("__getstate__")?;
+                Ok((obj.get_type(), args, state).to_object(py))
+            }
+        }
+    };
+    let methods = crate::pyimpl::build_py_methods(&mut pickle_impl)?;
+
+    Ok(quote! {
+        #pickle_impl
+
+        #methods
+    })
+}
+
 fn check_generics(class: &mut syn::ItemStruct) -> syn::Result<()> {
     if class.generics.params.is_empty() {
         Ok(())
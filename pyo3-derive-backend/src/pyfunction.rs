@@ -17,10 +17,19 @@ pub enum Argument {
     Kwarg(syn::Path, String),
 }
 
+/// The runtime an `async fn` is spawned on, from `async_runtime = "..."`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AsyncRuntime {
+    Tokio,
+}
+
 /// The attributes of the pyfunction macro
 #[derive(Default)]
 pub struct PyFunctionAttr {
     pub arguments: Vec<Argument>,
+    /// Whether the function is called with the GIL released, from `allow_threads`
+    pub allow_threads: bool,
+    pub async_runtime: Option<AsyncRuntime>,
     has_kw: bool,
     has_varargs: bool,
     has_kwargs: bool,
@@ -45,7 +54,21 @@ impl PyFunctionAttr {
 
     pub fn add_item(&mut self, item: &NestedMeta) -> syn::Result<()> {
         match item {
+            NestedMeta::Meta(syn::Meta::Path(ref ident)) if ident.is_ident("allow_threads") => {
+                self.allow_threads = true;
+            }
             NestedMeta::Meta(syn::Meta::Path(ref ident)) => self.add_work(item, ident)?,
+            NestedMeta::Meta(syn::Meta::NameValue(ref nv)) if nv.path.is_ident("async_runtime") => {
+                self.async_runtime = match nv.lit {
+                    syn::Lit::Str(ref lit) if lit.value() == "tokio" => Some(AsyncRuntime::Tokio),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &nv.lit,
+                            "Unsupported async runtime, expected \"tokio\"",
+                        ))
+                    }
+                };
+            }
             NestedMeta::Meta(syn::Meta::NameValue(ref nv)) => {
                 self.add_name_value(item, nv)?;
             }
@@ -234,12 +257,12 @@ pub fn parse_name_attribute(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Opti
 pub fn build_py_function(ast: &mut syn::ItemFn, args: PyFunctionAttr) -> syn::Result<TokenStream> {
     let python_name =
         parse_name_attribute(&mut ast.attrs)?.unwrap_or_else(|| ast.sig.ident.unraw());
-    Ok(add_fn_to_module(ast, python_name, args.arguments))
+    Ok(add_fn_to_module(ast, python_name, args))
 }
 
 #[cfg(test)]
 mod test {
-    use super::{Argument, PyFunctionAttr};
+    use super::{Argument, AsyncRuntime, PyFunctionAttr};
     use proc_macro2::TokenStream;
     use quote::quote;
     use syn::parse_quote;
@@ -257,6 +280,29 @@ mod test {
         assert!(items(quote! {test, kwargs="**", args}).is_err());
     }
 
+    #[test]
+    fn test_allow_threads() {
+        let attr: PyFunctionAttr = syn::parse2(quote! {test1, allow_threads}).unwrap();
+        assert!(attr.allow_threads);
+        assert!(attr.arguments == vec![Argument::Arg(parse_quote! {test1}, None)]);
+
+        let attr: PyFunctionAttr = syn::parse2(quote! {test1}).unwrap();
+        assert!(!attr.allow_threads);
+    }
+
+    #[test]
+    fn test_async_runtime() {
+        let attr: PyFunctionAttr = syn::parse2(quote! {test1, async_runtime = "tokio"}).unwrap();
+        assert_eq!(attr.async_runtime, Some(AsyncRuntime::Tokio));
+        assert!(attr.arguments == vec![Argument::Arg(parse_quote! {test1}, None)]);
+
+        let attr: PyFunctionAttr = syn::parse2(quote! {test1}).unwrap();
+        assert_eq!(attr.async_runtime, None);
+
+        assert!(items(quote! {async_runtime = "async-std"}).is_err());
+        assert!(items(quote! {async_runtime = 1}).is_err());
+    }
+
     #[test]
     fn test_simple_args() {
         let args = items(quote! {test1, test2, test3="None"}).unwrap();
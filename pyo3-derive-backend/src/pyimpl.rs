@@ -24,8 +24,16 @@ pub fn impl_methods(ty: &syn::Type, impls: &mut Vec<syn::ImplItem>) -> syn::Resu
     // get method names in impl block
     let mut methods = Vec::new();
     for iimpl in impls.iter_mut() {
-        if let syn::ImplItem::Method(ref mut meth) = iimpl {
-            methods.push(pymethod::gen_py_method(ty, &mut meth.sig, &mut meth.attrs)?);
+        match iimpl {
+            syn::ImplItem::Method(ref mut meth) => {
+                methods.push(pymethod::gen_py_method(ty, &mut meth.sig, &mut meth.attrs)?);
+            }
+            syn::ImplItem::Const(ref mut konst) => {
+                if let Some(meth) = pymethod::gen_py_const(ty, &konst.ident, &mut konst.attrs) {
+                    methods.push(meth);
+                }
+            }
+            _ => (),
         }
     }
 
@@ -2,7 +2,9 @@
 use crate::method::{FnArg, FnSpec, FnType};
 use crate::utils;
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
 
 pub fn gen_py_method(
     cls: &syn::Type,
@@ -12,17 +14,55 @@ pub fn gen_py_method(
     check_generic(sig)?;
     let spec = FnSpec::parse(sig, &mut *meth_attrs, true)?;
 
-    Ok(match spec.tp {
+    let tokens = match spec.tp {
         FnType::Fn => impl_py_method_def(&spec, &impl_wrap(cls, &spec, true)),
         FnType::PySelf(ref self_ty) => {
             impl_py_method_def(&spec, &impl_wrap_pyslf(cls, &spec, self_ty, true))
         }
         FnType::FnNew => impl_py_method_def_new(&spec, &impl_wrap_new(cls, &spec)),
+        FnType::FnInit => impl_py_method_def_init(&spec, &impl_wrap_init(cls, &spec)),
         FnType::FnCall => impl_py_method_def_call(&spec, &impl_wrap(cls, &spec, false)),
         FnType::FnClass => impl_py_method_def_class(&spec, &impl_wrap_class(cls, &spec)),
         FnType::FnStatic => impl_py_method_def_static(&spec, &impl_wrap_static(cls, &spec)),
+        FnType::ClassAttribute => {
+            impl_py_method_class_attribute(&spec, &impl_wrap_class_attribute(cls, &spec))
+        }
         FnType::Getter => impl_py_getter_def(&spec, &impl_wrap_getter(cls, &spec)?),
         FnType::Setter => impl_py_setter_def(&spec, &impl_wrap_setter(cls, &spec)?),
+    };
+    let tokens = if spec.mut_self {
+        let assert_mutable = impl_assert_mutable(cls, &sig.inputs[0]);
+        quote! {{ #assert_mutable #tokens }}
+    } else {
+        tokens
+    };
+    utils::remove_arg_attrs(sig);
+    Ok(tokens)
+}
+
+/// Generates the class attribute for a `#[classattr]` associated constant
+pub fn gen_py_const(
+    cls: &syn::Type,
+    name: &syn::Ident,
+    attrs: &mut Vec<syn::Attribute>,
+) -> Option<TokenStream> {
+    let position = attrs
+        .iter()
+        .position(|attr| attr.path.is_ident("classattr"))?;
+    attrs.remove(position);
+
+    let python_name = name.unraw();
+    Some(quote! {
+        pyo3::class::PyMethodDefType::ClassAttribute({
+            fn __wrap(py: pyo3::Python) -> pyo3::PyObject {
+                pyo3::IntoPy::into_py(#cls::#name, py)
+            }
+
+            pyo3::class::PyClassAttributeDef {
+                name: stringify!(#python_name),
+                meth: __wrap,
+            }
+        })
     })
 }
 
@@ -45,7 +85,7 @@ fn check_generic(sig: &syn::Signature) -> syn::Result<()> {
 /// Generate function wrapper (PyCFunction, PyCFunctionWithKeywords)
 pub fn impl_wrap(cls: &syn::Type, spec: &FnSpec<'_>, noargs: bool) -> TokenStream {
     let body = impl_call(cls, &spec);
-    let slf = impl_self(&quote! { &mut #cls });
+    let slf = impl_borrow_self(cls, spec, quote! { ::std::ptr::null_mut() });
     impl_wrap_common(cls, spec, noargs, slf, body)
 }
 
@@ -123,6 +163,7 @@ pub fn impl_proto_wrap(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
     let python_name = &spec.python_name;
     let cb = impl_call(cls, &spec);
     let body = impl_arg_params(&spec, cb);
+    let slf = impl_borrow_self(cls, spec, quote! { ::std::ptr::null_mut() });
 
     quote! {
         #[allow(unused_mut)]
@@ -134,7 +175,7 @@ pub fn impl_proto_wrap(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
             const _LOCATION: &'static str = concat!(stringify!(#cls),".",stringify!(#python_name),"()");
             let _py = pyo3::Python::assume_gil_acquired();
             let _pool = pyo3::GILPool::new(_py);
-            let _slf = _py.mut_from_borrowed_ptr::<#cls>(_slf);
+            #slf
             let _args = _py.from_borrowed_ptr::<pyo3::types::PyTuple>(_args);
             let _kwargs: Option<&pyo3::types::PyDict> = _py.from_borrowed_ptr_or_opt(_kwargs);
 
@@ -175,7 +216,7 @@ pub fn impl_wrap_new(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
 
             #body
 
-            match _result.and_then(|init| pyo3::PyClassInitializer::from(init).create_shell(_py)) {
+            match _result.and_then(|init| pyo3::PyClassInitializer::from(init).create_shell_from_subtype(_py, _cls)) {
                 Ok(slf) => slf as _,
                 Err(e) => e.restore_and_null(_py),
             }
@@ -183,6 +224,40 @@ pub fn impl_wrap_new(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
     }
 }
 
+/// Generate the `tp_init` wrapper for a `#[init]` method
+pub fn impl_wrap_init(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
+    let python_name = &spec.python_name;
+    let cb = impl_call(cls, &spec);
+    let body = impl_arg_params(&spec, cb);
+    let slf = impl_borrow_self(cls, spec, quote! { -1 });
+
+    quote! {
+        #[allow(unused_mut)]
+        unsafe extern "C" fn __wrap(
+            _slf: *mut pyo3::ffi::PyObject,
+            _args: *mut pyo3::ffi::PyObject,
+            _kwargs: *mut pyo3::ffi::PyObject) -> ::std::os::raw::c_int
+        {
+            const _LOCATION: &'static str = concat!(stringify!(#cls),".",stringify!(#python_name),"()");
+            let _py = pyo3::Python::assume_gil_acquired();
+            let _pool = pyo3::GILPool::new(_py);
+            #slf
+            let _args = _py.from_borrowed_ptr::<pyo3::types::PyTuple>(_args);
+            let _kwargs: Option<&pyo3::types::PyDict> = _py.from_borrowed_ptr_or_opt(_kwargs);
+
+            #body
+
+            match _result {
+                Ok(()) => 0,
+                Err(e) => {
+                    e.restore(_py);
+                    -1
+                }
+            }
+        }
+    }
+}
+
 /// Generate class method wrapper (PyCFunction, PyCFunctionWithKeywords)
 pub fn impl_wrap_class(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
     let name = &spec.name;
@@ -244,6 +319,17 @@ pub fn impl_wrap_static(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
     }
 }
 
+/// Generate a wrapper for initialization of a class attribute
+pub fn impl_wrap_class_attribute(cls: &syn::Type, spec: &FnSpec<'_>) -> TokenStream {
+    let name = &spec.name;
+
+    quote! {
+        fn __wrap(py: pyo3::Python) -> pyo3::PyObject {
+            pyo3::IntoPy::into_py(#cls::#name(), py)
+        }
+    }
+}
+
 /// Generate functiona wrapper (PyCFunction, PyCFunctionWithKeywords)
 pub(crate) fn impl_wrap_getter(cls: &syn::Type, spec: &FnSpec) -> syn::Result<TokenStream> {
     let takes_py = match &*spec.args {
@@ -265,6 +351,7 @@ pub(crate) fn impl_wrap_getter(cls: &syn::Type, spec: &FnSpec) -> syn::Result<To
     } else {
         quote! { _slf.#name() }
     };
+    let slf = impl_borrow_self(cls, spec, quote! { ::std::ptr::null_mut() });
 
     Ok(quote! {
         unsafe extern "C" fn __wrap(
@@ -274,7 +361,7 @@ pub(crate) fn impl_wrap_getter(cls: &syn::Type, spec: &FnSpec) -> syn::Result<To
 
             let _py = pyo3::Python::assume_gil_acquired();
             let _pool = pyo3::GILPool::new(_py);
-            let _slf = _py.mut_from_borrowed_ptr::<#cls>(_slf);
+            #slf
 
             let result = pyo3::derive_utils::IntoPyResult::into_py_result(#fncall);
 
@@ -311,6 +398,7 @@ pub(crate) fn impl_wrap_setter(cls: &syn::Type, spec: &FnSpec<'_>) -> syn::Resul
             ))
         }
     };
+    let slf = impl_borrow_self(cls, spec, quote! { -1 });
 
     Ok(quote! {
         #[allow(unused_mut)]
@@ -321,7 +409,7 @@ pub(crate) fn impl_wrap_setter(cls: &syn::Type, spec: &FnSpec<'_>) -> syn::Resul
             const _LOCATION: &'static str = concat!(stringify!(#cls),".",stringify!(#python_name),"()");
             let _py = pyo3::Python::assume_gil_acquired();
             let _pool = pyo3::GILPool::new(_py);
-            let _slf = _py.mut_from_borrowed_ptr::<#cls>(_slf);
+            #slf
             let _value = _py.from_borrowed_ptr(_value);
 
             let _result = match <#val_ty as pyo3::FromPyObject>::extract(_value) {
@@ -360,6 +448,50 @@ fn impl_self<T: quote::ToTokens>(self_ty: &T) -> TokenStream {
     }
 }
 
+/// Borrows the receiver from its `PyCell`, mutably for methods taking `&mut self`
+///
+/// If the value is already borrowed, the wrapper raises the error of `try_borrow` or
+/// `try_borrow_mut` and returns `error_ret`.
+fn impl_borrow_self(cls: &syn::Type, spec: &FnSpec<'_>, error_ret: TokenStream) -> TokenStream {
+    let (slf, borrow) = if spec.mut_self {
+        (quote! { mut _slf }, quote! { try_borrow_mut })
+    } else {
+        (quote! { _slf }, quote! { try_borrow })
+    };
+    quote! {
+        let _cell: &pyo3::PyCell<#cls> = pyo3::FromPyPointer::from_borrowed_ptr(_py, _slf);
+        let #slf = match _cell.#borrow() {
+            Ok(slf) => slf,
+            Err(e) => {
+                e.restore(_py);
+                return #error_ret;
+            }
+        };
+    }
+}
+
+/// Rejects a method taking `&mut self` or `&mut PyCell<Self>` in a `#[pyclass(frozen)]` at
+/// compile time, as only the classes that aren't frozen implement `PyMutableClass`
+pub(crate) fn impl_assert_mutable(cls: &syn::Type, receiver: &syn::FnArg) -> TokenStream {
+    // Point the error at the receiver instead of the type of the impl block
+    let span = receiver.span();
+    let cls: TokenStream = cls
+        .to_token_stream()
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(span);
+            token
+        })
+        .collect();
+    quote_spanned! {span=>
+        const _: () = {
+            fn __assert_mutable() {
+                pyo3::derive_utils::assert_mutable::<#cls>();
+            }
+        };
+    }
+}
+
 /// Converts a bool to "true" or "false"
 fn bool_to_ident(condition: bool) -> syn::Ident {
     if condition {
@@ -468,8 +600,19 @@ fn impl_arg_param(
             let #arg_name = <#ty as pyo3::FromPyObject>::extract(_args.as_ref())?;
         };
     } else if spec.is_kwargs(&name) {
-        return quote! {
-            let #arg_name = _kwargs;
+        return if let Some(inner) = arg.optional {
+            quote! {
+                let #arg_name = match _kwargs {
+                    Some(_kwargs) => Some(<#inner as pyo3::FromPyObject>::extract(_kwargs.as_ref())?),
+                    None => None,
+                };
+            }
+        } else {
+            quote! {
+                let #arg_name = <#ty as pyo3::FromPyObject>::extract(
+                    _kwargs.unwrap_or_else(|| pyo3::types::PyDict::new(_py)).as_ref()
+                )?;
+            }
         };
     }
     let arg_value = quote!(output[#option_pos]);
@@ -565,6 +708,23 @@ pub fn impl_py_method_def_new(spec: &FnSpec, wrapper: &TokenStream) -> TokenStre
     }
 }
 
+pub fn impl_py_method_def_init(spec: &FnSpec, wrapper: &TokenStream) -> TokenStream {
+    let python_name = &spec.python_name;
+    let doc = &spec.doc;
+    quote! {
+        pyo3::class::PyMethodDefType::Init({
+            #wrapper
+
+            pyo3::class::PyMethodDef {
+                ml_name: stringify!(#python_name),
+                ml_meth: pyo3::class::PyMethodType::PyInitFunc(__wrap),
+                ml_flags: pyo3::ffi::METH_VARARGS | pyo3::ffi::METH_KEYWORDS,
+                ml_doc: #doc,
+            }
+        })
+    }
+}
+
 pub fn impl_py_method_def_class(spec: &FnSpec, wrapper: &TokenStream) -> TokenStream {
     let python_name = &spec.python_name;
     let doc = &spec.doc;
@@ -600,6 +760,20 @@ pub fn impl_py_method_def_static(spec: &FnSpec, wrapper: &TokenStream) -> TokenS
     }
 }
 
+pub fn impl_py_method_class_attribute(spec: &FnSpec, wrapper: &TokenStream) -> TokenStream {
+    let python_name = &spec.python_name;
+    quote! {
+        pyo3::class::PyMethodDefType::ClassAttribute({
+            #wrapper
+
+            pyo3::class::PyClassAttributeDef {
+                name: stringify!(#python_name),
+                meth: __wrap,
+            }
+        })
+    }
+}
+
 pub fn impl_py_method_def_call(spec: &FnSpec, wrapper: &TokenStream) -> TokenStream {
     let python_name = &spec.python_name;
     let doc = &spec.doc;
@@ -63,6 +63,15 @@ fn impl_proto_impl(
 
     for iimpl in impls.iter_mut() {
         if let syn::ImplItem::Method(ref mut met) = iimpl {
+            let is_proto_method = proto.methods.iter().any(|m| met.sig.ident == m.name())
+                || proto.py_methods.iter().any(|m| met.sig.ident == m.name);
+            if let Some(receiver) = met.sig.inputs.first() {
+                if let syn::FnArg::Receiver(recv) = receiver {
+                    if is_proto_method && recv.reference.is_some() && recv.mutability.is_some() {
+                        pymethod::impl_assert_mutable(ty, receiver).to_tokens(&mut tokens);
+                    }
+                }
+            }
             for m in proto.methods {
                 if met.sig.ident == m.name() {
                     impl_method_proto(ty, &mut met.sig, m).to_tokens(&mut tokens);
@@ -21,6 +21,73 @@ pub fn if_type_is_python(ty: &syn::Type) -> bool {
     }
 }
 
+/// The options of a function argument given with `#[pyo3(...)]`.
+#[derive(Default)]
+pub struct ArgAttributes {
+    /// `#[pyo3(kw_only)]`: the argument can only be passed by keyword
+    pub kw_only: bool,
+    /// `#[pyo3(args)]`: the argument captures the extra positional arguments, like `*args`
+    pub var_args: bool,
+    /// `#[pyo3(kwargs)]`: the argument captures the extra keyword arguments, like `**kwargs`
+    pub var_kwargs: bool,
+}
+
+/// Parses the `#[pyo3(...)]` attributes of a function argument.
+pub fn parse_arg_attrs(attrs: &[syn::Attribute]) -> syn::Result<ArgAttributes> {
+    let mut arg_attrs = ArgAttributes::default();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("pyo3")) {
+        if let syn::Meta::List(ref list) = attr.parse_meta()? {
+            for meta in list.nested.iter() {
+                match meta {
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path))
+                        if path.is_ident("kw_only") =>
+                    {
+                        arg_attrs.kw_only = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if path.is_ident("args") => {
+                        arg_attrs.var_args = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(ref path)) if path.is_ident("kwargs") => {
+                        arg_attrs.var_kwargs = true;
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            meta,
+                            "Only kw_only, args and kwargs are supported on arguments",
+                        ))
+                    }
+                }
+            }
+        } else {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "Expected #[pyo3(kw_only)], #[pyo3(args)] or #[pyo3(kwargs)]",
+            ));
+        }
+        let count = [arg_attrs.kw_only, arg_attrs.var_args, arg_attrs.var_kwargs]
+            .iter()
+            .filter(|&&set| set)
+            .count();
+        if count > 1 {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "kw_only, args and kwargs can not be combined",
+            ));
+        }
+    }
+    Ok(arg_attrs)
+}
+
+/// Removes the `#[pyo3(...)]` attributes from the arguments of a function once they have been
+/// parsed, since rustc doesn't know them.
+pub fn remove_arg_attrs(sig: &mut syn::Signature) {
+    for input in sig.inputs.iter_mut() {
+        if let syn::FnArg::Typed(ref mut pat_type) = input {
+            pat_type.attrs.retain(|attr| !attr.path.is_ident("pyo3"));
+        }
+    }
+}
+
 pub fn is_text_signature_attr(attr: &syn::Attribute) -> bool {
     attr.path.is_ident("text_signature")
 }
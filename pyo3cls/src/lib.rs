@@ -5,8 +5,8 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use pyo3_derive_backend::{
-    build_py_class, build_py_function, build_py_methods, build_py_proto, get_doc,
-    process_functions_in_module, py_init, PyClassArgs, PyFunctionAttr,
+    build_py_class, build_py_function, build_py_gc_traverse, build_py_methods, build_py_proto,
+    get_doc, process_functions_in_module, py_init, PyClassArgs, PyFunctionAttr, PyModuleAttr,
 };
 use quote::quote;
 use syn::parse_macro_input;
@@ -17,11 +17,8 @@ use syn::parse_macro_input;
 pub fn pymodule(attr: TokenStream, input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as syn::ItemFn);
 
-    let modname = if attr.is_empty() {
-        ast.sig.ident.clone()
-    } else {
-        parse_macro_input!(attr as syn::Ident)
-    };
+    let attr = parse_macro_input!(attr as PyModuleAttr);
+    let modname = attr.name.unwrap_or_else(|| ast.sig.ident.clone());
 
     process_functions_in_module(&mut ast);
 
@@ -30,7 +27,7 @@ pub fn pymodule(attr: TokenStream, input: TokenStream) -> TokenStream {
         Err(err) => return err.to_compile_error().into(),
     };
 
-    let expanded = py_init(&ast.sig.ident, &modname, doc);
+    let expanded = py_init(&ast.sig.ident, &modname, doc, attr.parent.as_ref());
 
     quote!(
         #ast
@@ -89,3 +86,11 @@ pub fn pyfunction(attr: TokenStream, input: TokenStream) -> TokenStream {
     )
     .into()
 }
+
+#[proc_macro_derive(PyGCTraverse)]
+pub fn py_gc_traverse(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    build_py_gc_traverse(&ast)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
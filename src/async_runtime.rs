@@ -0,0 +1,74 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Async runtimes that `#[pyfunction(async_runtime = "...")]` spawns async functions on.
+
+/// The tokio runtime, enabled with the `tokio` feature.
+///
+/// An `async fn` exported with `#[pyfunction(async_runtime = "tokio")]` is spawned on a
+/// multi-threaded runtime, which is started when the first such function is called and then
+/// runs for the rest of the process. The Python coroutine returned by the call awaits the
+/// output of the task.
+///
+/// ```ignore
+/// use pyo3::prelude::*;
+///
+/// #[pyfunction(async_runtime = "tokio")]
+/// async fn sleep_for(secs: u64) -> u64 {
+///     tokio::time::delay_for(std::time::Duration::from_secs(secs)).await;
+///     secs
+/// }
+/// ```
+pub mod tokio {
+    use crate::derive_utils::IntoPyResult;
+    use crate::exceptions::RuntimeError;
+    use crate::PyResult;
+    use ::tokio::runtime::{Handle, Runtime};
+    use ::tokio::task::JoinHandle;
+    use once_cell::sync::OnceCell;
+    use std::future::Future;
+    use std::marker::PhantomData;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// Gets the handle of the runtime, starting the runtime if it isn't running yet.
+    ///
+    /// This can be used to spawn other tasks on the same runtime.
+    pub fn handle() -> &'static Handle {
+        static HANDLE: OnceCell<Handle> = OnceCell::new();
+        HANDLE.get_or_init(|| {
+            let runtime = Runtime::new().expect("Failed to start the tokio runtime");
+            let handle = runtime.handle().clone();
+            // The runtime is never shut down, since the returned handle is `'static`
+            std::mem::forget(runtime);
+            handle
+        })
+    }
+
+    /// Spawns `future` on the runtime, and returns a future of its output.
+    ///
+    /// A panic of the task is returned as a `RuntimeError`.
+    pub fn spawn<F, T>(future: F) -> Spawned<F::Output, T>
+    where
+        F: Future + Send + 'static,
+        F::Output: IntoPyResult<T> + Send + 'static,
+    {
+        Spawned(handle().spawn(future), PhantomData)
+    }
+
+    /// The future returned by [spawn].
+    pub struct Spawned<R, T>(JoinHandle<R>, PhantomData<fn() -> T>);
+
+    impl<R, T> Future for Spawned<R, T>
+    where
+        R: IntoPyResult<T>,
+    {
+        type Output = PyResult<T>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<PyResult<T>> {
+            Pin::new(&mut self.0).poll(cx).map(|result| match result {
+                Ok(output) => output.into_py_result(),
+                Err(err) => Err(RuntimeError::py_err(err.to_string())),
+            })
+        }
+    }
+}
@@ -13,12 +13,12 @@ use crate::class::methods::PyMethodDef;
 use crate::err::{PyErr, PyResult};
 use crate::ffi;
 use crate::objectprotocol::ObjectProtocol;
-use crate::type_object::PyTypeInfo;
+use crate::pyclass::{PyCell, PyClass};
 use crate::types::PyAny;
-use crate::FromPyObject;
 use crate::IntoPyPointer;
 use crate::Python;
 use crate::{exceptions, IntoPy, PyObject};
+use crate::{FromPyObject, FromPyPointer};
 use std::os::raw::c_int;
 use std::ptr;
 
@@ -35,7 +35,7 @@ pub enum CompareOp {
 
 /// Basic python class customization
 #[allow(unused_variables)]
-pub trait PyObjectProtocol<'p>: PyTypeInfo {
+pub trait PyObjectProtocol<'p>: PyClass {
     fn __getattr__(&'p self, name: Self::Name) -> Self::Result
     where
         Self: PyObjectGetAttrProtocol<'p>,
@@ -99,12 +99,47 @@ pub trait PyObjectProtocol<'p>: PyTypeInfo {
         unimplemented!()
     }
 
+    fn __fspath__(&'p self) -> Self::Result
+    where
+        Self: PyObjectFspathProtocol<'p>,
+    {
+        unimplemented!()
+    }
+
     fn __richcmp__(&'p self, other: Self::Other, op: CompareOp) -> Self::Result
     where
         Self: PyObjectRichcmpProtocol<'p>,
     {
         unimplemented!()
     }
+
+    /// Called with the arguments of the constructor call after `__new__`.
+    ///
+    /// If the class also has a method marked with `#[init]`, that method is used instead.
+    fn __init__(slf: &PyCell<Self>, args: Self::Args, kwargs: Option<Self::Kwargs>) -> Self::Result
+    where
+        Self: PyObjectInitProtocol<'p>,
+    {
+        unimplemented!()
+    }
+
+    /// Called when the object is about to be destroyed, see `tp_finalize`.
+    ///
+    /// Errors can't be propagated from a finalizer, so they are reported through
+    /// `sys.unraisablehook` instead, like errors raised in a Python `__del__`.
+    /// `__del__` must not panic; catch any panics and turn them into errors
+    /// (or print them with `PyErr::print`), since unwinding into Python aborts the process.
+    ///
+    /// The finalizer may resurrect the object, e.g. by storing a reference to it somewhere.
+    /// In that case the object is not deallocated, and Python runs the finalizer again
+    /// when the object becomes unreachable the next time, unless the class supports GC
+    /// (a finalizer of a GC object only runs once).
+    fn __del__(&'p mut self) -> Self::Result
+    where
+        Self: PyObjectDelProtocol<'p>,
+    {
+        unimplemented!()
+    }
 }
 
 pub trait PyObjectGetAttrProtocol<'p>: PyObjectProtocol<'p> {
@@ -144,11 +179,23 @@ pub trait PyObjectBytesProtocol<'p>: PyObjectProtocol<'p> {
     type Success: IntoPy<PyObject>;
     type Result: Into<PyResult<Self::Success>>;
 }
+pub trait PyObjectFspathProtocol<'p>: PyObjectProtocol<'p> {
+    type Success: IntoPy<PyObject>;
+    type Result: Into<PyResult<Self::Success>>;
+}
 pub trait PyObjectRichcmpProtocol<'p>: PyObjectProtocol<'p> {
     type Other: FromPyObject<'p>;
     type Success: IntoPy<PyObject>;
     type Result: Into<PyResult<Self::Success>>;
 }
+pub trait PyObjectInitProtocol<'p>: PyObjectProtocol<'p> + PyClass {
+    type Args: FromPyObject<'p>;
+    type Kwargs: FromPyObject<'p>;
+    type Result: Into<PyResult<()>>;
+}
+pub trait PyObjectDelProtocol<'p>: PyObjectProtocol<'p> {
+    type Result: Into<PyResult<()>>;
+}
 
 #[doc(hidden)]
 pub trait PyObjectProtocolImpl {
@@ -180,6 +227,9 @@ where
         if let Some(def) = <Self as BytesProtocolImpl>::__bytes__() {
             methods.push(def)
         }
+        if let Some(def) = <Self as FspathProtocolImpl>::__fspath__() {
+            methods.push(def)
+        }
         if let Some(def) = <Self as UnicodeProtocolImpl>::__unicode__() {
             methods.push(def)
         }
@@ -192,6 +242,8 @@ where
         type_object.tp_getattro = Self::tp_getattro();
         type_object.tp_richcompare = Self::tp_richcompare();
         type_object.tp_setattro = tp_setattro_impl::tp_setattro::<Self>();
+        type_object.tp_init = Self::tp_init();
+        type_object.tp_finalize = Self::tp_finalize();
     }
     fn nb_bool_fn() -> Option<ffi::inquiry> {
         Self::nb_bool()
@@ -237,11 +289,12 @@ where
                 return existing;
             }
 
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
+            let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.try_borrow();
             let arg = py.from_borrowed_ptr::<crate::types::PyAny>(arg);
 
             let result = match arg.extract() {
-                Ok(arg) => slf.__getattr__(arg).into(),
+                Ok(arg) => py_call_borrowed!(slf, __getattr__(arg)),
                 Err(e) => Err(e),
             };
             crate::callback::cb_convert(PyObjectCallbackConverter, py, result)
@@ -422,6 +475,19 @@ where
     }
 }
 
+#[doc(hidden)]
+pub trait FspathProtocolImpl {
+    fn __fspath__() -> Option<PyMethodDef>;
+}
+impl<'p, T> FspathProtocolImpl for T
+where
+    T: PyObjectProtocol<'p>,
+{
+    default fn __fspath__() -> Option<PyMethodDef> {
+        None
+    }
+}
+
 #[doc(hidden)]
 pub trait UnicodeProtocolImpl {
     fn __unicode__() -> Option<PyMethodDef>;
@@ -513,12 +579,13 @@ where
         {
             let py = Python::assume_gil_acquired();
             let _pool = crate::GILPool::new(py);
-            let slf = py.from_borrowed_ptr::<T>(slf);
+            let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.try_borrow();
             let arg = py.from_borrowed_ptr::<PyAny>(arg);
 
             let res = match extract_op(op) {
                 Ok(op) => match arg.extract() {
-                    Ok(arg) => slf.__richcmp__(arg, op).into(),
+                    Ok(arg) => py_call_borrowed!(slf, __richcmp__(arg, op)),
                     Err(e) => Err(e),
                 },
                 Err(e) => Err(e),
@@ -535,6 +602,98 @@ where
     }
 }
 
+trait InitProtocolImpl {
+    fn tp_init() -> Option<ffi::initproc>;
+}
+impl<'p, T> InitProtocolImpl for T
+where
+    T: PyObjectProtocol<'p>,
+{
+    default fn tp_init() -> Option<ffi::initproc> {
+        None
+    }
+}
+impl<T> InitProtocolImpl for T
+where
+    T: for<'p> PyObjectInitProtocol<'p>,
+{
+    fn tp_init() -> Option<ffi::initproc> {
+        unsafe extern "C" fn wrap<T>(
+            slf: *mut ffi::PyObject,
+            args: *mut ffi::PyObject,
+            kwargs: *mut ffi::PyObject,
+        ) -> c_int
+        where
+            T: for<'p> PyObjectInitProtocol<'p>,
+        {
+            let py = Python::assume_gil_acquired();
+            let _pool = crate::GILPool::new(py);
+            let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+            let args = py.from_borrowed_ptr::<PyAny>(args);
+            let kwargs = py.from_borrowed_ptr_or_opt::<PyAny>(kwargs);
+
+            let res = match args.extract() {
+                Ok(args) => match kwargs.map(ObjectProtocol::extract).transpose() {
+                    Ok(kwargs) => T::__init__(slf, args, kwargs).into(),
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            };
+            match res {
+                Ok(()) => 0,
+                Err(e) => {
+                    e.restore(py);
+                    -1
+                }
+            }
+        }
+        Some(wrap::<T>)
+    }
+}
+
+trait DelProtocolImpl {
+    fn tp_finalize() -> Option<ffi::destructor>;
+}
+impl<'p, T> DelProtocolImpl for T
+where
+    T: PyObjectProtocol<'p>,
+{
+    default fn tp_finalize() -> Option<ffi::destructor> {
+        None
+    }
+}
+impl<T> DelProtocolImpl for T
+where
+    T: for<'p> PyObjectDelProtocol<'p>,
+{
+    fn tp_finalize() -> Option<ffi::destructor> {
+        unsafe extern "C" fn wrap<T>(slf: *mut ffi::PyObject)
+        where
+            T: for<'p> PyObjectDelProtocol<'p>,
+        {
+            let py = Python::assume_gil_acquired();
+            let _pool = crate::GILPool::new(py);
+
+            // A finalizer must leave the current exception, if any, untouched
+            let mut ptype = ptr::null_mut();
+            let mut pvalue = ptr::null_mut();
+            let mut ptraceback = ptr::null_mut();
+            ffi::PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback);
+
+            let slf1: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf1 = slf1.try_borrow_mut();
+            let res: PyResult<()> = py_call_borrowed!(slf1, __del__());
+            if let Err(e) = res {
+                e.restore(py);
+                ffi::PyErr_WriteUnraisable(slf);
+            }
+
+            ffi::PyErr_Restore(ptype, pvalue, ptraceback);
+        }
+        Some(wrap::<T>)
+    }
+}
+
 fn extract_op(op: c_int) -> PyResult<CompareOp> {
     match op {
         ffi::Py_LT => Ok(CompareOp::Lt),
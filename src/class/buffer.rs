@@ -5,17 +5,42 @@
 //! For more information check [buffer protocol](https://docs.python.org/3/c-api/buffer.html)
 //! c-api
 use crate::callback::UnitCallbackConverter;
-use crate::err::PyResult;
+use crate::err::{PyErr, PyResult};
 use crate::ffi;
-use crate::type_object::PyTypeInfo;
-use std::os::raw::c_int;
+use crate::pyclass::{PyCell, PyClass};
+use crate::{FromPyPointer, Python};
+use std::os::raw::{c_int, c_void};
 
 /// Buffer protocol interface
 ///
 /// For more information check [buffer protocol](https://docs.python.org/3/c-api/buffer.html)
 /// c-api
+///
+/// `view.obj` is set to a new reference to the exporting object before `bf_getbuffer` is
+/// called, which keeps the object alive until the buffer is released. [fill_view_from_slice]
+/// and [fill_view_from_mut_slice] fill the rest of the view for a slice of bytes:
+///
+/// ```
+/// # #![feature(specialization)]
+/// use pyo3::class::buffer::fill_view_from_slice;
+/// use pyo3::prelude::*;
+/// use pyo3::{ffi, PyBufferProtocol};
+/// use std::os::raw::c_int;
+///
+/// #[pyclass]
+/// struct Bytes {
+///     data: Vec<u8>,
+/// }
+///
+/// #[pyproto]
+/// impl PyBufferProtocol for Bytes {
+///     fn bf_getbuffer(&self, view: *mut ffi::Py_buffer, flags: c_int) -> PyResult<()> {
+///         unsafe { fill_view_from_slice(view, flags, &self.data) }
+///     }
+/// }
+/// ```
 #[allow(unused_variables)]
-pub trait PyBufferProtocol<'p>: PyTypeInfo {
+pub trait PyBufferProtocol<'p>: PyClass {
     fn bf_getbuffer(&'p self, view: *mut ffi::Py_buffer, flags: c_int) -> Self::Result
     where
         Self: PyBufferGetBufferProtocol<'p>,
@@ -59,7 +84,7 @@ where
     fn tp_as_buffer() -> Option<ffi::PyBufferProcs> {
         Some(ffi::PyBufferProcs {
             bf_getbuffer: Self::cb_bf_getbuffer(),
-            bf_releasebuffer: None,
+            bf_releasebuffer: Self::cb_bf_releasebuffer(),
             ..ffi::PyBufferProcs_INIT
         })
     }
@@ -94,11 +119,134 @@ where
         {
             let py = crate::Python::assume_gil_acquired();
             let _pool = crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
+            let obj = slf;
+            let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.try_borrow();
 
-            let result = slf.bf_getbuffer(arg1, arg2).into();
+            // The consumer may pass an uninitialized view, so `obj` is always overwritten
+            ffi::Py_INCREF(obj);
+            (*arg1).obj = obj;
+            let result: PyResult<()> = py_call_borrowed!(slf, bf_getbuffer(arg1, arg2));
+            if result.is_err() {
+                // A failed request must leave `obj` null
+                ffi::Py_XDECREF((*arg1).obj);
+                (*arg1).obj = std::ptr::null_mut();
+            } else if (*arg1).obj.is_null() {
+                (*arg1).obj = obj;
+            } else if (*arg1).obj != obj {
+                // The implementation stored another object, so our reference isn't needed
+                ffi::Py_DECREF(obj);
+            }
             crate::callback::cb_convert(UnitCallbackConverter, py, result)
         }
         Some(wrap::<T>)
     }
 }
+
+trait PyBufferReleaseBufferProtocolImpl {
+    fn cb_bf_releasebuffer() -> Option<ffi::releasebufferproc>;
+}
+
+impl<'p, T> PyBufferReleaseBufferProtocolImpl for T
+where
+    T: PyBufferProtocol<'p>,
+{
+    default fn cb_bf_releasebuffer() -> Option<ffi::releasebufferproc> {
+        None
+    }
+}
+
+impl<T> PyBufferReleaseBufferProtocolImpl for T
+where
+    T: for<'p> PyBufferReleaseBufferProtocol<'p>,
+{
+    #[inline]
+    fn cb_bf_releasebuffer() -> Option<ffi::releasebufferproc> {
+        unsafe extern "C" fn wrap<T>(slf: *mut ffi::PyObject, arg1: *mut ffi::Py_buffer)
+        where
+            T: for<'p> PyBufferReleaseBufferProtocol<'p>,
+        {
+            let py = crate::Python::assume_gil_acquired();
+            let _pool = crate::GILPool::new(py);
+            let obj = slf;
+            let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.try_borrow();
+
+            // Releasing a buffer can't fail, so errors are reported as unraisable
+            let result: PyResult<()> = py_call_borrowed!(slf, bf_releasebuffer(arg1));
+            if let Err(e) = result {
+                e.restore(py);
+                ffi::PyErr_WriteUnraisable(obj);
+            }
+        }
+        Some(wrap::<T>)
+    }
+}
+
+/// Fills `view` to export `data` as a read-only, one-dimensional buffer of unsigned bytes.
+///
+/// Fails with `BufferError` if the consumer requested a writable buffer.
+///
+/// # Safety
+///
+/// `view` must be the pointer passed to `bf_getbuffer`, and `data` must neither be freed
+/// nor moved while the buffer is exported, e.g. the `Vec` it borrows from must not be resized.
+pub unsafe fn fill_view_from_slice(
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+    data: &[u8],
+) -> PyResult<()> {
+    fill_view(view, flags, data.as_ptr() as *mut c_void, data.len(), true)
+}
+
+/// Fills `view` to export `data` as a writable, one-dimensional buffer of unsigned bytes.
+///
+/// # Safety
+///
+/// The same requirements as for [fill_view_from_slice] apply. In addition, the object must
+/// not access `data` while the buffer is exported, since the consumer may write to it.
+pub unsafe fn fill_view_from_mut_slice(
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+    data: &mut [u8],
+) -> PyResult<()> {
+    fill_view(
+        view,
+        flags,
+        data.as_mut_ptr() as *mut c_void,
+        data.len(),
+        false,
+    )
+}
+
+unsafe fn fill_view(
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+    buf: *mut c_void,
+    len: usize,
+    readonly: bool,
+) -> PyResult<()> {
+    // `PyBuffer_FillInfo` overwrites `obj`, which the `bf_getbuffer` wrapper has already set
+    // to the exporting object
+    let obj = if view.is_null() {
+        std::ptr::null_mut()
+    } else {
+        (*view).obj
+    };
+    let ret = ffi::PyBuffer_FillInfo(
+        view,
+        std::ptr::null_mut(),
+        buf,
+        len as ffi::Py_ssize_t,
+        readonly as c_int,
+        flags,
+    );
+    if !view.is_null() {
+        (*view).obj = obj;
+    }
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(PyErr::fetch(Python::assume_gil_acquired()))
+    }
+}
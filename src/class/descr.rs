@@ -5,40 +5,44 @@
 //! [Python information](
 //! https://docs.python.org/3/reference/datamodel.html#implementing-descriptors)
 
-use crate::callback::{PyObjectCallbackConverter, UnitCallbackConverter};
+use crate::callback::PyObjectCallbackConverter;
 use crate::class::methods::PyMethodDef;
-use crate::err::PyResult;
-use crate::type_object::PyTypeInfo;
-use crate::types::{PyAny, PyType};
-use crate::FromPyObject;
-use crate::{ffi, IntoPy, PyObject};
-use std::os::raw::c_int;
+use crate::err::{PyErr, PyResult};
+use crate::objectprotocol::ObjectProtocol;
+use crate::pyclass::{PyCell, PyClass};
+use crate::types::PyAny;
+use crate::{exceptions, ffi, IntoPy, PyObject};
+use crate::{FromPyObject, FromPyPointer};
 
 /// Descriptor interface
+///
+/// `__get__` receives `None` for `instance` when the descriptor is accessed through the owner
+/// class, so both of its arguments have to be `Option`s.
+/// Deleting the attribute calls `__delete__`, assigning it calls `__set__`.
 #[allow(unused_variables)]
-pub trait PyDescrProtocol<'p>: PyTypeInfo {
-    fn __get__(&'p self, instance: &'p PyAny, owner: Option<&'p PyType>) -> Self::Result
+pub trait PyDescrProtocol<'p>: PyClass {
+    fn __get__(&'p self, instance: Option<Self::Inst>, owner: Option<Self::Owner>) -> Self::Result
     where
         Self: PyDescrGetProtocol<'p>,
     {
         unimplemented!()
     }
 
-    fn __set__(&'p self, instance: &'p PyAny, value: &'p PyAny) -> Self::Result
+    fn __set__(&'p self, instance: Self::Inst, value: Self::Value) -> Self::Result
     where
         Self: PyDescrSetProtocol<'p>,
     {
         unimplemented!()
     }
 
-    fn __delete__(&'p self, instance: &'p PyAny) -> Self::Result
+    fn __delete__(&'p self, instance: Self::Inst) -> Self::Result
     where
         Self: PyDescrDeleteProtocol<'p>,
     {
         unimplemented!()
     }
 
-    fn __set_name__(&'p self, instance: &'p PyAny) -> Self::Result
+    fn __set_name__(&'p mut self, owner: Self::Owner, name: Self::Name) -> Self::Result
     where
         Self: PyDescrSetNameProtocol<'p>,
     {
@@ -65,7 +69,8 @@ pub trait PyDescrDeleteProtocol<'p>: PyDescrProtocol<'p> {
 }
 
 pub trait PyDescrSetNameProtocol<'p>: PyDescrProtocol<'p> {
-    type Inst: FromPyObject<'p>;
+    type Owner: FromPyObject<'p>;
+    type Name: FromPyObject<'p>;
     type Result: Into<PyResult<()>>;
 }
 
@@ -86,54 +91,150 @@ where
     T: for<'p> PyDescrGetProtocol<'p>,
 {
     fn tp_descr_get() -> Option<ffi::descrgetfunc> {
-        py_ternary_func!(
-            PyDescrGetProtocol,
-            T::__get__,
-            T::Success,
-            PyObjectCallbackConverter
-        )
+        unsafe extern "C" fn wrap<T>(
+            slf: *mut ffi::PyObject,
+            instance: *mut ffi::PyObject,
+            owner: *mut ffi::PyObject,
+        ) -> *mut ffi::PyObject
+        where
+            T: for<'p> PyDescrGetProtocol<'p>,
+        {
+            let py = crate::Python::assume_gil_acquired();
+            let _pool = crate::GILPool::new(py);
+            let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.try_borrow();
+            // `instance` is NULL when the descriptor is accessed through the owner class
+            let instance = py.from_borrowed_ptr_or_opt::<PyAny>(instance);
+            let owner = py.from_borrowed_ptr_or_opt::<PyAny>(owner);
+
+            let result = match extract_opt(instance) {
+                Ok(instance) => match extract_opt(owner) {
+                    Ok(owner) => py_call_borrowed!(slf, __get__(instance, owner)),
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            };
+            crate::callback::cb_convert(PyObjectCallbackConverter, py, result)
+        }
+        Some(wrap::<T>)
     }
 }
 
-trait PyDescrSetProtocolImpl {
-    fn tp_descr_set() -> Option<ffi::descrsetfunc>;
-}
-impl<'p, T> PyDescrSetProtocolImpl for T
-where
-    T: PyDescrProtocol<'p>,
-{
-    default fn tp_descr_set() -> Option<ffi::descrsetfunc> {
-        None
+/// Extracts an optional argument, treating both NULL and `None` as missing
+fn extract_opt<'p, T: FromPyObject<'p>>(obj: Option<&'p PyAny>) -> PyResult<Option<T>> {
+    match obj {
+        Some(obj) if !obj.is_none() => obj.extract().map(Some),
+        _ => Ok(None),
     }
 }
-impl<T> PyDescrSetProtocolImpl for T
-where
-    T: for<'p> PyDescrSetProtocol<'p>,
-{
-    fn tp_descr_set() -> Option<ffi::descrsetfunc> {
-        py_ternary_func!(
-            PyDescrSetProtocol,
-            T::__set__,
-            (),
-            UnitCallbackConverter,
-            c_int
-        )
+
+/// A descriptor may support `__set__` and `__delete__`, which share the `tp_descr_set` slot.
+/// The slot is called with a NULL value to delete the attribute.
+mod tp_descr_set_impl {
+    use super::*;
+
+    pub(super) fn tp_descr_set<'p, T: PyDescrProtocol<'p>>() -> Option<ffi::descrsetfunc> {
+        if let Some(set_del) = T::set_del() {
+            Some(set_del)
+        } else if let Some(set) = T::set() {
+            Some(set)
+        } else if let Some(del) = T::del() {
+            Some(del)
+        } else {
+            None
+        }
     }
-}
 
-trait PyDescrDelProtocolImpl {
-    fn __del__() -> Option<PyMethodDef> {
-        None
+    trait Set {
+        fn set() -> Option<ffi::descrsetfunc>;
+    }
+
+    impl<'p, T: PyDescrProtocol<'p>> Set for T {
+        default fn set() -> Option<ffi::descrsetfunc> {
+            None
+        }
     }
+
+    impl<T> Set for T
+    where
+        T: for<'p> PyDescrSetProtocol<'p>,
+    {
+        fn set() -> Option<ffi::descrsetfunc> {
+            py_func_set!(
+                PyDescrSetProtocol,
+                T,
+                __set__,
+                PyErr::new::<exceptions::AttributeError, _>("can't delete attribute"),
+                try_borrow
+            )
+        }
+    }
+
+    trait Del {
+        fn del() -> Option<ffi::descrsetfunc>;
+    }
+
+    impl<'p, T: PyDescrProtocol<'p>> Del for T {
+        default fn del() -> Option<ffi::descrsetfunc> {
+            None
+        }
+    }
+
+    impl<T> Del for T
+    where
+        T: for<'p> PyDescrDeleteProtocol<'p>,
+    {
+        fn del() -> Option<ffi::descrsetfunc> {
+            py_func_del!(
+                PyDescrDeleteProtocol,
+                T,
+                __delete__,
+                PyErr::new::<exceptions::AttributeError, _>("can't set attribute"),
+                try_borrow
+            )
+        }
+    }
+
+    trait SetDel {
+        fn set_del() -> Option<ffi::descrsetfunc>;
+    }
+
+    impl<'p, T: PyDescrProtocol<'p>> SetDel for T {
+        default fn set_del() -> Option<ffi::descrsetfunc> {
+            None
+        }
+    }
+
+    impl<T> SetDel for T
+    where
+        T: for<'p> PyDescrSetProtocol<'p> + for<'p> PyDescrDeleteProtocol<'p>,
+    {
+        fn set_del() -> Option<ffi::descrsetfunc> {
+            py_func_set_del!(
+                PyDescrSetProtocol,
+                PyDescrDeleteProtocol,
+                T,
+                __set__,
+                __delete__,
+                try_borrow
+            )
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait PyDescrSetNameProtocolImpl {
+    fn __set_name__() -> Option<PyMethodDef>;
 }
-impl<'p, T> PyDescrDelProtocolImpl for T where T: PyDescrProtocol<'p> {}
 
-trait PyDescrSetNameProtocolImpl {
-    fn __set_name__() -> Option<PyMethodDef> {
+impl<'p, T> PyDescrSetNameProtocolImpl for T
+where
+    T: PyDescrProtocol<'p>,
+{
+    default fn __set_name__() -> Option<PyMethodDef> {
         None
     }
 }
-impl<'p, T> PyDescrSetNameProtocolImpl for T where T: PyDescrProtocol<'p> {}
 
 #[doc(hidden)]
 pub trait PyDescrProtocolImpl {
@@ -153,10 +254,16 @@ where
     T: PyDescrProtocol<'p>,
 {
     fn methods() -> Vec<PyMethodDef> {
-        Vec::new()
+        let mut methods = Vec::new();
+
+        if let Some(def) = <Self as PyDescrSetNameProtocolImpl>::__set_name__() {
+            methods.push(def)
+        }
+
+        methods
     }
     fn tp_as_descr(type_object: &mut ffi::PyTypeObject) {
         type_object.tp_descr_get = Self::tp_descr_get();
-        type_object.tp_descr_set = Self::tp_descr_set();
+        type_object.tp_descr_set = tp_descr_set_impl::tp_descr_set::<Self>();
     }
 }
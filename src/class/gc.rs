@@ -4,16 +4,19 @@
 //!
 
 use crate::ffi;
-use crate::type_object::PyTypeInfo;
-use crate::AsPyPointer;
-use crate::Python;
+use crate::pyclass::{PyCell, PyClass};
+use crate::{AsPyPointer, FromPyPointer};
+use crate::{Py, PyObject, Python};
+use std::cell::RefCell;
 use std::os::raw::{c_int, c_void};
 
+pub use pyo3cls::PyGCTraverse;
+
 #[repr(transparent)]
 pub struct PyTraverseError(c_int);
 
 /// GC support
-pub trait PyGCProtocol<'p>: PyTypeInfo {
+pub trait PyGCProtocol<'p>: PyClass {
     fn __traverse__(&'p self, visit: PyVisit) -> Result<(), PyTraverseError>;
     fn __clear__(&'p mut self);
 }
@@ -64,6 +67,110 @@ impl<'p> PyVisit<'p> {
     }
 }
 
+/// Types whose references to Python objects can be visited and cleared by the garbage collector.
+///
+/// `#[derive(PyGCTraverse)]` implements this trait and `PyGCProtocol` for a struct by
+/// forwarding to each of its fields, so it can only be derived when all fields implement it.
+/// Fields that don't hold Python objects, like numbers and strings, implement it
+/// with no-op methods.
+///
+/// `Py<T>` and `PyObject` fields are visited, but can't be cleared. Wrap them in an `Option`,
+/// which is reset to `None` by `clear`, so that reference cycles through them can be broken:
+///
+/// ```
+/// # #![feature(specialization)]
+/// use pyo3::class::gc::PyGCTraverse;
+/// use pyo3::prelude::*;
+///
+/// #[pyclass(gc)]
+/// #[derive(PyGCTraverse)]
+/// struct Node {
+///     value: i32,
+///     next: Option<PyObject>,
+/// }
+/// ```
+pub trait PyGCTraverse {
+    /// Calls `visit.call()` for each contained reference to a Python object.
+    fn traverse(&self, visit: PyVisit) -> Result<(), PyTraverseError>;
+
+    /// Releases the contained references to Python objects, if possible.
+    fn clear(&mut self) {}
+}
+
+impl<T> PyGCTraverse for Py<T> {
+    fn traverse(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        visit.call(self)
+    }
+}
+
+impl PyGCTraverse for PyObject {
+    fn traverse(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        visit.call(self)
+    }
+}
+
+impl<T: PyGCTraverse> PyGCTraverse for Option<T> {
+    fn traverse(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        match self {
+            Some(inner) => inner.traverse(visit),
+            None => Ok(()),
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = None;
+    }
+}
+
+impl<T: PyGCTraverse> PyGCTraverse for Vec<T> {
+    fn traverse(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        self.iter().try_for_each(|item| item.traverse(visit))
+    }
+
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+}
+
+impl<T: PyGCTraverse> PyGCTraverse for Box<T> {
+    fn traverse(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        (**self).traverse(visit)
+    }
+
+    fn clear(&mut self) {
+        (**self).clear();
+    }
+}
+
+impl<T: PyGCTraverse> PyGCTraverse for RefCell<T> {
+    fn traverse(&self, visit: PyVisit) -> Result<(), PyTraverseError> {
+        // A mutably borrowed value can't be visited, and isn't garbage anyway
+        match self.try_borrow() {
+            Ok(inner) => inner.traverse(visit),
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.get_mut().clear();
+    }
+}
+
+macro_rules! no_op_traverse {
+    ($($t:ty),*) => {
+        $(
+            impl PyGCTraverse for $t {
+                fn traverse(&self, _visit: PyVisit) -> Result<(), PyTraverseError> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+no_op_traverse!((), bool, char, String);
+no_op_traverse!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
 trait PyGCTraverseProtocolImpl {
     fn tp_traverse() -> Option<ffi::traverseproc>;
 }
@@ -94,16 +201,25 @@ where
         {
             let py = Python::assume_gil_acquired();
             let _pool = crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
+            let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
 
+            let code = slf.traverse_slots(visit, arg);
+            if code != 0 {
+                return code;
+            }
             let visit = PyVisit {
                 visit,
                 arg,
                 _py: py,
             };
-            match slf.__traverse__(visit) {
-                Ok(()) => 0,
-                Err(PyTraverseError(code)) => code,
+            // The collector may run while a method holds a mutable borrow of the value. Its
+            // references are skipped then, which keeps the objects it refers to alive.
+            match slf.try_borrow() {
+                Ok(slf) => match slf.__traverse__(visit) {
+                    Ok(()) => 0,
+                    Err(PyTraverseError(code)) => code,
+                },
+                Err(_) => 0,
             }
         }
 
@@ -136,11 +252,40 @@ where
         {
             let py = Python::assume_gil_acquired();
             let _pool = crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
+            let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
 
-            slf.__clear__();
+            // The collector ignores errors from `tp_clear`, so they are reported as unraisable
+            match slf.try_borrow_mut() {
+                Ok(mut slf) => slf.__clear__(),
+                Err(e) => {
+                    e.restore(py);
+                    ffi::PyErr_WriteUnraisable(slf.as_ptr());
+                }
+            }
+            slf.clear_slots(py);
             0
         }
         Some(tp_clear::<T>)
     }
 }
+
+/// `tp_traverse` of a class with `__slots__` that doesn't implement `PyGCProtocol`.
+pub(crate) unsafe extern "C" fn tp_traverse_slots<T: PyClass>(
+    slf: *mut ffi::PyObject,
+    visit: ffi::visitproc,
+    arg: *mut c_void,
+) -> c_int {
+    let py = Python::assume_gil_acquired();
+    let _pool = crate::GILPool::new(py);
+    let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+    slf.traverse_slots(visit, arg)
+}
+
+/// `tp_clear` of a class with `__slots__` that doesn't implement `PyGCProtocol`.
+pub(crate) unsafe extern "C" fn tp_clear_slots<T: PyClass>(slf: *mut ffi::PyObject) -> c_int {
+    let py = Python::assume_gil_acquired();
+    let _pool = crate::GILPool::new(py);
+    let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+    slf.clear_slots(py);
+    0
+}
@@ -3,8 +3,9 @@
 //! Trait and support implementation for implementing iterators
 
 use crate::callback::{CallbackConverter, PyObjectCallbackConverter};
+use crate::class::methods::PyMethodDef;
 use crate::err::PyResult;
-use crate::{ffi, pyclass::PyClassShell, IntoPy, PyClass, PyObject};
+use crate::{ffi, pyclass::PyCell, IntoPy, Py, PyClass, PyObject};
 use crate::{IntoPyPointer, Python};
 use std::ptr;
 
@@ -14,19 +15,26 @@ use std::ptr;
 /// `https://docs.python.org/3/c-api/typeobj.html#c.PyTypeObject.tp_iter`
 #[allow(unused_variables)]
 pub trait PyIterProtocol<'p>: PyClass {
-    fn __iter__(slf: &mut PyClassShell<Self>) -> Self::Result
+    fn __iter__(slf: &PyCell<Self>) -> Self::Result
     where
         Self: PyIterIterProtocol<'p>,
     {
         unimplemented!()
     }
 
-    fn __next__(slf: &mut PyClassShell<Self>) -> Self::Result
+    fn __next__(slf: &PyCell<Self>) -> Self::Result
     where
         Self: PyIterNextProtocol<'p>,
     {
         unimplemented!()
     }
+
+    fn __length_hint__(&'p self) -> Self::Result
+    where
+        Self: PyIterLengthHintProtocol<'p>,
+    {
+        unimplemented!()
+    }
 }
 
 pub trait PyIterIterProtocol<'p>: PyIterProtocol<'p> {
@@ -39,13 +47,21 @@ pub trait PyIterNextProtocol<'p>: PyIterProtocol<'p> {
     type Result: Into<PyResult<Option<Self::Success>>>;
 }
 
+pub trait PyIterLengthHintProtocol<'p>: PyIterProtocol<'p> {
+    type Result: Into<PyResult<usize>>;
+}
+
 #[doc(hidden)]
 pub trait PyIterProtocolImpl {
     fn tp_as_iter(_typeob: &mut ffi::PyTypeObject);
+    fn methods() -> Vec<PyMethodDef>;
 }
 
 impl<T> PyIterProtocolImpl for T {
     default fn tp_as_iter(_typeob: &mut ffi::PyTypeObject) {}
+    default fn methods() -> Vec<PyMethodDef> {
+        Vec::new()
+    }
 }
 
 impl<'p, T> PyIterProtocolImpl for T
@@ -57,6 +73,17 @@ where
         typeob.tp_iter = Self::tp_iter();
         typeob.tp_iternext = Self::tp_iternext();
     }
+
+    #[inline]
+    fn methods() -> Vec<PyMethodDef> {
+        let mut methods = Vec::new();
+
+        if let Some(def) = <Self as PyIterLengthHintProtocolImpl>::__length_hint__() {
+            methods.push(def)
+        }
+
+        methods
+    }
 }
 
 trait PyIterIterProtocolImpl {
@@ -115,6 +142,20 @@ where
     }
 }
 
+#[doc(hidden)]
+pub trait PyIterLengthHintProtocolImpl {
+    fn __length_hint__() -> Option<PyMethodDef>;
+}
+
+impl<'p, T> PyIterLengthHintProtocolImpl for T
+where
+    T: PyIterProtocol<'p>,
+{
+    default fn __length_hint__() -> Option<PyMethodDef> {
+        None
+    }
+}
+
 struct IterNextConverter;
 
 impl<T> CallbackConverter<Option<T>> for IterNextConverter
@@ -138,3 +179,79 @@ where
         ptr::null_mut()
     }
 }
+
+/// Wraps a Rust iterator, so that it can be returned to Python as an iterator object.
+///
+/// This allows a `#[pyfunction]` or a method to lazily yield values, like a Python generator,
+/// without implementing [PyIterProtocol] for a custom `#[pyclass]`.
+///
+/// # Example
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::class::iter::PyGenerator;
+/// use pyo3::{py_run, wrap_pyfunction};
+///
+/// #[pyfunction]
+/// fn squares(n: u32) -> PyGenerator<impl Iterator<Item = u32> + Send> {
+///     PyGenerator::new((0..n).map(|i| i * i))
+/// }
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let squares = wrap_pyfunction!(squares)(py);
+/// py_run!(py, squares, "assert list(squares(4)) == [0, 1, 4, 9]");
+/// ```
+pub struct PyGenerator<I>(I);
+
+impl<I> PyGenerator<I>
+where
+    I: Iterator + Send + 'static,
+    I::Item: IntoPy<PyObject>,
+{
+    /// Creates a new `PyGenerator` yielding the items of `iter`.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        PyGenerator(iter.into_iter())
+    }
+}
+
+impl<I> IntoPy<PyObject> for PyGenerator<I>
+where
+    I: Iterator + Send + 'static,
+    I::Item: IntoPy<PyObject>,
+{
+    fn into_py(self, py: Python) -> PyObject {
+        let mut iter = self.0;
+        let generator = generator::Generator {
+            next: Box::new(move |py| iter.next().map(|item| item.into_py(py))),
+        };
+        Py::new(py, generator)
+            .expect("Failed to create generator object")
+            .into_py(py)
+    }
+}
+
+mod generator {
+    use crate as pyo3;
+    use crate::class::iter::PyIterProtocol;
+    use crate::{pyclass::PyCell, Py, PyObject, PyResult, Python};
+    use pyo3cls::{pyclass, pyproto};
+
+    #[pyclass]
+    pub struct Generator {
+        pub(super) next: Box<dyn FnMut(Python) -> Option<PyObject> + Send>,
+    }
+
+    #[pyproto]
+    impl PyIterProtocol for Generator {
+        fn __iter__(slf: &PyCell<Self>) -> PyResult<Py<Generator>> {
+            Ok(slf.into())
+        }
+
+        fn __next__(slf: &PyCell<Self>) -> PyResult<Option<PyObject>> {
+            let py = unsafe { Python::assume_gil_acquired() };
+            // Advancing the generator from within its own iterator raises a `RuntimeError`
+            Ok((slf.try_borrow_mut()?.next)(py))
+        }
+    }
+}
@@ -1,5 +1,23 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
+/// Calls `$f` on the value borrowed with `PyCell::try_borrow` or `PyCell::try_borrow_mut`,
+/// converting its result to a `PyResult`.
+///
+/// `$slf` is the result of the borrow. It has to be bound for the whole wrapper, since the
+/// arguments and the result of a protocol method may borrow from the receiver. If the value
+/// couldn't be borrowed, e.g. because a `&mut self` method of the object runs further up the
+/// stack, this evaluates to the error of the borrow instead.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_call_borrowed {
+    ($slf:ident, $f:ident ( $($arg:expr),* )) => {
+        match $slf {
+            Ok(ref mut slf) => slf.$f($($arg),*).into(),
+            Err(e) => Err(e),
+        }
+    };
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_unary_func {
@@ -12,21 +30,26 @@ macro_rules! py_unary_func {
             *mut $crate::ffi::PyObject
         );
     };
-    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr, $ret_type:ty) => {{
+    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr, $ret_type:ty) => {
+        py_unary_func!($trait, $class::$f, $res_type, $conv, $ret_type, try_borrow);
+    };
+    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr, $ret_type:ty, $borrow:ident) => {{
         unsafe extern "C" fn wrap<T>(slf: *mut $crate::ffi::PyObject) -> $ret_type
         where
             T: for<'p> $trait<'p>,
         {
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
-            let res = slf.$f().into();
-            $crate::callback::cb_convert($conv, py, res.map(|x| x))
+            let slf: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.$borrow();
+            let res = py_call_borrowed!(slf, $f());
+            $crate::callback::cb_convert($conv, py, res)
         }
         Some(wrap::<$class>)
     }};
 }
 
+/// Wraps a method like `__iter__` that receives the `&PyCell<Self>` itself instead of a borrow.
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_unary_pyref_func {
@@ -35,10 +58,9 @@ macro_rules! py_unary_pyref_func {
         where
             T: for<'p> $trait<'p>,
         {
-            use $crate::pyclass::PyClassShell;
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf: &mut PyClassShell<T> = &mut *(slf as *mut PyClassShell<T>);
+            let slf: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
             let res = $class::$f(slf).into();
             $crate::callback::cb_convert($conv, py, res)
         }
@@ -56,9 +78,10 @@ macro_rules! py_len_func {
         {
             let py = Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
+            let slf: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.try_borrow();
 
-            let result = slf.$f().into();
+            let result = py_call_borrowed!(slf, $f());
             $crate::callback::cb_convert($conv, py, result)
         }
         Some(wrap::<$class>)
@@ -77,7 +100,10 @@ macro_rules! py_binary_func {
             *mut $crate::ffi::PyObject
         )
     };
-    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr, $return:ty) => {{
+    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr, $return:ty) => {
+        py_binary_func!($trait, $class::$f, $res_type, $conv, $return, try_borrow)
+    };
+    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr, $return:ty, $borrow:ident) => {{
         #[allow(unused_mut)]
         unsafe extern "C" fn wrap<T>(slf: *mut ffi::PyObject, arg: *mut ffi::PyObject) -> $return
         where
@@ -86,11 +112,12 @@ macro_rules! py_binary_func {
             use $crate::ObjectProtocol;
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
+            let slf: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.$borrow();
             let arg = py.from_borrowed_ptr::<$crate::types::PyAny>(arg);
 
             let result = match arg.extract() {
-                Ok(arg) => slf.$f(arg).into(),
+                Ok(arg) => py_call_borrowed!(slf, $f(arg)),
                 Err(e) => Err(e.into()),
             };
             $crate::callback::cb_convert($conv, py, result)
@@ -130,6 +157,44 @@ macro_rules! py_binary_num_func {
     }};
 }
 
+/// Wraps a reflected method like `__rlshift__` in a binary slot.
+///
+/// CPython calls the slot of the right operand with the operands in their original order, so
+/// the reflected method only applies when `rhs` is an instance of the class. Otherwise
+/// `NotImplemented` is returned, which lets the interpreter try the other operand.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_binary_reverse_num_func {
+    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr) => {{
+        #[allow(unused_mut)]
+        unsafe extern "C" fn wrap<T>(
+            lhs: *mut ffi::PyObject,
+            rhs: *mut ffi::PyObject,
+        ) -> *mut $crate::ffi::PyObject
+        where
+            T: for<'p> $trait<'p>,
+        {
+            use $crate::ObjectProtocol;
+            let py = $crate::Python::assume_gil_acquired();
+            let _pool = $crate::GILPool::new(py);
+            let lhs = py.from_borrowed_ptr::<$crate::types::PyAny>(lhs);
+
+            if !<T as $crate::PyTypeInfo>::is_instance(py.from_borrowed_ptr(rhs)) {
+                return $crate::IntoPyPointer::into_ptr(py.NotImplemented());
+            }
+            let slf: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, rhs);
+            let mut slf = slf.try_borrow();
+
+            let result = match lhs.extract() {
+                Ok(lhs) => py_call_borrowed!(slf, $f(lhs)),
+                Err(e) => Err(e.into()),
+            };
+            $crate::callback::cb_convert($conv, py, result)
+        }
+        Some(wrap::<$class>)
+    }};
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_binary_self_func {
@@ -146,11 +211,12 @@ macro_rules! py_binary_self_func {
 
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf1 = py.mut_from_borrowed_ptr::<T>(slf);
+            let slf1: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf1 = slf1.try_borrow_mut();
             let arg = py.from_borrowed_ptr::<$crate::types::PyAny>(arg);
 
             let result = match arg.extract() {
-                Ok(arg) => slf1.$f(arg).into(),
+                Ok(arg) => py_call_borrowed!(slf1, $f(arg)),
                 Err(e) => Err(e.into()),
             };
             match result {
@@ -171,7 +237,10 @@ macro_rules! py_binary_self_func {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_ssizearg_func {
-    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr) => {{
+    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr) => {
+        py_ssizearg_func!($trait, $class::$f, $res_type, $conv, try_borrow)
+    };
+    ($trait:ident, $class:ident :: $f:ident, $res_type:ty, $conv:expr, $borrow:ident) => {{
         #[allow(unused_mut)]
         unsafe extern "C" fn wrap<T>(
             slf: *mut ffi::PyObject,
@@ -182,8 +251,9 @@ macro_rules! py_ssizearg_func {
         {
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
-            let result = slf.$f(arg.into()).into();
+            let slf: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.$borrow();
+            let result = py_call_borrowed!(slf, $f(arg.into()));
             $crate::callback::cb_convert($conv, py, result)
         }
         Some(wrap::<$class>)
@@ -215,13 +285,14 @@ macro_rules! py_ternary_func {
 
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<T>(slf);
+            let slf: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.try_borrow();
             let arg1 = py.from_borrowed_ptr::<$crate::types::PyAny>(arg1);
             let arg2 = py.from_borrowed_ptr::<$crate::types::PyAny>(arg2);
 
             let result = match arg1.extract() {
                 Ok(arg1) => match arg2.extract() {
-                    Ok(arg2) => slf.$f(arg1, arg2).into(),
+                    Ok(arg2) => py_call_borrowed!(slf, $f(arg1, arg2)),
                     Err(e) => Err(e.into()),
                 },
                 Err(e) => Err(e.into()),
@@ -286,13 +357,14 @@ macro_rules! py_ternary_self_func {
 
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf1 = py.mut_from_borrowed_ptr::<T>(slf);
+            let slf1: &$crate::PyCell<T> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf1 = slf1.try_borrow_mut();
             let arg1 = py.from_borrowed_ptr::<$crate::types::PyAny>(arg1);
             let arg2 = py.from_borrowed_ptr::<$crate::types::PyAny>(arg2);
 
             let result = match arg1.extract() {
                 Ok(arg1) => match arg2.extract() {
-                    Ok(arg2) => slf1.$f(arg1, arg2).into(),
+                    Ok(arg2) => py_call_borrowed!(slf1, $f(arg1, arg2)),
                     Err(e) => Err(e.into()),
                 },
                 Err(e) => Err(e.into()),
@@ -312,7 +384,21 @@ macro_rules! py_ternary_self_func {
 
 #[doc(hidden)]
 macro_rules! py_func_set {
-    ($trait_name:ident, $generic:ident, $fn_set:ident) => {{
+    ($trait_name:ident, $generic:ident, $fn_set:ident) => {
+        py_func_set!(
+            $trait_name,
+            $generic,
+            $fn_set,
+            $crate::PyErr::new::<exceptions::NotImplementedError, _>(format!(
+                "Subscript deletion not supported by {:?}",
+                stringify!($generic)
+            ))
+        )
+    };
+    ($trait_name:ident, $generic:ident, $fn_set:ident, $del_err:expr) => {
+        py_func_set!($trait_name, $generic, $fn_set, $del_err, try_borrow_mut)
+    };
+    ($trait_name:ident, $generic:ident, $fn_set:ident, $del_err:expr, $borrow:ident) => {{
         unsafe extern "C" fn wrap<$generic>(
             slf: *mut $crate::ffi::PyObject,
             name: *mut $crate::ffi::PyObject,
@@ -325,21 +411,17 @@ macro_rules! py_func_set {
 
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<$generic>(slf);
+            let slf: &$crate::PyCell<$generic> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.$borrow();
 
             let result = if value.is_null() {
-                Err($crate::PyErr::new::<exceptions::NotImplementedError, _>(
-                    format!(
-                        "Subscript deletion not supported by {:?}",
-                        stringify!($generic)
-                    ),
-                ))
+                Err($del_err)
             } else {
-                let name = py.mut_from_borrowed_ptr::<$crate::types::PyAny>(name);
+                let name = py.from_borrowed_ptr::<$crate::types::PyAny>(name);
                 let value = py.from_borrowed_ptr::<$crate::types::PyAny>(value);
                 match name.extract() {
                     Ok(name) => match value.extract() {
-                        Ok(value) => slf.$fn_set(name, value).into(),
+                        Ok(value) => py_call_borrowed!(slf, $fn_set(name, value)),
                         Err(e) => Err(e.into()),
                     },
                     Err(e) => Err(e.into()),
@@ -360,7 +442,20 @@ macro_rules! py_func_set {
 
 #[doc(hidden)]
 macro_rules! py_func_del {
-    ($trait_name:ident, $generic:ident, $fn_del:ident) => {{
+    ($trait_name:ident, $generic:ident, $fn_del:ident) => {
+        py_func_del!(
+            $trait_name,
+            $generic,
+            $fn_del,
+            $crate::PyErr::new::<exceptions::NotImplementedError, _>(
+                "Subscript assignment not supported"
+            )
+        )
+    };
+    ($trait_name:ident, $generic:ident, $fn_del:ident, $set_err:expr) => {
+        py_func_del!($trait_name, $generic, $fn_del, $set_err, try_borrow_mut)
+    };
+    ($trait_name:ident, $generic:ident, $fn_del:ident, $set_err:expr, $borrow:ident) => {{
         unsafe extern "C" fn wrap<U>(
             slf: *mut $crate::ffi::PyObject,
             name: *mut $crate::ffi::PyObject,
@@ -373,19 +468,18 @@ macro_rules! py_func_del {
 
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
+            let slf: &$crate::PyCell<U> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.$borrow();
 
             let result = if value.is_null() {
-                let slf = py.mut_from_borrowed_ptr::<U>(slf);
                 let name = py.from_borrowed_ptr::<$crate::types::PyAny>(name);
 
                 match name.extract() {
-                    Ok(name) => slf.$fn_del(name).into(),
+                    Ok(name) => py_call_borrowed!(slf, $fn_del(name)),
                     Err(e) => Err(e.into()),
                 }
             } else {
-                Err(PyErr::new::<exceptions::NotImplementedError, _>(
-                    "Subscript assignment not supported",
-                ))
+                Err($set_err)
             };
             match result {
                 Ok(_) => 0,
@@ -402,7 +496,10 @@ macro_rules! py_func_del {
 
 #[doc(hidden)]
 macro_rules! py_func_set_del {
-    ($trait1:ident, $trait2:ident, $generic:ident, $fn_set:ident, $fn_del:ident) => {{
+    ($trait1:ident, $trait2:ident, $generic:ident, $fn_set:ident, $fn_del:ident) => {
+        py_func_set_del!($trait1, $trait2, $generic, $fn_set, $fn_del, try_borrow_mut)
+    };
+    ($trait1:ident, $trait2:ident, $generic:ident, $fn_set:ident, $fn_del:ident, $borrow:ident) => {{
         unsafe extern "C" fn wrap<$generic>(
             slf: *mut $crate::ffi::PyObject,
             name: *mut $crate::ffi::PyObject,
@@ -415,19 +512,20 @@ macro_rules! py_func_set_del {
 
             let py = $crate::Python::assume_gil_acquired();
             let _pool = $crate::GILPool::new(py);
-            let slf = py.mut_from_borrowed_ptr::<$generic>(slf);
+            let slf: &$crate::PyCell<$generic> = $crate::FromPyPointer::from_borrowed_ptr(py, slf);
+            let mut slf = slf.$borrow();
             let name = py.from_borrowed_ptr::<$crate::types::PyAny>(name);
 
             let result = if value.is_null() {
                 match name.extract() {
-                    Ok(name) => slf.$fn_del(name).into(),
+                    Ok(name) => py_call_borrowed!(slf, $fn_del(name)),
                     Err(e) => Err(e.into()),
                 }
             } else {
                 let value = py.from_borrowed_ptr::<$crate::types::PyAny>(value);
                 match name.extract() {
                     Ok(name) => match value.extract() {
-                        Ok(value) => slf.$fn_set(name, value).into(),
+                        Ok(value) => py_call_borrowed!(slf, $fn_set(name, value)),
                         Err(e) => Err(e.into()),
                     },
                     Err(e) => Err(e.into()),
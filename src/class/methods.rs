@@ -1,6 +1,6 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
-use crate::ffi;
+use crate::{ffi, PyObject, Python};
 use libc::c_int;
 use std::ffi::CString;
 
@@ -10,6 +10,8 @@ use std::ffi::CString;
 pub enum PyMethodDefType {
     /// Represents class `__new__` method
     New(PyMethodDef),
+    /// Represents class `__init__` method, used by `#[init]`
+    Init(PyMethodDef),
     /// Represents class `__call__` method
     Call(PyMethodDef),
     /// Represents class method
@@ -22,6 +24,8 @@ pub enum PyMethodDefType {
     Getter(PyGetterDef),
     /// Represents setter descriptor, used by `#[setter]`
     Setter(PySetterDef),
+    /// Represents class attribute, used by `#[classattr]`
+    ClassAttribute(PyClassAttributeDef),
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -54,6 +58,20 @@ pub struct PySetterDef {
     pub doc: &'static str,
 }
 
+#[derive(Copy, Clone)]
+pub struct PyClassAttributeDef {
+    pub name: &'static str,
+    pub meth: for<'p> fn(Python<'p>) -> PyObject,
+}
+
+impl std::fmt::Debug for PyClassAttributeDef {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("PyClassAttributeDef")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
 unsafe impl Sync for PyMethodDef {}
 
 unsafe impl Sync for ffi::PyMethodDef {}
@@ -16,15 +16,19 @@ pub mod methods;
 pub mod number;
 pub mod pyasync;
 pub mod sequence;
+pub mod vectorcall;
 
 pub use self::basic::PyObjectProtocol;
 pub use self::buffer::PyBufferProtocol;
 pub use self::context::PyContextProtocol;
 pub use self::descr::PyDescrProtocol;
-pub use self::gc::{PyGCProtocol, PyTraverseError, PyVisit};
-pub use self::iter::PyIterProtocol;
+pub use self::gc::{PyGCProtocol, PyGCTraverse, PyTraverseError, PyVisit};
+pub use self::iter::{PyGenerator, PyIterProtocol};
 pub use self::mapping::PyMappingProtocol;
-pub use self::methods::{PyGetterDef, PyMethodDef, PyMethodDefType, PyMethodType, PySetterDef};
+pub use self::methods::{
+    PyClassAttributeDef, PyGetterDef, PyMethodDef, PyMethodDefType, PyMethodType, PySetterDef,
+};
 pub use self::number::PyNumberProtocol;
-pub use self::pyasync::PyAsyncProtocol;
+pub use self::pyasync::{PyAsyncProtocol, PyCoroutine};
 pub use self::sequence::PySequenceProtocol;
+pub use self::vectorcall::PyVectorcallProtocol;
@@ -7,13 +7,13 @@ use crate::callback::PyObjectCallbackConverter;
 use crate::class::basic::PyObjectProtocolImpl;
 use crate::class::methods::PyMethodDef;
 use crate::err::PyResult;
-use crate::type_object::PyTypeInfo;
+use crate::pyclass::PyClass;
 use crate::FromPyObject;
 use crate::{ffi, IntoPy, PyObject};
 
 /// Number interface
 #[allow(unused_variables)]
-pub trait PyNumberProtocol<'p>: PyTypeInfo {
+pub trait PyNumberProtocol<'p>: PyClass {
     fn __add__(lhs: Self::Left, rhs: Self::Right) -> Self::Result
     where
         Self: PyNumberAddProtocol<'p>,
@@ -660,11 +660,11 @@ where
             nb_absolute: Self::nb_absolute(),
             nb_bool: <Self as PyObjectProtocolImpl>::nb_bool_fn(),
             nb_invert: Self::nb_invert(),
-            nb_lshift: Self::nb_lshift(),
-            nb_rshift: Self::nb_rshift(),
-            nb_and: Self::nb_and(),
-            nb_xor: Self::nb_xor(),
-            nb_or: Self::nb_or(),
+            nb_lshift: <Self as PyNumberLShiftProtocolImpl>::nb_lshift(),
+            nb_rshift: <Self as PyNumberRShiftProtocolImpl>::nb_rshift(),
+            nb_and: <Self as PyNumberAndProtocolImpl>::nb_and(),
+            nb_xor: <Self as PyNumberXorProtocolImpl>::nb_xor(),
+            nb_or: <Self as PyNumberOrProtocolImpl>::nb_or(),
             nb_int: Self::nb_int(),
             nb_reserved: ::std::ptr::null_mut(),
             nb_float: Self::nb_float(),
@@ -997,7 +997,7 @@ where
     T: PyNumberProtocol<'p>,
 {
     default fn nb_lshift() -> Option<ffi::binaryfunc> {
-        None
+        <T as PyNumberReflectedLShiftImpl>::nb_lshift()
     }
 }
 
@@ -1015,6 +1015,34 @@ where
     }
 }
 
+/// Fills `nb_lshift` from `__rlshift__` when the class doesn't implement the forward operator.
+trait PyNumberReflectedLShiftImpl {
+    fn nb_lshift() -> Option<ffi::binaryfunc>;
+}
+
+impl<'p, T> PyNumberReflectedLShiftImpl for T
+where
+    T: PyNumberProtocol<'p>,
+{
+    default fn nb_lshift() -> Option<ffi::binaryfunc> {
+        None
+    }
+}
+
+impl<T> PyNumberReflectedLShiftImpl for T
+where
+    T: for<'p> PyNumberRLShiftProtocol<'p>,
+{
+    fn nb_lshift() -> Option<ffi::binaryfunc> {
+        py_binary_reverse_num_func!(
+            PyNumberRLShiftProtocol,
+            T::__rlshift__,
+            T::Success,
+            PyObjectCallbackConverter
+        )
+    }
+}
+
 trait PyNumberRShiftProtocolImpl {
     fn nb_rshift() -> Option<ffi::binaryfunc>;
 }
@@ -1024,7 +1052,7 @@ where
     T: PyNumberProtocol<'p>,
 {
     default fn nb_rshift() -> Option<ffi::binaryfunc> {
-        None
+        <T as PyNumberReflectedRShiftImpl>::nb_rshift()
     }
 }
 
@@ -1042,6 +1070,34 @@ where
     }
 }
 
+/// Fills `nb_rshift` from `__rrshift__` when the class doesn't implement the forward operator.
+trait PyNumberReflectedRShiftImpl {
+    fn nb_rshift() -> Option<ffi::binaryfunc>;
+}
+
+impl<'p, T> PyNumberReflectedRShiftImpl for T
+where
+    T: PyNumberProtocol<'p>,
+{
+    default fn nb_rshift() -> Option<ffi::binaryfunc> {
+        None
+    }
+}
+
+impl<T> PyNumberReflectedRShiftImpl for T
+where
+    T: for<'p> PyNumberRRShiftProtocol<'p>,
+{
+    fn nb_rshift() -> Option<ffi::binaryfunc> {
+        py_binary_reverse_num_func!(
+            PyNumberRRShiftProtocol,
+            T::__rrshift__,
+            T::Success,
+            PyObjectCallbackConverter
+        )
+    }
+}
+
 trait PyNumberAndProtocolImpl {
     fn nb_and() -> Option<ffi::binaryfunc>;
 }
@@ -1051,7 +1107,7 @@ where
     T: PyNumberProtocol<'p>,
 {
     default fn nb_and() -> Option<ffi::binaryfunc> {
-        None
+        <T as PyNumberReflectedAndImpl>::nb_and()
     }
 }
 
@@ -1069,6 +1125,34 @@ where
     }
 }
 
+/// Fills `nb_and` from `__rand__` when the class doesn't implement the forward operator.
+trait PyNumberReflectedAndImpl {
+    fn nb_and() -> Option<ffi::binaryfunc>;
+}
+
+impl<'p, T> PyNumberReflectedAndImpl for T
+where
+    T: PyNumberProtocol<'p>,
+{
+    default fn nb_and() -> Option<ffi::binaryfunc> {
+        None
+    }
+}
+
+impl<T> PyNumberReflectedAndImpl for T
+where
+    T: for<'p> PyNumberRAndProtocol<'p>,
+{
+    fn nb_and() -> Option<ffi::binaryfunc> {
+        py_binary_reverse_num_func!(
+            PyNumberRAndProtocol,
+            T::__rand__,
+            T::Success,
+            PyObjectCallbackConverter
+        )
+    }
+}
+
 trait PyNumberXorProtocolImpl {
     fn nb_xor() -> Option<ffi::binaryfunc>;
 }
@@ -1078,7 +1162,7 @@ where
     T: PyNumberProtocol<'p>,
 {
     default fn nb_xor() -> Option<ffi::binaryfunc> {
-        None
+        <T as PyNumberReflectedXorImpl>::nb_xor()
     }
 }
 
@@ -1096,6 +1180,34 @@ where
     }
 }
 
+/// Fills `nb_xor` from `__rxor__` when the class doesn't implement the forward operator.
+trait PyNumberReflectedXorImpl {
+    fn nb_xor() -> Option<ffi::binaryfunc>;
+}
+
+impl<'p, T> PyNumberReflectedXorImpl for T
+where
+    T: PyNumberProtocol<'p>,
+{
+    default fn nb_xor() -> Option<ffi::binaryfunc> {
+        None
+    }
+}
+
+impl<T> PyNumberReflectedXorImpl for T
+where
+    T: for<'p> PyNumberRXorProtocol<'p>,
+{
+    fn nb_xor() -> Option<ffi::binaryfunc> {
+        py_binary_reverse_num_func!(
+            PyNumberRXorProtocol,
+            T::__rxor__,
+            T::Success,
+            PyObjectCallbackConverter
+        )
+    }
+}
+
 trait PyNumberOrProtocolImpl {
     fn nb_or() -> Option<ffi::binaryfunc>;
 }
@@ -1105,7 +1217,7 @@ where
     T: PyNumberProtocol<'p>,
 {
     default fn nb_or() -> Option<ffi::binaryfunc> {
-        None
+        <T as PyNumberReflectedOrImpl>::nb_or()
     }
 }
 
@@ -1123,6 +1235,34 @@ where
     }
 }
 
+/// Fills `nb_or` from `__ror__` when the class doesn't implement the forward operator.
+trait PyNumberReflectedOrImpl {
+    fn nb_or() -> Option<ffi::binaryfunc>;
+}
+
+impl<'p, T> PyNumberReflectedOrImpl for T
+where
+    T: PyNumberProtocol<'p>,
+{
+    default fn nb_or() -> Option<ffi::binaryfunc> {
+        None
+    }
+}
+
+impl<T> PyNumberReflectedOrImpl for T
+where
+    T: for<'p> PyNumberROrProtocol<'p>,
+{
+    fn nb_or() -> Option<ffi::binaryfunc> {
+        py_binary_reverse_num_func!(
+            PyNumberROrProtocol,
+            T::__ror__,
+            T::Success,
+            PyObjectCallbackConverter
+        )
+    }
+}
+
 trait PyNumberIAddProtocolImpl {
     fn nb_inplace_add() -> Option<ffi::binaryfunc>;
 }
@@ -8,18 +8,20 @@
 //! [PEP-0492](https://www.python.org/dev/peps/pep-0492/)
 //!
 
-use crate::callback::PyObjectCallbackConverter;
+use crate::callback::{CallbackConverter, PyObjectCallbackConverter};
 use crate::class::methods::PyMethodDef;
 use crate::err::PyResult;
 use crate::ffi;
-use crate::type_object::PyTypeInfo;
-use crate::PyObject;
+use crate::pyclass::PyClass;
+use crate::{IntoPy, IntoPyPointer, Py, PyObject, Python};
+use std::future::Future;
+use std::ptr;
 
 /// Python Async/Await support interface.
 ///
 /// Each method in this trait corresponds to Python async/await implementation.
 #[allow(unused_variables)]
-pub trait PyAsyncProtocol<'p>: PyTypeInfo {
+pub trait PyAsyncProtocol<'p>: PyClass {
     fn __await__(&'p self) -> Self::Result
     where
         Self: PyAsyncAwaitProtocol<'p>,
@@ -244,7 +246,9 @@ mod anext {
                 PyAsyncAnextProtocol,
                 T::__anext__,
                 Option<T::Success>,
-                IterANextResultConverter
+                IterANextResultConverter,
+                *mut ffi::PyObject,
+                try_borrow_mut
             )
         }
     }
@@ -275,3 +279,185 @@ where
         None
     }
 }
+
+/// Wraps a Rust `Future`, so that it can be returned to Python as an awaitable object.
+///
+/// The future is polled by the Python coroutine that awaits it. While the future is pending,
+/// the awaitable yields `None`, which asyncio takes as a request to resume it in the next
+/// iteration of the event loop. Wakeups through the `Waker` are not used, so the future is
+/// polled once per iteration until it completes, with its output as the result of the `await`.
+///
+/// Futures that depend on a runtime like tokio, e.g. for timers or sockets, have to be spawned
+/// on that runtime, and their join handle is then wrapped instead.
+///
+/// # Example
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::class::pyasync::PyCoroutine;
+/// use pyo3::{py_run, wrap_pyfunction};
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+///
+/// struct Answer;
+///
+/// impl Future for Answer {
+///     type Output = PyResult<u32>;
+///
+///     fn poll(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<PyResult<u32>> {
+///         Poll::Ready(Ok(42))
+///     }
+/// }
+///
+/// #[pyfunction]
+/// fn answer() -> PyCoroutine<Answer> {
+///     PyCoroutine::new(Answer)
+/// }
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let answer = wrap_pyfunction!(answer)(py);
+/// py_run!(py, answer, r#"
+/// import asyncio
+/// loop = asyncio.new_event_loop()
+/// assert loop.run_until_complete(answer()) == 42
+/// loop.close()
+/// "#);
+/// ```
+pub struct PyCoroutine<F>(F);
+
+impl<F, T> PyCoroutine<F>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: IntoPy<PyObject>,
+{
+    /// Creates a new `PyCoroutine` awaiting `future`.
+    pub fn new(future: F) -> Self {
+        PyCoroutine(future)
+    }
+}
+
+impl<F, T> IntoPy<PyObject> for PyCoroutine<F>
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: IntoPy<PyObject>,
+{
+    fn into_py(self, py: Python) -> PyObject {
+        let coroutine = coroutine::Coroutine::new(self.0);
+        Py::new(py, coroutine)
+            .expect("Failed to create coroutine object")
+            .into_py(py)
+    }
+}
+
+/// Converts a Rust `Future` returned by a callback to a Python awaitable, like [PyCoroutine].
+pub struct CoroutineConverter;
+
+impl<F, T> CallbackConverter<F> for CoroutineConverter
+where
+    F: Future<Output = PyResult<T>> + Send + 'static,
+    T: IntoPy<PyObject>,
+{
+    type R = *mut ffi::PyObject;
+
+    fn convert(future: F, py: Python) -> *mut ffi::PyObject {
+        PyCoroutine::new(future).into_py(py).into_ptr()
+    }
+
+    #[inline]
+    fn error_value() -> *mut ffi::PyObject {
+        ptr::null_mut()
+    }
+}
+
+mod coroutine {
+    use crate as pyo3;
+    use crate::class::iter::PyIterProtocol;
+    use crate::class::pyasync::PyAsyncProtocol;
+    use crate::exceptions::{RuntimeError, StopIteration};
+    use crate::{pyclass::PyCell, IntoPy, Py, PyObject, PyResult, Python};
+    use pyo3cls::{pyclass, pyproto};
+    use std::cell::RefCell;
+    use std::future::Future;
+    use std::ptr;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    type PollFn = Box<dyn FnMut(Python, &mut Context) -> Poll<PyResult<PyObject>> + Send>;
+
+    /// The awaitable object, which can be awaited once.
+    #[pyclass]
+    pub struct Coroutine {
+        poll: RefCell<Option<PollFn>>,
+    }
+
+    impl Coroutine {
+        pub(super) fn new<F, T>(future: F) -> Self
+        where
+            F: Future<Output = PyResult<T>> + Send + 'static,
+            T: IntoPy<PyObject>,
+        {
+            let mut future = Box::pin(future);
+            let poll: PollFn = Box::new(move |py, cx| {
+                future
+                    .as_mut()
+                    .poll(cx)
+                    .map(|result| result.map(|value| value.into_py(py)))
+            });
+            Coroutine {
+                poll: RefCell::new(Some(poll)),
+            }
+        }
+    }
+
+    #[pyproto]
+    impl PyAsyncProtocol for Coroutine {
+        fn __await__(&self) -> PyResult<Awaiter> {
+            match self.poll.borrow_mut().take() {
+                Some(poll) => Ok(Awaiter { poll: Some(poll) }),
+                None => Err(RuntimeError::py_err(
+                    "cannot reuse already awaited coroutine",
+                )),
+            }
+        }
+    }
+
+    /// The iterator returned by `__await__`, which polls the future on each step.
+    #[pyclass]
+    pub struct Awaiter {
+        poll: Option<PollFn>,
+    }
+
+    #[pyproto]
+    impl PyIterProtocol for Awaiter {
+        fn __iter__(slf: &PyCell<Self>) -> PyResult<Py<Awaiter>> {
+            Ok(slf.into())
+        }
+
+        fn __next__(slf: &PyCell<Self>) -> PyResult<Option<PyObject>> {
+            let py = unsafe { Python::assume_gil_acquired() };
+            let mut slf = slf.try_borrow_mut()?;
+            let result = match slf.poll.as_mut() {
+                Some(poll) => poll(py, &mut Context::from_waker(&noop_waker())),
+                None => return Ok(None),
+            };
+            match result {
+                Poll::Pending => Ok(Some(py.None())),
+                Poll::Ready(result) => {
+                    slf.poll = None;
+                    // The output of the future is the value of the `StopIteration`
+                    Err(StopIteration::py_err((result?,)))
+                }
+            }
+        }
+    }
+
+    fn noop_waker() -> Waker {
+        unsafe fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(ptr::null(), &VTABLE)
+        }
+        unsafe fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+    }
+}
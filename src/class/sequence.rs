@@ -7,16 +7,16 @@ use crate::callback::{BoolCallbackConverter, LenResultConverter, PyObjectCallbac
 use crate::err::{PyErr, PyResult};
 use crate::ffi;
 use crate::objectprotocol::ObjectProtocol;
-use crate::type_object::PyTypeInfo;
+use crate::pyclass::{PyCell, PyClass};
 use crate::types::PyAny;
-use crate::FromPyObject;
 use crate::Python;
 use crate::{exceptions, IntoPy, PyObject};
+use crate::{FromPyObject, FromPyPointer};
 use std::os::raw::c_int;
 
 /// Sequence interface
 #[allow(unused_variables)]
-pub trait PySequenceProtocol<'p>: PyTypeInfo + Sized {
+pub trait PySequenceProtocol<'p>: PyClass + Sized {
     fn __len__(&'p self) -> Self::Result
     where
         Self: PySequenceLenProtocol<'p>,
@@ -267,7 +267,8 @@ mod sq_ass_item_impl {
             {
                 let py = Python::assume_gil_acquired();
                 let _pool = crate::GILPool::new(py);
-                let slf = py.mut_from_borrowed_ptr::<T>(slf);
+                let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+                let mut slf = slf.try_borrow_mut();
 
                 let result = if value.is_null() {
                     Err(PyErr::new::<exceptions::NotImplementedError, _>(format!(
@@ -277,7 +278,7 @@ mod sq_ass_item_impl {
                 } else {
                     let value = py.from_borrowed_ptr::<PyAny>(value);
                     match value.extract() {
-                        Ok(value) => slf.__setitem__(key.into(), value).into(),
+                        Ok(value) => py_call_borrowed!(slf, __setitem__(key.into(), value)),
                         Err(e) => Err(e),
                     }
                 };
@@ -322,10 +323,11 @@ mod sq_ass_item_impl {
             {
                 let py = Python::assume_gil_acquired();
                 let _pool = crate::GILPool::new(py);
-                let slf = py.mut_from_borrowed_ptr::<T>(slf);
+                let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+                let mut slf = slf.try_borrow_mut();
 
                 let result = if value.is_null() {
-                    slf.__delitem__(key.into()).into()
+                    py_call_borrowed!(slf, __delitem__(key.into()))
                 } else {
                     Err(PyErr::new::<exceptions::NotImplementedError, _>(format!(
                         "Item assignment not supported by {:?}",
@@ -373,14 +375,15 @@ mod sq_ass_item_impl {
             {
                 let py = Python::assume_gil_acquired();
                 let _pool = crate::GILPool::new(py);
-                let slf = py.mut_from_borrowed_ptr::<T>(slf);
+                let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+                let mut slf = slf.try_borrow_mut();
 
                 let result = if value.is_null() {
-                    slf.__delitem__(key.into()).into()
+                    py_call_borrowed!(slf, __delitem__(key.into()))
                 } else {
                     let value = py.from_borrowed_ptr::<PyAny>(value);
                     match value.extract() {
-                        Ok(value) => slf.__setitem__(key.into(), value).into(),
+                        Ok(value) => py_call_borrowed!(slf, __setitem__(key.into(), value)),
                         Err(e) => Err(e),
                     }
                 };
@@ -501,7 +504,9 @@ where
             PySequenceInplaceConcatProtocol,
             T::__inplace_concat__,
             T,
-            PyObjectCallbackConverter
+            PyObjectCallbackConverter,
+            *mut ffi::PyObject,
+            try_borrow_mut
         )
     }
 }
@@ -528,7 +533,8 @@ where
             PySequenceInplaceRepeatProtocol,
             T::__inplace_repeat__,
             T,
-            PyObjectCallbackConverter
+            PyObjectCallbackConverter,
+            try_borrow_mut
         )
     }
 }
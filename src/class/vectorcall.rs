@@ -0,0 +1,118 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Represent Python vectorcall protocol implementation
+//!
+//! For more information check [PEP 590](https://www.python.org/dev/peps/pep-0590/)
+use crate::callback::PyObjectCallbackConverter;
+use crate::err::PyResult;
+use crate::ffi;
+use crate::pyclass::{PyCell, PyClass};
+use crate::types::{PyAny, PyDict, PyTuple};
+use crate::{FromPyPointer, PyObject, Python};
+
+/// Calling instances with the vectorcall calling convention of
+/// [PEP 590](https://www.python.org/dev/peps/pep-0590/).
+///
+/// Classes declared with `#[pyclass(vectorcall)]` must implement this trait, which makes their
+/// instances callable. On Python 3.8 and later, calls don't need to create a tuple and a dict
+/// for the arguments. On older versions, the arguments are unpacked from the tuple and dict of
+/// a regular call. A `#[__call__]` method of the class is ignored.
+///
+/// `args` holds the positional arguments followed by the values of the keyword arguments, whose
+/// names are in `kwnames`:
+///
+/// ```
+/// # #![feature(specialization)]
+/// use pyo3::class::PyVectorcallProtocol;
+/// use pyo3::prelude::*;
+/// use pyo3::types::{PyAny, PyTuple};
+///
+/// #[pyclass(vectorcall)]
+/// struct Counter {}
+///
+/// impl PyVectorcallProtocol for Counter {
+///     fn __vectorcall__(
+///         &self,
+///         py: Python,
+///         args: &[&PyAny],
+///         kwnames: Option<&PyTuple>,
+///     ) -> PyResult<PyObject> {
+///         let nkwargs = kwnames.map_or(0, PyTuple::len);
+///         Ok((args.len() - nkwargs, nkwargs).to_object(py))
+///     }
+/// }
+/// ```
+pub trait PyVectorcallProtocol: PyClass {
+    fn __vectorcall__(
+        &self,
+        py: Python,
+        args: &[&PyAny],
+        kwnames: Option<&PyTuple>,
+    ) -> PyResult<PyObject>;
+}
+
+/// Borrows the instance and calls `__vectorcall__`.
+unsafe fn call_impl<T>(
+    py: Python,
+    slf: *mut ffi::PyObject,
+    args: &[&PyAny],
+    kwnames: Option<&PyTuple>,
+) -> *mut ffi::PyObject
+where
+    T: PyVectorcallProtocol,
+{
+    let slf = <&PyCell<T> as FromPyPointer>::from_borrowed_ptr(py, slf);
+    let result = slf
+        .try_borrow()
+        .and_then(|slf| slf.__vectorcall__(py, args, kwnames));
+    crate::callback::cb_convert(PyObjectCallbackConverter, py, result)
+}
+
+/// The `vectorcallfunc` stored in each instance.
+pub(crate) unsafe extern "C" fn vectorcall<T>(
+    slf: *mut ffi::PyObject,
+    args: *const *mut ffi::PyObject,
+    nargsf: libc::size_t,
+    kwnames: *mut ffi::PyObject,
+) -> *mut ffi::PyObject
+where
+    T: PyVectorcallProtocol,
+{
+    let py = Python::assume_gil_acquired();
+    let _pool = crate::GILPool::new(py);
+
+    // Clear PY_VECTORCALL_ARGUMENTS_OFFSET, the highest bit
+    let nargs = nargsf & !(1 << (8 * std::mem::size_of::<libc::size_t>() - 1));
+    let kwnames: Option<&PyTuple> = py.from_borrowed_ptr_or_opt(kwnames);
+    let len = nargs + kwnames.map_or(0, PyTuple::len);
+    let args: &[&PyAny] = if len == 0 {
+        &[]
+    } else {
+        // `&PyAny` has the same layout as `*mut ffi::PyObject`
+        std::slice::from_raw_parts(args as *const &PyAny, len)
+    };
+    call_impl::<T>(py, slf, args, kwnames)
+}
+
+/// The `tp_call` slot, which unpacks the arguments of a regular call.
+pub(crate) unsafe extern "C" fn tp_call<T>(
+    slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+) -> *mut ffi::PyObject
+where
+    T: PyVectorcallProtocol,
+{
+    let py = Python::assume_gil_acquired();
+    let _pool = crate::GILPool::new(py);
+
+    let args = py.from_borrowed_ptr::<PyTuple>(args);
+    let kwargs: Option<&PyDict> = py.from_borrowed_ptr_or_opt(kwargs);
+    let mut all_args: Vec<&PyAny> = args.iter().collect();
+    let kwnames = kwargs.filter(|kwargs| !kwargs.is_empty()).map(|kwargs| {
+        let (keys, values): (Vec<_>, Vec<_>) = kwargs.iter().unzip();
+        all_args.extend(values);
+        PyTuple::new(py, keys)
+    });
+    call_impl::<T>(py, slf, &all_args, kwnames)
+}
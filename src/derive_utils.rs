@@ -4,15 +4,24 @@
 
 //! Functionality for the code generated by the derive backend
 
+use crate::callback::{cb_convert, HashConverter, PyObjectCallbackConverter};
 use crate::err::PyResult;
 use crate::exceptions::TypeError;
 use crate::init_once;
 use crate::instance::PyNativeType;
-use crate::pyclass::PyClass;
+use crate::pyclass::{PyCell, PyClass, PyMutableClass};
 use crate::pyclass_init::PyClassInitializer;
+use crate::type_object::{type_flags, PyTypeInfo};
 use crate::types::{PyAny, PyDict, PyModule, PyTuple};
-use crate::{ffi, GILPool, IntoPy, PyObject, Python};
+use crate::{ffi, AsPyPointer, FromPyPointer, GILPool, IntoPy, IntoPyPointer, PyObject, Python};
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::pin::Pin;
 use std::ptr;
+use std::task::{Context, Poll};
 
 /// Description of a python parameter; used for `parse_args()`.
 #[derive(Debug)]
@@ -56,9 +65,13 @@ pub fn parse_fn_args<'p>(
     };
     // Iterate through the parameters and assign values to output:
     for (i, (p, out)) in params.iter().zip(output).enumerate() {
-        *out = match kwargs.and_then(|d| d.get_item(p.name)) {
+        let kwarg = match kwargs {
+            Some(kwargs) => kwargs.get_item_with_error(p.name)?,
+            None => None,
+        };
+        *out = match kwarg {
             Some(kwarg) => {
-                if i < nargs {
+                if i < nargs && !p.kw_only {
                     raise_error!("got multiple values for argument: {}", p.name)
                 }
                 kwargs.as_ref().unwrap().del_item(p.name).unwrap();
@@ -113,6 +126,32 @@ pub fn parse_fn_args<'p>(
     Ok((args, kwargs))
 }
 
+/// A `#[pymodule(parent = "...")]`, which is added to its parent when the parent is initialized.
+pub struct ModuleRegistration {
+    /// The fully qualified name of the parent `#[pymodule]`, including its package
+    pub parent: &'static str,
+    pub name: &'static str,
+    /// The `PyInit_` function of the module
+    pub init: unsafe extern "C" fn() -> *mut ffi::PyObject,
+}
+
+inventory::collect!(ModuleRegistration);
+
+/// Adds the modules registered with `#[pymodule(parent = "...")]` for `module` to it.
+///
+/// The registrations of all crates linked into the binary are collected together, so they are
+/// matched on the `__name__` of `module`, which includes its package when it is imported from one.
+unsafe fn add_registered_submodules(py: Python, module: &PyModule) -> PyResult<()> {
+    let name = module.name()?;
+    for registration in inventory::iter::<ModuleRegistration> {
+        if registration.parent == name {
+            let submodule = PyObject::from_owned_ptr_or_err(py, (registration.init)())?;
+            module.add(registration.name, submodule)?;
+        }
+    }
+    Ok(())
+}
+
 /// Builds a module (or null) from a user given initializer. Used for `#[pymodule]`.
 pub unsafe fn make_module(
     name: &str,
@@ -152,7 +191,8 @@ pub unsafe fn make_module(
     module
         .add("__doc__", doc)
         .expect("Failed to add doc for module");
-    match initializer(py, module) {
+    let result = initializer(py, module).and_then(|_| add_registered_submodules(py, module));
+    match result {
         Ok(_) => module.into_ptr(),
         Err(e) => {
             e.restore(py);
@@ -182,6 +222,32 @@ impl<T: IntoPy<PyObject>> IntoPyResult<T> for PyResult<T> {
     }
 }
 
+/// The future of an `async fn` exported with `#[pyfunction]`, with its output converted by
+/// [IntoPyResult], so that it can be wrapped in a `PyCoroutine`.
+pub struct PyResultFuture<F, T>(Pin<Box<F>>, PhantomData<fn() -> T>);
+
+impl<F, T> PyResultFuture<F, T>
+where
+    F: Future,
+    F::Output: IntoPyResult<T>,
+{
+    pub fn new(future: F) -> Self {
+        PyResultFuture(Box::pin(future), PhantomData)
+    }
+}
+
+impl<F, T> Future for PyResultFuture<F, T>
+where
+    F: Future,
+    F::Output: IntoPyResult<T>,
+{
+    type Output = PyResult<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<PyResult<T>> {
+        self.0.as_mut().poll(cx).map(IntoPyResult::into_py_result)
+    }
+}
+
 /// Variant of IntoPyResult for the specific case of #[new]. In the case of returning (Sub, Base)
 /// from #[new], IntoPyResult can't apply because (Sub, Base) doesn't implement IntoPy<PyObject>.
 pub trait IntoPyNewResult<T: PyClass, I: Into<PyClassInitializer<T>>> {
@@ -199,3 +265,105 @@ impl<T: PyClass, I: Into<PyClassInitializer<T>>> IntoPyNewResult<T, I> for PyRes
         self
     }
 }
+
+/// Fails with a `TypeError` if `T` was declared with `#[pyclass(frozen)]`.
+///
+/// Used by `PyCell::try_borrow_mut`.
+pub fn ensure_mutable<T: PyTypeInfo>() -> PyResult<()> {
+    if T::FLAGS & type_flags::FROZEN != 0 {
+        Err(TypeError::py_err(format!(
+            "'{}' is frozen and can't be mutated",
+            T::NAME
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Fails to compile if `T` was declared with `#[pyclass(frozen)]`.
+///
+/// Used for methods taking `&mut self` or `&mut PyCell<Self>`.
+pub fn assert_mutable<T: PyMutableClass>() {}
+
+/// `tp_hash` of `#[pyclass(hash)]`, which uses the `Hash` implementation of the class.
+pub unsafe extern "C" fn hash_slot<T>(slf: *mut ffi::PyObject) -> ffi::Py_hash_t
+where
+    T: PyClass + Hash,
+{
+    let py = Python::assume_gil_acquired();
+    let _pool = GILPool::new(py);
+    let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+    let result = slf.try_borrow().map(|value| {
+        // `DefaultHasher::new` always uses the same keys, so equal values have equal hashes.
+        // The `-1` that CPython reserves for errors is mapped to `-2` by `HashConverter`.
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish() as isize
+    });
+    cb_convert(HashConverter, py, result)
+}
+
+/// `tp_richcompare` of `#[pyclass(eq)]`, which uses the `PartialEq` implementation of the class.
+pub unsafe extern "C" fn richcmp_eq_slot<T>(
+    slf: *mut ffi::PyObject,
+    other: *mut ffi::PyObject,
+    op: c_int,
+) -> *mut ffi::PyObject
+where
+    T: PyClass + PartialEq,
+{
+    richcmp_slot::<T, _>(slf, other, |a, b| match op {
+        ffi::Py_EQ => Some(a == b),
+        ffi::Py_NE => Some(a != b),
+        _ => None,
+    })
+}
+
+/// `tp_richcompare` of `#[pyclass(ord)]`, which uses the `PartialOrd` implementation of the class.
+pub unsafe extern "C" fn richcmp_ord_slot<T>(
+    slf: *mut ffi::PyObject,
+    other: *mut ffi::PyObject,
+    op: c_int,
+) -> *mut ffi::PyObject
+where
+    T: PyClass + PartialOrd,
+{
+    richcmp_slot::<T, _>(slf, other, |a, b| match op {
+        ffi::Py_LT => Some(a < b),
+        ffi::Py_LE => Some(a <= b),
+        ffi::Py_EQ => Some(a == b),
+        ffi::Py_NE => Some(a != b),
+        ffi::Py_GT => Some(a > b),
+        ffi::Py_GE => Some(a >= b),
+        _ => None,
+    })
+}
+
+/// Compares two instances of `T`, returning `NotImplemented` for other types and for operators
+/// that `compare` doesn't support, so that Python can try the reflected operation of `other`.
+unsafe fn richcmp_slot<T, F>(
+    slf: *mut ffi::PyObject,
+    other: *mut ffi::PyObject,
+    compare: F,
+) -> *mut ffi::PyObject
+where
+    T: PyClass,
+    F: FnOnce(&T, &T) -> Option<bool>,
+{
+    let py = Python::assume_gil_acquired();
+    let _pool = GILPool::new(py);
+    let slf: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, slf);
+    let other = py.from_borrowed_ptr::<PyAny>(other);
+    if !T::is_instance(other) {
+        return py.NotImplemented().into_ptr();
+    }
+    let other: &PyCell<T> = FromPyPointer::from_borrowed_ptr(py, other.as_ptr());
+    let result = slf.try_borrow().and_then(|a| {
+        let b = other.try_borrow()?;
+        Ok(match compare(&a, &b) {
+            Some(result) => result.into_py(py),
+            None => py.NotImplemented(),
+        })
+    });
+    cb_convert(PyObjectCallbackConverter, py, result)
+}
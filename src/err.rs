@@ -1,13 +1,13 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
-use crate::instance::Py;
+use crate::instance::{AsPyRef, Py, PyNativeType};
 use crate::object::PyObject;
 use crate::type_object::PyTypeObject;
-use crate::types::{PyAny, PyType};
+use crate::types::{PyAny, PyTraceback, PyType};
 use crate::AsPyPointer;
 use crate::IntoPyPointer;
 use crate::Python;
-use crate::{exceptions, IntoPy};
+use crate::{exceptions, FromPyObject, IntoPy, ObjectProtocol};
 use crate::{ffi, FromPy};
 use crate::{ToBorrowedObject, ToPyObject};
 use libc::c_int;
@@ -76,6 +76,7 @@ impl PyErr {
     ///
     /// Example:
     ///  `return Err(PyErr::new::<exceptions::TypeError, _>("Error message"));`
+    #[must_use = "a PyErr does nothing unless it is returned or restored"]
     pub fn new<T, V>(value: V) -> PyErr
     where
         T: PyTypeObject,
@@ -95,6 +96,7 @@ impl PyErr {
     /// `exc` is the exception type; usually one of the standard exceptions
     /// like `exceptions::RuntimeError`.
     /// `args` is the a tuple of arguments to pass to the exception constructor.
+    #[must_use = "a PyErr does nothing unless it is returned or restored"]
     pub fn from_type<A>(exc: Py<PyType>, args: A) -> PyErr
     where
         A: ToPyObject + 'static,
@@ -107,6 +109,7 @@ impl PyErr {
     }
 
     /// Creates a new PyErr of type `T`.
+    #[must_use = "a PyErr does nothing unless it is returned or restored"]
     pub fn from_value<T>(value: PyErrValue) -> PyErr
     where
         T: PyTypeObject,
@@ -127,6 +130,7 @@ impl PyErr {
     /// If `obj` is a Python exception type object, the PyErr will (lazily) create a new
     /// instance of that type.
     /// Otherwise, a `TypeError` is created instead.
+    #[must_use = "a PyErr does nothing unless it is returned or restored"]
     pub fn from_instance(obj: &PyAny) -> PyErr {
         let ptr = obj.as_ptr();
 
@@ -302,9 +306,10 @@ impl PyErr {
     }
 
     /// Retrieves the exception instance for this error.
-    /// This method takes `mut self` because the error might need
+    ///
+    /// This method takes `&mut self` because the error might need
     /// to be normalized in order to create the exception instance.
-    fn instance(mut self, py: Python) -> PyObject {
+    pub fn instance(&mut self, py: Python) -> PyObject {
         self.normalize(py);
         match self.pvalue {
             PyErrValue::Value(ref instance) => instance.clone_ref(py),
@@ -312,6 +317,58 @@ impl PyErr {
         }
     }
 
+    /// Returns the cause of this error, i.e. the `__cause__` of the exception instance,
+    /// which is set by `raise ... from cause` in Python or by [PyErr::with_cause].
+    ///
+    /// Like [PyErr::instance], this takes `&mut self` to normalize the error.
+    pub fn cause(&mut self, py: Python) -> Option<PyErr> {
+        let instance = self.instance(py);
+        unsafe {
+            // Returns a new reference
+            let cause = ffi::PyException_GetCause(instance.as_ptr());
+            py.from_owned_ptr_or_opt::<PyAny>(cause)
+                .map(PyErr::from_instance)
+        }
+    }
+
+    /// Returns the traceback of this error, if it has one.
+    ///
+    /// Errors fetched from the interpreter carry the traceback they were raised with;
+    /// errors created in Rust have none until they are raised in Python.
+    pub fn traceback(&self, _py: Python) -> Option<Py<PyTraceback>> {
+        if let Some(ref ptraceback) = self.ptraceback {
+            return Some(unsafe { Py::from_borrowed_ptr(ptraceback.as_ptr()) });
+        }
+        match self.pvalue {
+            PyErrValue::Value(ref instance) => unsafe {
+                if ffi::PyExceptionInstance_Check(instance.as_ptr()) == 0 {
+                    return None;
+                }
+                // Returns a new reference
+                let ptraceback = ffi::PyException_GetTraceback(instance.as_ptr());
+                if ptraceback.is_null() {
+                    None
+                } else {
+                    Some(Py::from_owned_ptr(ptraceback))
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Sets the `__cause__` of this error, like `raise self from cause` in Python.
+    ///
+    /// The cause is shown in the traceback as the direct cause of this error.
+    #[must_use = "a PyErr does nothing unless it is returned or restored"]
+    pub fn with_cause(mut self, py: Python, mut cause: PyErr) -> PyErr {
+        let instance = self.instance(py);
+        if unsafe { ffi::PyExceptionInstance_Check(instance.as_ptr()) } != 0 {
+            // Steals the reference to the cause
+            unsafe { ffi::PyException_SetCause(instance.as_ptr(), cause.instance(py).into_ptr()) };
+        }
+        self
+    }
+
     /// Writes the error back to the Python interpreter's global state.
     /// This is the opposite of `PyErr::fetch()`.
     #[inline]
@@ -382,21 +439,21 @@ impl std::fmt::Debug for PyErr {
 }
 
 impl FromPy<PyErr> for PyObject {
-    fn from_py(other: PyErr, py: Python) -> Self {
+    fn from_py(mut other: PyErr, py: Python) -> Self {
         other.instance(py)
     }
 }
 
 impl ToPyObject for PyErr {
     fn to_object(&self, py: Python) -> PyObject {
-        let err = self.clone_ref(py);
+        let mut err = self.clone_ref(py);
         err.instance(py)
     }
 }
 
 impl<'a> IntoPy<PyObject> for &'a PyErr {
     fn into_py(self, py: Python) -> PyObject {
-        let err = self.clone_ref(py);
+        let mut err = self.clone_ref(py);
         err.instance(py)
     }
 }
@@ -489,6 +546,76 @@ impl PyErrArguments for io::Error {
     }
 }
 
+/// Creates a `RuntimeError` from an arbitrary Rust error, with the error's `source()`s chained
+/// as `__cause__`.
+impl std::convert::From<Box<dyn std::error::Error + Send + Sync>> for PyErr {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> PyErr {
+        PyErr::new::<exceptions::RuntimeError, _>(ErrorChain::new(&*err))
+    }
+}
+
+/// Creates a `RuntimeError` from an arbitrary Rust error, with the error's `source()`s chained
+/// as `__cause__`.
+impl std::convert::From<Box<dyn std::error::Error>> for PyErr {
+    fn from(err: Box<dyn std::error::Error>) -> PyErr {
+        PyErr::new::<exceptions::RuntimeError, _>(ErrorChain::new(&*err))
+    }
+}
+
+/// The messages of a Rust error and its sources, which become a chain of `RuntimeError`s.
+///
+/// The messages are collected eagerly, since the error itself may not be `Send`.
+struct ErrorChain(Vec<String>);
+
+impl ErrorChain {
+    fn new(err: &dyn std::error::Error) -> Self {
+        let mut messages = vec![err.to_string()];
+        let mut source = err.source();
+        while let Some(err) = source {
+            messages.push(err.to_string());
+            source = err.source();
+        }
+        ErrorChain(messages)
+    }
+}
+
+impl ToPyObject for ErrorChain {
+    /// Returns the outermost exception instance, which Python uses as is
+    fn to_object(&self, py: Python) -> PyObject {
+        let mut cause: Option<PyObject> = None;
+        for message in self.0.iter().rev() {
+            let exc = PyErr::new::<exceptions::RuntimeError, _>(message.clone()).instance(py);
+            if let Some(cause) = cause.take() {
+                // Steals the reference to `cause`
+                unsafe { ffi::PyException_SetCause(exc.as_ptr(), cause.into_ptr()) };
+            }
+            cause = Some(exc);
+        }
+        cause.expect("an error chain contains at least one error")
+    }
+}
+
+/// Creates a `RuntimeError` from an `anyhow::Error`, with the error's context chain as
+/// `__cause__`.
+#[cfg(feature = "anyhow")]
+impl std::convert::From<anyhow::Error> for PyErr {
+    fn from(err: anyhow::Error) -> PyErr {
+        PyErr::new::<exceptions::RuntimeError, _>(ErrorChain::new(&*err))
+    }
+}
+
+/// Converts the Python exception into an `anyhow::Error` with the message `TypeName: message`,
+/// since `PyErr` itself can't be sent between threads.
+///
+/// This acquires the GIL.
+#[cfg(feature = "anyhow")]
+impl std::convert::From<PyErr> for anyhow::Error {
+    fn from(err: PyErr) -> anyhow::Error {
+        let gil = Python::acquire_gil();
+        anyhow::Error::msg(error_message(gil.python(), err))
+    }
+}
+
 impl<W: 'static + Send + std::fmt::Debug> std::convert::From<std::io::IntoInnerError<W>> for PyErr {
     fn from(err: std::io::IntoInnerError<W>) -> PyErr {
         PyErr::from_value::<exceptions::OSError>(PyErrValue::from_err_args(err))
@@ -543,10 +670,126 @@ pub fn error_on_minusone(py: Python, result: c_int) -> PyResult<()> {
     }
 }
 
+/// Formats a Python exception as `TypeName: message`.
+pub(crate) fn error_message(py: Python, mut err: PyErr) -> String {
+    let value = err.instance(py);
+    let value = value.as_ref(py);
+    let name = value.get_type().name();
+    match value.str() {
+        Ok(msg) => format!("{}: {}", name, msg.to_string_lossy()),
+        Err(_) => name.into_owned(),
+    }
+}
+
+/// Extracts an item of a collection, mentioning the item in the error on failure.
+///
+/// `kind` describes the item, e.g. `"dict key"`. The original error is returned unchanged,
+/// except that a `TypeError` naming the item is added at the end of its chain of causes,
+/// so that errors from nested collections list the outermost item first in the traceback.
+pub(crate) fn extract_item<'a, T>(ob: &'a PyAny, kind: &str) -> PyResult<T>
+where
+    T: FromPyObject<'a>,
+{
+    T::extract(ob).map_err(|mut err| {
+        let py = ob.py();
+        let repr = match ob.repr() {
+            Ok(repr) => repr.to_string_lossy().into_owned(),
+            Err(_) => "<unprintable object>".to_string(),
+        };
+        let context = exceptions::TypeError::py_err(format!("failed to extract {} {}", kind, repr));
+
+        // `PyErr`s created from the same instance share its cause, so the chain can be
+        // extended in place. Causes may form a cycle, which is where the walk stops.
+        let mut last = PyErr::from_instance(err.instance(py).as_ref(py));
+        let mut seen = vec![last.instance(py).as_ptr()];
+        while let Some(mut cause) = last.cause(py) {
+            let ptr = cause.instance(py).as_ptr();
+            if seen.contains(&ptr) {
+                return err;
+            }
+            seen.push(ptr);
+            last = cause;
+        }
+        let _ = last.with_cause(py, context);
+        err
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::exceptions;
-    use crate::{PyErr, Python};
+    use crate::types::IntoPyDict;
+    use crate::{AsPyRef, FromPy, ObjectProtocol, PyErr, PyObject, PyResult, Python};
+
+    #[test]
+    fn boxed_error_with_source() {
+        #[derive(Debug)]
+        struct Outer(std::num::ParseIntError);
+
+        impl std::fmt::Display for Outer {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "invalid config")
+            }
+        }
+
+        impl std::error::Error for Outer {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        fn parse(value: &str) -> PyResult<i32> {
+            let value = value
+                .parse()
+                .map_err(|e| Box::new(Outer(e)) as Box<dyn std::error::Error + Send + Sync>)?;
+            Ok(value)
+        }
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert_eq!(parse("1").unwrap(), 1);
+
+        let err = parse("x").unwrap_err();
+        let locals = [("err", PyObject::from_py(err, py))].into_py_dict(py);
+        py.run(
+            "assert isinstance(err, RuntimeError) and str(err) == 'invalid config'\n\
+             assert isinstance(err.__cause__, RuntimeError)\n\
+             assert str(err.__cause__) == 'invalid digit found in string'\n\
+             assert err.__cause__.__cause__ is None",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "anyhow")]
+    fn anyhow_roundtrip() {
+        use anyhow::Context;
+
+        fn parse(value: &str) -> anyhow::Result<i32> {
+            Ok(value.parse::<i32>().context("invalid number")?)
+        }
+
+        fn parse_py(value: &str) -> PyResult<i32> {
+            Ok(parse(value)?)
+        }
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let mut err = parse_py("x").unwrap_err();
+        assert!(err.is_instance::<exceptions::RuntimeError>(py));
+        let value = err.instance(py);
+        let cause = value.getattr(py, "__cause__").unwrap();
+        assert_eq!(
+            cause.as_ref(py).str().unwrap().to_string_lossy(),
+            "invalid digit found in string"
+        );
+
+        let err: anyhow::Error = PyErr::new::<exceptions::ValueError, _>("bad value").into();
+        assert_eq!(err.to_string(), "ValueError: bad value");
+    }
 
     #[test]
     fn set_typeerror() {
@@ -557,4 +800,51 @@ mod tests {
         assert!(PyErr::occurred(py));
         drop(PyErr::fetch(py));
     }
+
+    #[test]
+    fn error_cause() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let mut err = PyErr::new::<exceptions::ValueError, _>("outer");
+        assert!(err.cause(py).is_none());
+
+        let mut err = err.with_cause(py, PyErr::new::<exceptions::KeyError, _>("inner"));
+        let mut cause = err.cause(py).unwrap();
+        assert!(cause.is_instance::<exceptions::KeyError>(py));
+        assert!(cause.cause(py).is_none());
+
+        let locals = [("err", PyObject::from_py(err, py))].into_py_dict(py);
+        py.run(
+            "assert isinstance(err.__cause__, KeyError)\n\
+             assert err.__suppress_context__",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+
+        let mut err = py
+            .run(
+                "try:\n    {}['x']\nexcept KeyError as e:\n    raise ValueError() from e",
+                None,
+                None,
+            )
+            .unwrap_err();
+        assert!(err
+            .cause(py)
+            .unwrap()
+            .is_instance::<exceptions::KeyError>(py));
+    }
+
+    #[test]
+    fn instance_is_normalized() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut err = PyErr::new::<exceptions::ValueError, _>("some error");
+        let instance = err.instance(py);
+        assert!(py
+            .is_instance::<exceptions::ValueError, _>(&instance)
+            .unwrap());
+        assert_eq!(instance, err.instance(py));
+    }
 }
@@ -29,6 +29,7 @@ macro_rules! impl_exception_boilerplate {
         }
 
         impl $name {
+            #[must_use = "a PyErr does nothing unless it is returned or restored"]
             pub fn py_err<T: $crate::ToPyObject + 'static>(args: T) -> $crate::PyErr {
                 $crate::PyErr::new::<Self, T>(args)
             }
@@ -216,6 +217,7 @@ macro_rules! impl_native_exception (
             }
         }
         impl $name {
+            #[must_use = "a PyErr does nothing unless it is returned or restored"]
             pub fn py_err<V: ToPyObject + 'static>(args: V) -> PyErr {
                 PyErr::new::<$name, V>(args)
             }
@@ -35,6 +35,30 @@ pub type PyVectorcall_Call = unsafe extern "C" fn(
     dict: *mut PyObject,
 ) -> *mut PyObject;
 
+#[cfg(all(Py_3_8, not(Py_LIMITED_API)))]
+pub type vectorcallfunc = unsafe extern "C" fn(
+    callable: *mut PyObject,
+    args: *const *mut PyObject,
+    nargsf: libc::size_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject;
+
+/// Returns the vectorcall function of `callable`, if its type supports the protocol.
+#[cfg(all(Py_3_8, not(Py_LIMITED_API), not(PyPy)))]
+#[inline]
+pub unsafe fn _PyVectorcall_Function(callable: *mut PyObject) -> Option<vectorcallfunc> {
+    let tp = Py_TYPE(callable);
+    if crate::ffi::object::PyType_HasFeature(tp, crate::ffi::object::_Py_TPFLAGS_HAVE_VECTORCALL)
+        == 0
+    {
+        return None;
+    }
+    let offset = (*tp).tp_vectorcall_offset;
+    debug_assert!(offset > 0);
+    let ptr = (callable as *const c_char).offset(offset) as *const Option<vectorcallfunc>;
+    *ptr
+}
+
 #[cfg(all(Py_3_7, not(Py_LIMITED_API)))]
 const PY_VECTORCALL_ARGUMENTS_OFFSET: crate::ffi::pyport::Py_ssize_t =
     1 << (8 * std::mem::size_of::<usize>() - 1);
@@ -762,6 +762,9 @@ extern "C" {
     pub fn PyType_ClearCache() -> c_uint;
     #[cfg_attr(PyPy, link_name = "PyPyType_Modified")]
     pub fn PyType_Modified(t: *mut PyTypeObject);
+    #[cfg(not(Py_LIMITED_API))]
+    #[cfg_attr(PyPy, link_name = "_PyPyType_Lookup")]
+    pub fn _PyType_Lookup(t: *mut PyTypeObject, name: *mut PyObject) -> *mut PyObject;
 
     #[cfg(not(Py_LIMITED_API))]
     #[cfg_attr(PyPy, link_name = "PyPyObject_Print")]
@@ -857,6 +860,14 @@ pub const Py_TPFLAGS_BASETYPE: c_ulong = (1 << 10);
 #[cfg(all(Py_3_8, not(Py_LIMITED_API)))]
 pub const _Py_TPFLAGS_HAVE_VECTORCALL: c_ulong = (1 << 11);
 
+/// Set if instances of the type match sequence patterns (PEP 634)
+#[cfg(Py_3_10)]
+pub const Py_TPFLAGS_SEQUENCE: c_ulong = (1 << 5);
+
+/// Set if instances of the type match mapping patterns (PEP 634)
+#[cfg(Py_3_10)]
+pub const Py_TPFLAGS_MAPPING: c_ulong = (1 << 6);
+
 /// Set if the type is 'ready' -- fully initialized
 pub const Py_TPFLAGS_READY: c_ulong = (1 << 12);
 
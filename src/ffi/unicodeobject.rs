@@ -1,7 +1,7 @@
 use crate::ffi::object::*;
-use crate::ffi::pyport::Py_ssize_t;
+use crate::ffi::pyport::{Py_hash_t, Py_ssize_t};
 use libc::wchar_t;
-use std::os::raw::{c_char, c_int, c_void};
+use std::os::raw::{c_char, c_int, c_uint, c_void};
 
 #[cfg(not(Py_LIMITED_API))]
 pub type Py_UNICODE = wchar_t;
@@ -29,6 +29,29 @@ pub unsafe fn PyUnicode_CheckExact(op: *mut PyObject) -> c_int {
     (Py_TYPE(op) == &mut PyUnicode_Type) as c_int
 }
 
+/// The header of all string objects in CPython.
+///
+/// Only the fields up to `state` are declared, since the later ones differ
+/// between Python versions.
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+#[repr(C)]
+pub struct PyASCIIObject {
+    pub ob_base: PyObject,
+    pub length: Py_ssize_t,
+    pub hash: Py_hash_t,
+    pub state: u32,
+}
+
+/// Returns `SSTATE_NOT_INTERNED` (0) if the string is not interned.
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+#[inline]
+pub unsafe fn PyUnicode_CHECK_INTERNED(op: *mut PyObject) -> c_uint {
+    (*(op as *mut PyASCIIObject)).state & 0b11
+}
+
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+pub const SSTATE_NOT_INTERNED: c_uint = 0;
+
 pub const Py_UNICODE_REPLACEMENT_CHARACTER: Py_UCS4 = 0xFFFD;
 
 #[cfg_attr(windows, link(name = "pythonXY"))]
@@ -82,14 +82,21 @@ where
     }
 
     unsafe fn dealloc(py: Python, self_: *mut Self::ConcreteLayout) {
-        (*self_).py_drop(py);
-
         let obj = self_ as _;
+        // The finalizer (`__del__`) needs the Rust value, and it may resurrect the object
         if ffi::PyObject_CallFinalizerFromDealloc(obj) < 0 {
             return;
         }
+        (*self_).py_drop(py);
 
-        if let Some(obj) = <Self as PyClassWithFreeList>::get_free_list().insert(obj) {
+        // Instances of subclasses defined in Python have a different size and type
+        let subtype = ffi::Py_TYPE(obj);
+        if subtype != <Self as PyTypeInfo>::type_object().as_ptr() {
+            match (*subtype).tp_free {
+                Some(free) => free(obj as *mut c_void),
+                None => tp_free_fallback(obj),
+            }
+        } else if let Some(obj) = <Self as PyClassWithFreeList>::get_free_list().insert(obj) {
             match Self::type_object().as_ref().tp_free {
                 Some(free) => free(obj as *mut c_void),
                 None => tp_free_fallback(obj),
@@ -6,6 +6,7 @@ use crate::ffi;
 use crate::internal_tricks::Unsendable;
 use crate::types::PyAny;
 use crate::Python;
+use std::cell::UnsafeCell;
 use std::ptr::NonNull;
 use std::{any, sync};
 
@@ -271,6 +272,62 @@ impl GILGuard {
     }
 }
 
+/// A container that can be shared between threads, whose contents may only be accessed
+/// while holding the GIL.
+///
+/// Since the GIL guarantees that only one thread runs Python code at a time, this allows
+/// sharing data, e.g. in a `static` or a `#[pyclass]`, without the overhead of a `Mutex`.
+///
+/// # Example
+/// ```
+/// use pyo3::{GILProtected, Python};
+/// use std::cell::Cell;
+///
+/// static COUNTER: GILProtected<Cell<u32>> = GILProtected::new(Cell::new(0));
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let counter = COUNTER.get(py);
+/// counter.set(counter.get() + 1);
+/// assert_eq!(COUNTER.get(py).get(), 1);
+/// ```
+pub struct GILProtected<T> {
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for GILProtected<T> {}
+unsafe impl<T: Send> Sync for GILProtected<T> {}
+
+impl<T> GILProtected<T> {
+    /// Creates a new container holding `value`.
+    pub const fn new(value: T) -> Self {
+        GILProtected {
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns a reference to the contents, which is valid as long as the GIL is held.
+    ///
+    /// Use interior mutability, e.g. `Cell` or `RefCell`, to modify the contents.
+    pub fn get<'py>(&'py self, _py: Python<'py>) -> &'py T {
+        unsafe { &*self.value.get() }
+    }
+
+    /// Returns a mutable reference to the contents.
+    ///
+    /// This doesn't need the GIL, since `&mut self` already guarantees exclusive access.
+    /// Handing out `&mut T` from `&self` would not be sound even under the GIL,
+    /// because two calls could then alias.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.value.get() }
+    }
+
+    /// Consumes the container, returning the contents.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
 use self::array_list::ArrayList;
 
 mod array_list {
@@ -329,7 +386,7 @@ mod array_list {
 
 #[cfg(test)]
 mod test {
-    use super::{GILPool, NonNull, ReleasePool, POOL};
+    use super::{GILPool, GILProtected, NonNull, ReleasePool, POOL};
     use crate::object::PyObject;
     use crate::AsPyPointer;
     use crate::Python;
@@ -501,4 +558,28 @@ mod test {
             assert_eq!(ffi::Py_REFCNT(obj_ptr), 1);
         }
     }
+
+    #[test]
+    fn test_gil_protected() {
+        use std::cell::RefCell;
+        use std::sync::Arc;
+
+        let protected = Arc::new(GILProtected::new(RefCell::new(Vec::new())));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let protected = protected.clone();
+                std::thread::spawn(move || {
+                    let gil = Python::acquire_gil();
+                    protected.get(gil.python()).borrow_mut().push(i);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut protected = Arc::try_unwrap(protected).ok().unwrap();
+        protected.get_mut().get_mut().sort();
+        assert_eq!(protected.into_inner().into_inner(), vec![0, 1, 2, 3]);
+    }
 }
@@ -3,10 +3,10 @@ use crate::err::{PyErr, PyResult};
 use crate::gil;
 use crate::object::PyObject;
 use crate::objectprotocol::ObjectProtocol;
-use crate::pyclass::{PyClass, PyClassShell};
+use crate::pyclass::{PyCell, PyClass, PyFrozenClass, PyRef, PyRefMut};
 use crate::pyclass_init::PyClassInitializer;
-use crate::type_object::{PyObjectLayout, PyTypeInfo};
-use crate::types::PyAny;
+use crate::type_object::{PyDowncastImpl, PyTypeInfo};
+use crate::types::{PyAny, PyDict, PyTuple};
 use crate::{ffi, IntoPy};
 use crate::{AsPyPointer, FromPyObject, IntoPyPointer, Python, ToPyObject};
 use std::marker::PhantomData;
@@ -97,6 +97,77 @@ impl<T> Py<T> {
         Py(NonNull::new_unchecked(ptr), PhantomData)
     }
 
+    /// Immutably borrows the value of a `#[pyclass]`.
+    ///
+    /// Fails with a `RuntimeError` if the value is currently mutably borrowed.
+    /// See [PyCell::try_borrow].
+    pub fn try_borrow(&self, py: Python) -> PyResult<PyRef<T>>
+    where
+        T: PyClass,
+    {
+        self.as_shell(py).try_borrow()
+    }
+
+    /// Mutably borrows the value of a `#[pyclass]`.
+    ///
+    /// Fails with a `RuntimeError` if the value is currently borrowed.
+    /// See [PyCell::try_borrow_mut].
+    pub fn try_borrow_mut(&self, py: Python) -> PyResult<PyRefMut<T>>
+    where
+        T: PyClass,
+    {
+        self.as_shell(py).try_borrow_mut()
+    }
+
+    /// Calls `f` with a shared reference to the inner value and returns its result.
+    ///
+    /// The reference cannot outlive the call to `f`, and the borrow is released even if `f`
+    /// panics.
+    ///
+    /// Panics if the value is currently mutably borrowed.
+    pub fn with<R>(&self, py: Python, f: impl FnOnce(&T) -> R) -> R
+    where
+        T: PyClass,
+    {
+        let value = self
+            .try_borrow(py)
+            .expect("Py::with: the value is already mutably borrowed");
+        f(&*value)
+    }
+
+    /// Calls `f` with a mutable reference to the inner value and returns its result.
+    ///
+    /// The reference cannot outlive the call to `f`, and the borrow is released even if `f`
+    /// panics.
+    ///
+    /// Panics if the value is currently borrowed.
+    pub fn with_mut<R>(&self, py: Python, f: impl FnOnce(&mut T) -> R) -> R
+    where
+        T: PyClass,
+    {
+        let mut value = self
+            .try_borrow_mut(py)
+            .expect("Py::with_mut: the value is already borrowed");
+        f(&mut *value)
+    }
+
+    /// Gets a shared reference to the value of a `#[pyclass(frozen)]`.
+    ///
+    /// See [PyCell::get].
+    pub fn get(&self, py: Python) -> &T
+    where
+        T: PyFrozenClass,
+    {
+        self.as_shell(py).get()
+    }
+
+    fn as_shell(&self, _py: Python) -> &PyCell<T>
+    where
+        T: PyClass,
+    {
+        unsafe { &*(self.as_ptr() as *const PyCell<T>) }
+    }
+
     /// Gets the reference count of the ffi::PyObject pointer.
     #[inline]
     pub fn get_refcnt(&self) -> isize {
@@ -109,6 +180,75 @@ impl<T> Py<T> {
         unsafe { Py::from_borrowed_ptr(self.0.as_ptr()) }
     }
 
+    /// Calls the object.
+    /// This is equivalent to the Python expression: `self(*args, **kwargs)`.
+    ///
+    /// For a `Py<PyType>` this constructs a new instance of the type.
+    pub fn call(
+        &self,
+        py: Python,
+        args: impl IntoPy<Py<PyTuple>>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        self.as_any(py).call(args, kwargs).map(Into::into)
+    }
+
+    /// Calls a method on the object.
+    /// This is equivalent to the Python expression: `self.name(*args, **kwargs)`.
+    pub fn call_method(
+        &self,
+        py: Python,
+        name: &str,
+        args: impl IntoPy<Py<PyTuple>>,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        self.as_any(py)
+            .call_method(name, args, kwargs)
+            .map(Into::into)
+    }
+
+    /// Returns whether the object is considered to be None.
+    /// This is equivalent to the Python expression: 'is None'
+    pub fn is_none(&self) -> bool {
+        unsafe { ffi::Py_None() == self.as_ptr() }
+    }
+
+    /// Returns whether the object is considered to be true.
+    /// This is equivalent to the Python expression: 'not not self'
+    pub fn is_true(&self, py: Python) -> PyResult<bool> {
+        self.as_any(py).is_true()
+    }
+
+    /// Returns whether the object is an instance of `U` or a subclass of it.
+    /// This is equivalent to the Python expression: 'isinstance(self, U)'
+    pub fn is_instance_of<U: PyTypeInfo>(&self, py: Python) -> bool {
+        U::is_instance(self.as_any(py))
+    }
+
+    /// Returns whether the object is callable.
+    /// This is equivalent to the Python expression: 'callable(self)'
+    pub fn is_callable(&self, py: Python) -> bool {
+        self.as_any(py).is_callable()
+    }
+
+    /// Creates a weak reference to the object, like `weakref.ref(self)` in Python.
+    ///
+    /// Fails with a `TypeError` if the type doesn't support weak references; for a
+    /// `#[pyclass]` this requires `#[pyclass(weakref)]`.
+    pub fn downgrade(&self, py: Python) -> PyResult<PyWeakRef<T>> {
+        let weakref = unsafe {
+            PyObject::from_owned_ptr_or_err(
+                py,
+                ffi::PyWeakref_NewRef(self.as_ptr(), std::ptr::null_mut()),
+            )?
+        };
+        Ok(PyWeakRef(weakref, PhantomData))
+    }
+
+    fn as_any<'p>(&'p self, _py: Python<'p>) -> &'p PyAny {
+        unsafe { &*(self as *const Py<T> as *const PyAny) }
+    }
+
     /// Returns the inner pointer without decreasing the refcount
     ///
     /// This will eventually move into its own trait
@@ -119,15 +259,60 @@ impl<T> Py<T> {
     }
 }
 
+/// A weak reference to a Python object, created with [Py::downgrade].
+///
+/// Like `std::sync::Weak`, it doesn't keep the object alive, so it must be upgraded
+/// to a `Py<T>` before the object can be used.
+pub struct PyWeakRef<T>(PyObject, PhantomData<T>);
+
+impl<T> PyWeakRef<T> {
+    /// Returns a strong reference to the object, or `None` if it has been destroyed.
+    pub fn upgrade(&self, _py: Python) -> Option<Py<T>> {
+        unsafe {
+            // Returns a borrowed reference, which is `None` once the object is dead
+            let ptr = ffi::PyWeakref_GetObject(self.0.as_ptr());
+            if ptr.is_null() || ptr == ffi::Py_None() {
+                None
+            } else {
+                Some(Py::from_borrowed_ptr(ptr))
+            }
+        }
+    }
+
+    /// Clone self, Calls Py_INCREF() on the weak reference object.
+    #[inline]
+    pub fn clone_ref(&self, py: Python) -> PyWeakRef<T> {
+        PyWeakRef(self.0.clone_ref(py), PhantomData)
+    }
+}
+
+// Implemented by hand so that `T` doesn't need to implement `Debug`
+impl<T> std::fmt::Debug for PyWeakRef<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_tuple("PyWeakRef").field(&self.0).finish()
+    }
+}
+
+impl<T> AsPyPointer for PyWeakRef<T> {
+    /// Gets the underlying FFI pointer of the weak reference object.
+    #[inline]
+    fn as_ptr(&self) -> *mut ffi::PyObject {
+        self.0.as_ptr()
+    }
+}
+
 pub trait AsPyRef<T: PyTypeInfo>: Sized {
     /// Return reference to object.
-    fn as_ref(&self, py: Python) -> &T;
+    ///
+    /// For a `#[pyclass]` this is the `PyCell` holding the value, which has to be borrowed
+    /// with [PyCell::borrow] or [PyCell::try_borrow] to access it.
+    fn as_ref(&self, py: Python) -> &T::AsRefTarget;
 }
 
 impl<T: PyTypeInfo> AsPyRef<T> for Py<T> {
-    fn as_ref(&self, _py: Python) -> &T {
+    fn as_ref(&self, _py: Python) -> &T::AsRefTarget {
         let any = self as *const Py<T> as *const PyAny;
-        unsafe { T::ConcreteLayout::internal_ref_cast(&*any) }
+        unsafe { PyDowncastImpl::unchecked_downcast(&*any) }
     }
 }
 
@@ -174,21 +359,21 @@ where
     }
 }
 
-// `&PyClassShell<T>` can be converted to `Py<T>`
-impl<'a, T> std::convert::From<&PyClassShell<T>> for Py<T>
+// `&PyCell<T>` can be converted to `Py<T>`
+impl<'a, T> std::convert::From<&PyCell<T>> for Py<T>
 where
     T: PyClass,
 {
-    fn from(shell: &PyClassShell<T>) -> Self {
+    fn from(shell: &PyCell<T>) -> Self {
         unsafe { Py::from_borrowed_ptr(shell.as_ptr()) }
     }
 }
 
-impl<'a, T> std::convert::From<&mut PyClassShell<T>> for Py<T>
+impl<'a, T> std::convert::From<&mut PyCell<T>> for Py<T>
 where
     T: PyClass,
 {
-    fn from(shell: &mut PyClassShell<T>) -> Self {
+    fn from(shell: &mut PyCell<T>) -> Self {
         unsafe { Py::from_borrowed_ptr(shell.as_ptr()) }
     }
 }
@@ -284,6 +469,14 @@ impl<'p, T: ToPyObject> ManagedPyRef<'p, T> {
     pub fn from_to_pyobject(py: Python<'p>, to_pyobject: &T) -> Self {
         to_pyobject.to_managed_py_ref(py)
     }
+
+    /// Converts the reference into an owned `PyObject`, calls Py_INCREF() on the ptr.
+    ///
+    /// The returned handle stays valid after the `ManagedPyRef` has been dropped. It is a
+    /// `PyObject` rather than a `Py<T>`, since `T` is the Rust type that was converted.
+    pub fn as_owned(&self, py: Python) -> PyObject {
+        unsafe { PyObject::from_borrowed_ptr(py, self.data) }
+    }
 }
 
 impl<'p, T: ToPyObject> AsPyPointer for ManagedPyRef<'p, T> {
@@ -350,9 +543,10 @@ impl<'p, T: ToPyObject + ?Sized> Drop for ManagedPyRef<'p, T> {
 #[cfg(test)]
 mod test {
     use super::{ManagedPyRef, Py};
+    use crate::exceptions;
     use crate::ffi;
-    use crate::types::PyDict;
-    use crate::{AsPyPointer, Python};
+    use crate::types::{IntoPyDict, PyAny, PyDict, PyInt, PyType};
+    use crate::{AsPyPointer, AsPyRef, PyObject, Python, ToPyObject};
 
     #[test]
     fn py_from_dict() {
@@ -365,6 +559,77 @@ mod test {
         assert_eq!(unsafe { ffi::Py_REFCNT(dict.as_ptr()) }, 1);
     }
 
+    #[test]
+    fn py_type_call() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let int_type: Py<PyType> = py.get_type::<PyInt>().into();
+        let obj = int_type.call(py, ("ff",), Some([("base", 16)].into_py_dict(py)));
+        assert_eq!(obj.unwrap().extract::<i32>(py).unwrap(), 255);
+        assert!(int_type.call(py, ("foo",), None).is_err());
+    }
+
+    #[test]
+    fn py_call_and_call_method() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict: Py<PyDict> = [("a", 1)].into_py_dict(py).into();
+
+        let value = dict.call_method(py, "get", ("b", 2), None).unwrap();
+        assert_eq!(value.extract::<i32>(py).unwrap(), 2);
+        assert!(dict.call(py, (), None).is_err());
+
+        let dict_type: Py<PyType> = py.get_type::<PyDict>().into();
+        let copy = dict_type.call(py, (), Some(dict.as_ref(py))).unwrap();
+        assert_eq!(
+            copy.call_method1(py, "get", ("a",))
+                .unwrap()
+                .extract::<i32>(py)
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn py_is_none_is_true_is_instance_of() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let none: Py<PyAny> = py.None().as_ref(py).into();
+        assert!(none.is_none());
+        assert!(!none.is_true(py).unwrap());
+        assert!(!none.is_callable(py));
+
+        let dict: Py<PyDict> = [("a", 1)].into_py_dict(py).into();
+        assert!(!dict.is_none());
+        assert!(dict.is_true(py).unwrap());
+        assert!(dict.is_instance_of::<PyDict>(py));
+        assert!(!dict.is_instance_of::<PyType>(py));
+
+        let dict_type: Py<PyType> = py.get_type::<PyDict>().into();
+        assert!(dict_type.is_callable(py));
+    }
+
+    #[test]
+    fn py_downgrade_and_upgrade() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let locals = PyDict::new(py);
+        py.run("class A:\n    pass\na = A()", None, Some(locals))
+            .unwrap();
+        let a: Py<PyAny> = locals.get_item_with_error("a").unwrap().unwrap().into();
+
+        let weak = a.downgrade(py).unwrap();
+        assert_eq!(weak.upgrade(py).unwrap().as_ptr(), a.as_ptr());
+
+        locals.del_item("a").unwrap();
+        drop(a);
+        assert!(weak.upgrade(py).is_none());
+
+        let number: Py<PyAny> = 1.to_object(py).as_ref(py).into();
+        let err = number.downgrade(py).unwrap_err();
+        assert!(err.is_instance::<exceptions::TypeError>(py));
+    }
+
     #[test]
     fn borrowed_py_ref_with_to_pointer() {
         let gil = Python::acquire_gil();
@@ -397,4 +662,17 @@ mod test {
             ffi::Py_DECREF(ptr);
         }
     }
+
+    #[test]
+    fn managed_py_ref_as_owned() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let convertible = (1, 2, 3);
+        let borrowed = ManagedPyRef::from_to_pyobject(py, &convertible);
+        let owned: PyObject = borrowed.as_owned(py);
+        assert_eq!(owned.as_ptr(), borrowed.data);
+        assert_eq!(2, owned.get_refcnt());
+        drop(borrowed);
+        assert_eq!(1, owned.get_refcnt());
+    }
 }
@@ -123,11 +123,13 @@ pub use crate::conversion::{
     ToBorrowedObject, ToPyObject,
 };
 pub use crate::err::{PyDowncastError, PyErr, PyErrArguments, PyErrValue, PyResult};
-pub use crate::gil::{init_once, GILGuard, GILPool};
-pub use crate::instance::{AsPyRef, ManagedPyRef, Py, PyNativeType};
+pub use crate::gil::{init_once, GILGuard, GILPool, GILProtected};
+pub use crate::instance::{AsPyRef, ManagedPyRef, Py, PyNativeType, PyWeakRef};
 pub use crate::object::PyObject;
 pub use crate::objectprotocol::ObjectProtocol;
-pub use crate::pyclass::{PyClass, PyClassShell};
+#[allow(deprecated)]
+pub use crate::pyclass::PyClassShell;
+pub use crate::pyclass::{PyCell, PyClass, PyClassInfo, PyFrozenClass, PyRef, PyRefMut};
 pub use crate::pyclass_init::PyClassInitializer;
 pub use crate::python::{prepare_freethreaded_python, Python};
 pub use crate::type_object::{type_flags, PyTypeInfo};
@@ -148,6 +150,8 @@ pub use libc;
 #[doc(hidden)]
 pub use unindent;
 
+#[cfg(feature = "tokio")]
+pub mod async_runtime;
 pub mod buffer;
 #[doc(hidden)]
 pub mod callback;
@@ -174,6 +178,8 @@ pub mod pyclass;
 pub mod pyclass_init;
 pub mod pyclass_slots;
 mod python;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod type_object;
 pub mod types;
 
@@ -206,6 +212,37 @@ macro_rules! wrap_pymodule {
     }};
 }
 
+/// Adds several `#[pyclass]`es to a module with [types::PyModule::add_class],
+/// stopping at the first error.
+///
+/// # Example
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::add_classes;
+///
+/// #[pyclass]
+/// struct Foo {}
+///
+/// #[pyclass]
+/// struct Bar {}
+///
+/// #[pymodule]
+/// fn my_module(_py: Python, m: &PyModule) -> PyResult<()> {
+///     add_classes!(m, [Foo, Bar])?;
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! add_classes {
+    ($module: expr, [$($class: ty),+ $(,)?]) => {{
+        let module: &$crate::types::PyModule = $module;
+        (|| -> $crate::PyResult<()> {
+            $(module.add_class::<$class>()?;)+
+            Ok(())
+        })()
+    }};
+}
+
 /// A convenient macro to execute a Python code snippet, with some local variables set.
 ///
 /// # Example
@@ -221,7 +258,7 @@ macro_rules! wrap_pymodule {
 ///
 /// # Example
 /// ```
-/// use pyo3::{prelude::*, py_run, PyClassShell};
+/// use pyo3::{prelude::*, py_run, PyCell};
 /// #[pyclass]
 /// #[derive(Debug)]
 /// struct Time {
@@ -244,7 +281,7 @@ macro_rules! wrap_pymodule {
 /// }
 /// let gil = Python::acquire_gil();
 /// let py = gil.python();
-/// let time = PyClassShell::new_ref(py, Time {hour: 8, minute: 43, second: 16}).unwrap();
+/// let time = PyCell::new_ref(py, Time {hour: 8, minute: 43, second: 16}).unwrap();
 /// let time_as_tuple = (8, 43, 16);
 /// py_run!(py, time time_as_tuple, r#"
 /// assert time.hour == 8
@@ -272,20 +309,20 @@ macro_rules! py_run {
 #[doc(hidden)]
 macro_rules! py_run_impl {
     ($py:expr, $($val:ident)+, $code:expr) => {{
-        use pyo3::types::IntoPyDict;
         use pyo3::ToPyObject;
-        let d = [$((stringify!($val), $val.to_object($py)),)+].into_py_dict($py);
-
-        $py.run($code, None, Some(d))
-            .map_err(|e| {
-                e.print($py);
-                // So when this c api function the last line called printed the error to stderr,
-                // the output is only written into a buffer which is never flushed because we
-                // panic before flushing. This is where this hack comes into place
-                $py.run("import sys; sys.stderr.flush()", None, None)
-                    .unwrap();
-            })
-            .expect($code)
+        $py.run_with_locals(
+            $code,
+            &[$((stringify!($val), &$val.to_object($py) as &dyn ToPyObject),)+],
+        )
+        .map_err(|e| {
+            e.print($py);
+            // So when this c api function the last line called printed the error to stderr,
+            // the output is only written into a buffer which is never flushed because we
+            // panic before flushing. This is where this hack comes into place
+            $py.run("import sys; sys.stderr.flush()", None, None)
+                .unwrap();
+        })
+        .expect($code)
     }};
 }
 
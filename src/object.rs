@@ -4,9 +4,10 @@ use crate::err::{PyDowncastError, PyErr, PyResult};
 use crate::ffi;
 use crate::gil;
 use crate::instance::{AsPyRef, PyNativeType};
-use crate::types::{PyAny, PyDict, PyTuple};
-use crate::{AsPyPointer, Py, Python};
+use crate::types::{PyAny, PyDict, PyString, PyTuple, PyType};
+use crate::{AsPyPointer, Py, PyTypeInfo, Python};
 use crate::{FromPyObject, IntoPy, IntoPyPointer, PyTryFrom, ToBorrowedObject, ToPyObject};
+use std::ffi::CStr;
 use std::ptr::NonNull;
 
 /// A python object
@@ -148,6 +149,49 @@ impl PyObject {
         }
     }
 
+    /// Returns whether the object is an instance of `T` or a subclass of it.
+    /// This is equivalent to the Python expression: 'isinstance(self, T)'
+    pub fn is_instance_of<T: PyTypeInfo>(&self, py: Python) -> bool {
+        T::is_instance(self.as_ref(py))
+    }
+
+    /// Returns whether the object is callable.
+    /// This is equivalent to the Python expression: 'callable(self)'
+    pub fn is_callable(&self, _py: Python) -> bool {
+        unsafe { ffi::PyCallable_Check(self.as_ptr()) != 0 }
+    }
+
+    /// Computes the string representation of the object, never failing.
+    /// This is equivalent to the Python expression: 'repr(self)'
+    ///
+    /// If `repr()` raises, the exception is discarded and a placeholder of the form
+    /// `<TypeName object at 0xADDR>` is returned instead, which makes this method
+    /// suitable for `Debug` impls and logging.
+    pub fn safe_repr(&self, py: Python) -> String {
+        unsafe {
+            let repr = ffi::PyObject_Repr(self.as_ptr());
+            if let Some(repr) = py.from_owned_ptr_or_opt::<PyString>(repr) {
+                return repr.to_string_lossy().into_owned();
+            }
+            // Discard the exception raised by `repr()`
+            PyErr::fetch(py);
+            let type_name = CStr::from_ptr((*ffi::Py_TYPE(self.as_ptr())).tp_name);
+            format!(
+                "<{} object at {:p}>",
+                type_name.to_string_lossy(),
+                self.as_ptr()
+            )
+        }
+    }
+
+    /// Gets the Python type object for this object's type as an owned handle.
+    ///
+    /// Unlike `ObjectProtocol::get_type`, the returned `Py<PyType>` is not bound to the GIL
+    /// lifetime, so it can be stored e.g. in a cache.
+    pub fn py_type(&self, _py: Python) -> Py<PyType> {
+        unsafe { Py::from_borrowed_ptr(ffi::Py_TYPE(self.as_ptr()) as *mut ffi::PyObject) }
+    }
+
     /// Casts the PyObject to a concrete Python object type.
     pub fn cast_as<D>(&self, py: Python) -> Result<&D, PyDowncastError>
     where
@@ -196,6 +240,20 @@ impl PyObject {
         result
     }
 
+    /// Calls the object with an existing argument tuple and keyword dict.
+    /// This is equivalent to the Python expression: 'self(*args, **kwargs)'
+    ///
+    /// Unlike [PyObject::call], `args` and `kwargs` are passed to the callee as they are,
+    /// without any conversion.
+    pub fn call_unpack(&self, py: Python, args: &PyTuple, kwargs: &PyDict) -> PyResult<PyObject> {
+        unsafe {
+            PyObject::from_owned_ptr_or_err(
+                py,
+                ffi::PyObject_Call(self.as_ptr(), args.as_ptr(), kwargs.as_ptr()),
+            )
+        }
+    }
+
     /// Calls the object without arguments.
     /// This is equivalent to the Python expression: 'self()'
     pub fn call0(&self, py: Python) -> PyResult<PyObject> {
@@ -298,6 +356,18 @@ impl<'a> FromPyObject<'a> for PyObject {
     }
 }
 
+/// Formats the object with Python's `str()`, like `Display` for [PyAny].
+///
+/// This acquires the GIL, so it can deadlock or panic where acquiring the GIL does.
+/// If `__str__` raises an exception, formatting fails with `fmt::Error`, which makes
+/// `format!` and `to_string` panic.
+impl std::fmt::Display for PyObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let gil = Python::acquire_gil();
+        std::fmt::Display::fmt(self.as_ref(gil.python()), f)
+    }
+}
+
 /// Dropping a `PyObject` instance decrements the reference count on the object by 1.
 impl Drop for PyObject {
     fn drop(&mut self) {
@@ -308,10 +378,33 @@ impl Drop for PyObject {
 }
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod test {
-    use crate::types::PyDict;
-    use crate::PyObject;
+    use crate::types::{IntoPyDict, PyDict, PyTuple};
     use crate::Python;
+    use crate::{AsPyRef, ObjectProtocol, PyErr, PyObject, ToPyObject};
+
+    #[test]
+    fn test_is_instance_of_and_is_callable() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj: PyObject = PyDict::new(py).into();
+        assert!(obj.is_instance_of::<PyDict>(py));
+        assert!(!obj.is_instance_of::<PyTuple>(py));
+        assert!(!obj.is_callable(py));
+        let method = obj.getattr(py, "keys").unwrap();
+        assert!(method.is_callable(py));
+    }
+
+    #[test]
+    fn test_display() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj: PyObject = "hello".to_object(py);
+        assert_eq!(format!("{}", obj), "hello");
+        assert_eq!(format!("{:?}", obj.as_ref(py)), "'hello'");
+        assert_eq!(obj.to_string(), "hello");
+    }
 
     #[test]
     fn test_call_for_non_existing_method() {
@@ -325,4 +418,47 @@ mod test {
         assert!(obj.call_method0(py, "nonexistent_method").is_err());
         assert!(obj.call_method1(py, "nonexistent_method", (1,)).is_err());
     }
+
+    #[test]
+    fn test_call_unpack() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict: PyObject = py.get_type::<PyDict>().into();
+        let args = PyTuple::new(py, &[vec![("a", 1)]]);
+        let kwargs = [("b", 2)].into_py_dict(py);
+        let result = dict.call_unpack(py, args, kwargs).unwrap();
+        let result = result.cast_as::<PyDict>(py).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get_item("b").unwrap().extract::<i32>().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_safe_repr() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj: PyObject = PyDict::new(py).into();
+        assert_eq!(obj.safe_repr(py), "{}");
+
+        let locals = PyDict::new(py);
+        py.run(
+            "class Broken:\n    def __repr__(self):\n        raise ValueError()",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        let broken = py.eval("Broken()", None, Some(locals)).unwrap();
+        let obj: PyObject = broken.into();
+        let repr = obj.safe_repr(py);
+        assert!(repr.starts_with("<Broken object at 0x"));
+        assert!(!PyErr::occurred(py));
+    }
+
+    #[test]
+    fn test_py_type() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj: PyObject = PyDict::new(py).into();
+        let ty = obj.py_type(py);
+        assert_eq!(ty.as_ref(py), py.get_type::<PyDict>());
+    }
 }
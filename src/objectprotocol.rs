@@ -69,6 +69,9 @@ pub trait ObjectProtocol {
     ///   * CompareOp::Le: `self <= other`
     ///   * CompareOp::Gt: `self > other`
     ///   * CompareOp::Ge: `self >= other`
+    ///
+    /// The result of the comparison is returned as-is, so it isn't necessarily a `bool`
+    /// (e.g. numpy arrays compare elementwise and return an array).
     fn rich_compare<O>(&self, other: O, compare_op: CompareOp) -> PyResult<PyObject>
     where
         O: ToPyObject;
@@ -81,6 +84,10 @@ pub trait ObjectProtocol {
     /// This is equivalent to the Python expression `str(self)`.
     fn str(&self) -> PyResult<&PyString>;
 
+    /// Formats self according to `format_spec`.
+    /// This is equivalent to the Python expression `format(self, format_spec)`.
+    fn format_str(&self, format_spec: &str) -> PyResult<&PyString>;
+
     /// Determines whether this object is callable.
     fn is_callable(&self) -> bool;
 
@@ -164,6 +171,12 @@ pub trait ObjectProtocol {
     where
         K: ToBorrowedObject;
 
+    /// Determines if self contains `value`.
+    /// This is equivalent to the Python expression `value in self`.
+    fn contains<V>(&self, value: V) -> PyResult<bool>
+    where
+        V: ToBorrowedObject;
+
     /// Takes an object and returns an iterator for it.
     /// This is typically a new iterator but if the argument
     /// is an iterator, this returns itself.
@@ -319,6 +332,13 @@ where
         }
     }
 
+    fn format_str(&self, format_spec: &str) -> PyResult<&PyString> {
+        format_spec.with_borrowed_ptr(self.py(), |format_spec| unsafe {
+            self.py()
+                .from_owned_ptr_or_err(ffi::PyObject_Format(self.as_ptr(), format_spec))
+        })
+    }
+
     fn is_callable(&self) -> bool {
         unsafe { ffi::PyCallable_Check(self.as_ptr()) != 0 }
     }
@@ -442,6 +462,20 @@ where
         })
     }
 
+    fn contains<V>(&self, value: V) -> PyResult<bool>
+    where
+        V: ToBorrowedObject,
+    {
+        let r = value.with_borrowed_ptr(self.py(), |value| unsafe {
+            ffi::PySequence_Contains(self.as_ptr(), value)
+        });
+        match r {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(PyErr::fetch(self.py())),
+        }
+    }
+
     fn iter(&self) -> PyResult<PyIterator> {
         Ok(PyIterator::from_object(self.py(), self)?)
     }
@@ -499,7 +533,7 @@ where
 mod test {
     use super::*;
     use crate::instance::AsPyRef;
-    use crate::types::{IntoPyDict, PyString};
+    use crate::types::{IntoPyDict, PyDict, PyString};
     use crate::Python;
     use crate::{PyTryFrom, ToPyObject};
 
@@ -542,6 +576,78 @@ mod test {
         assert_eq!(list.extract::<Vec<i32>>(py).unwrap(), vec![7, 6, 5, 4, 3]);
     }
 
+    #[test]
+    fn test_format_str() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py.eval("3.14159", None, None).unwrap();
+        assert_eq!(obj.format_str(".2f").unwrap().to_string().unwrap(), "3.14");
+        assert_eq!(obj.format_str("").unwrap().to_string().unwrap(), "3.14159");
+        assert!(obj.format_str("invalid").is_err());
+    }
+
+    #[test]
+    fn test_contains() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = py.eval("[1, 2, 3]", None, None).unwrap();
+        assert!(list.contains(2).unwrap());
+        assert!(!list.contains(4).unwrap());
+        let dict = py.eval("{'a': 1}", None, None).unwrap();
+        assert!(dict.contains("a").unwrap());
+        assert!(!dict.contains(1).unwrap());
+        let int = py.eval("42", None, None).unwrap();
+        assert!(int.contains(4).is_err());
+    }
+
+    #[test]
+    fn test_get_set_item() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = py.eval("[1, 2, 3]", None, None).unwrap();
+        assert_eq!(list.get_item(1).unwrap().extract::<i32>().unwrap(), 2);
+        list.set_item(1, 5).unwrap();
+        assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![1, 5, 3]);
+        assert!(list.get_item(10).is_err());
+        let dict = py.eval("{'a': 1}", None, None).unwrap();
+        dict.set_item("b", 2).unwrap();
+        assert_eq!(dict.get_item("b").unwrap().extract::<i32>().unwrap(), 2);
+        assert!(dict.get_item("c").is_err());
+    }
+
+    #[test]
+    fn test_del_item() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = py.eval("[1, 2, 3]", None, None).unwrap();
+        list.del_item(0).unwrap();
+        assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![2, 3]);
+        let dict = py.eval("{'a': 1, 'b': 2}", None, None).unwrap();
+        dict.del_item("a").unwrap();
+        assert!(!dict.contains("a").unwrap());
+        assert!(dict.del_item("a").is_err());
+    }
+
+    #[test]
+    fn test_rich_compare() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let one = py.eval("1", None, None).unwrap();
+        let result = one.rich_compare(2, CompareOp::Lt).unwrap();
+        assert!(result.extract::<bool>(py).unwrap());
+
+        let locals = PyDict::new(py);
+        py.run(
+            "class Elementwise:\n    def __eq__(self, other):\n        return [other, other]",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        let obj = py.eval("Elementwise()", None, Some(locals)).unwrap();
+        let result = obj.rich_compare(3, CompareOp::Eq).unwrap();
+        assert_eq!(result.extract::<Vec<i32>>(py).unwrap(), vec![3, 3]);
+    }
+
     #[test]
     fn test_type() {
         let gil = Python::acquire_gil();
@@ -2,28 +2,38 @@
 use crate::class::methods::{PyMethodDefType, PyMethodsProtocol};
 use crate::conversion::{AsPyPointer, FromPyPointer, ToPyObject};
 use crate::pyclass_init::PyClassInitializer;
-use crate::pyclass_slots::{PyClassDict, PyClassWeakRef};
-use crate::type_object::{type_flags, PyObjectLayout, PyObjectSizedLayout};
-use crate::types::PyAny;
-use crate::{class, ffi, gil, PyErr, PyObject, PyResult, PyTypeInfo, Python};
+use crate::pyclass_slots::{PyClassDict, PyClassSlots, PyClassVectorcall, PyClassWeakRef};
+use crate::type_object::{type_flags, PyDowncastImpl, PyObjectLayout, PyObjectSizedLayout};
+use crate::types::{PyAny, PyDict, PyType};
+use crate::{
+    class, exceptions, ffi, gil, ObjectProtocol, PyErr, PyObject, PyResult, PyTypeInfo, Python,
+};
+use std::cell::{Cell, UnsafeCell};
 use std::ffi::CString;
-use std::mem::ManuallyDrop;
-use std::os::raw::c_void;
+use std::fmt;
+use std::mem::{self, ManuallyDrop};
+use std::os::raw::{c_int, c_void};
 use std::ptr::{self, NonNull};
 
 #[inline]
 pub(crate) unsafe fn default_alloc<T: PyTypeInfo>() -> *mut ffi::PyObject {
-    let tp_ptr = T::type_object().as_ptr();
+    alloc_with_subtype::<T>(T::type_object().as_ptr())
+}
+
+/// Allocates an object of `subtype`, which is `T` or a subclass of `T` defined in Python.
+pub(crate) unsafe fn alloc_with_subtype<T: PyTypeInfo>(
+    subtype: *mut ffi::PyTypeObject,
+) -> *mut ffi::PyObject {
     if T::FLAGS & type_flags::EXTENDED != 0
         && <T::BaseType as PyTypeInfo>::ConcreteLayout::IS_NATIVE_TYPE
     {
         let base_tp = <T::BaseType as PyTypeInfo>::type_object();
         if let Some(base_new) = base_tp.as_ref().tp_new {
-            return base_new(tp_ptr, ptr::null_mut(), ptr::null_mut());
+            return base_new(subtype, ptr::null_mut(), ptr::null_mut());
         }
     }
-    let alloc = (*tp_ptr).tp_alloc.unwrap_or(ffi::PyType_GenericAlloc);
-    alloc(tp_ptr, 0)
+    let alloc = (*subtype).tp_alloc.unwrap_or(ffi::PyType_GenericAlloc);
+    alloc(subtype, 0)
 }
 
 /// This trait enables custom alloc/dealloc implementations for `T: PyClass`.
@@ -41,13 +51,15 @@ pub trait PyClassAlloc: PyTypeInfo + Sized {
     /// # Safety
     /// `self_` must be a valid pointer to the Python heap.
     unsafe fn dealloc(py: Python, self_: *mut Self::ConcreteLayout) {
-        (*self_).py_drop(py);
         let obj = self_ as _;
+        // The finalizer (`__del__`) needs the Rust value, and it may resurrect the object
         if ffi::PyObject_CallFinalizerFromDealloc(obj) < 0 {
             return;
         }
+        (*self_).py_drop(py);
 
-        match Self::type_object().as_ref().tp_free {
+        // The object may be an instance of a subclass defined in Python
+        match (*ffi::Py_TYPE(obj)).tp_free {
             Some(free) => free(obj as *mut c_void),
             None => tp_free_fallback(obj),
         }
@@ -71,25 +83,94 @@ pub unsafe fn tp_free_fallback(obj: *mut ffi::PyObject) {
 }
 
 /// If `PyClass` is implemented for `T`, then we can use `T` in the Python world,
-/// via `PyClassShell`.
+/// via `PyCell`.
 ///
 /// The `#[pyclass]` attribute automatically implements this trait for your Rust struct,
 /// so you don't have to use this trait directly.
 pub trait PyClass:
-    PyTypeInfo<ConcreteLayout = PyClassShell<Self>> + Sized + PyClassAlloc + PyMethodsProtocol
+    PyTypeInfo<ConcreteLayout = PyCell<Self>> + Sized + PyClassAlloc + PyMethodsProtocol
 {
     type Dict: PyClassDict;
     type WeakRef: PyClassWeakRef;
+    type Slots: PyClassSlots;
+    type Vectorcall: PyClassVectorcall;
+    /// The names in `__slots__`, given with `#[pyclass(slots = [...])]`.
+    const SLOT_NAMES: &'static [&'static str] = &[];
+    /// `tp_hash` generated by `#[pyclass(hash)]`, used unless `PyObjectProtocol` defines it.
+    #[doc(hidden)]
+    const DERIVED_HASH: Option<ffi::hashfunc> = None;
+    /// `tp_richcompare` generated by `#[pyclass(eq)]` or `#[pyclass(ord)]`, used unless
+    /// `PyObjectProtocol` defines it.
+    #[doc(hidden)]
+    const DERIVED_RICHCMP: Option<ffi::richcmpfunc> = None;
+}
+
+/// Metadata about a `#[pyclass]` that is available from Rust without the Python runtime.
+///
+/// The `#[pyclass]` attribute implements this trait for your Rust struct.
+///
+/// ```
+/// # use pyo3::prelude::*;
+/// use pyo3::PyClassInfo;
+///
+/// /// A point in the plane.
+/// #[pyclass(module = "geometry")]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// assert_eq!(Point::class_name(), "Point");
+/// assert_eq!(Point::module_name(), Some("geometry"));
+/// assert_eq!(Point::doc(), "A point in the plane.");
+/// assert_eq!(Point::field_names(), &["x", "y"]);
+/// ```
+pub trait PyClassInfo: PyTypeInfo {
+    /// The name of the class in Python.
+    fn class_name() -> &'static str {
+        Self::NAME
+    }
+
+    /// The module the class belongs to, if one was given with `#[pyclass(module = "...")]`.
+    fn module_name() -> Option<&'static str> {
+        Self::MODULE
+    }
+
+    /// The doc string of the class.
+    fn doc() -> &'static str {
+        Self::DESCRIPTION.trim_end_matches('\0')
+    }
+
+    /// The names of the fields of the Rust struct, in declaration order.
+    fn field_names() -> &'static [&'static str];
 }
 
-/// `PyClassShell` represents the concrete layout of `T: PyClass` when it is converted
-/// to a Python class.
+/// Marker trait for classes declared with `#[pyclass(frozen)]`.
+///
+/// Instances of a frozen class can't be mutated: methods taking `&mut self` or
+/// `&mut PyCell<Self>` and `#[pyo3(set)]` fields are rejected at compile time, and
+/// [PyCell::try_borrow_mut] fails with a `TypeError`. In exchange, the value can be
+/// accessed with [PyCell::get] without any runtime borrow checking.
+/// Frozen classes must be `Send` and `Sync`.
+pub trait PyFrozenClass: PyClass + Send + Sync {}
+
+/// Marker trait for classes that aren't frozen, implemented by `#[pyclass]`.
+///
+/// Methods taking `&mut self` in `#[pymethods]` and `#[pyproto]` require it.
+pub trait PyMutableClass: PyClass {}
+
+/// `PyCell` is the container of a `T: PyClass` in the Python heap, and its concrete layout
+/// when it is converted to a Python class.
+///
+/// Like `RefCell`, it gives access to the value with runtime borrow checking through
+/// [PyCell::borrow] and [PyCell::borrow_mut]. Protocol methods like `__iter__` receive
+/// their receiver as a `&PyCell<Self>`.
 ///
 /// You can use it to test your `#[pyclass]` correctly works.
 ///
 /// ```
 /// # use pyo3::prelude::*;
-/// # use pyo3::{py_run, PyClassShell};
+/// # use pyo3::{py_run, PyCell};
 /// #[pyclass]
 /// struct Book {
 ///     #[pyo3(get)]
@@ -102,19 +183,26 @@ pub trait PyClass:
 ///     name: "The Man in the High Castle",
 ///     author: "Philip Kindred Dick",
 /// };
-/// let book_shell = PyClassShell::new_ref(py, book).unwrap();
+/// let book_shell = PyCell::new_ref(py, book).unwrap();
 /// py_run!(py, book_shell, "assert book_shell.name[-6:] == 'Castle'");
 /// ```
 #[repr(C)]
-pub struct PyClassShell<T: PyClass> {
+pub struct PyCell<T: PyClass> {
     ob_base: <T::BaseType as PyTypeInfo>::ConcreteLayout,
-    pyclass: ManuallyDrop<T>,
+    borrow_flag: Cell<BorrowFlag>,
+    pyclass: UnsafeCell<ManuallyDrop<T>>,
+    slots: UnsafeCell<T::Slots>,
+    vectorcall: T::Vectorcall,
     dict: T::Dict,
     weakref: T::WeakRef,
 }
 
-impl<T: PyClass> PyClassShell<T> {
-    /// Make new `PyClassShell` on the Python heap and returns the reference of it.
+/// The former name of [PyCell].
+#[deprecated(since = "0.9.0", note = "renamed to `PyCell`")]
+pub type PyClassShell<T> = PyCell<T>;
+
+impl<T: PyClass> PyCell<T> {
+    /// Make new `PyCell` on the Python heap and returns the reference of it.
     pub fn new_ref(py: Python, value: impl Into<PyClassInitializer<T>>) -> PyResult<&Self>
     where
         <T::BaseType as PyTypeInfo>::ConcreteLayout:
@@ -127,7 +215,7 @@ impl<T: PyClass> PyClassShell<T> {
         }
     }
 
-    /// Make new `PyClassShell` on the Python heap and returns the mutable reference of it.
+    /// Make new `PyCell` on the Python heap and returns the mutable reference of it.
     pub fn new_mut(py: Python, value: impl Into<PyClassInitializer<T>>) -> PyResult<&mut Self>
     where
         <T::BaseType as PyTypeInfo>::ConcreteLayout:
@@ -140,6 +228,60 @@ impl<T: PyClass> PyClassShell<T> {
         }
     }
 
+    /// Immutably borrows the value, like `RefCell::borrow`.
+    ///
+    /// Doesn't need a `Python` token, since holding a `&PyCell` already proves that the
+    /// GIL is held. See [PyCell::try_borrow] for the non-panicking variant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently mutably borrowed.
+    pub fn borrow(&self) -> PyRef<T> {
+        self.try_borrow().expect("Already mutably borrowed")
+    }
+
+    /// Mutably borrows the value, like `RefCell::borrow_mut`.
+    ///
+    /// See [PyCell::try_borrow_mut] for the non-panicking variant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is currently borrowed, or if `T` is a `#[pyclass(frozen)]`.
+    pub fn borrow_mut(&self) -> PyRefMut<T> {
+        self.try_borrow_mut().expect("Already borrowed")
+    }
+
+    /// Immutably borrows the value, like `RefCell::try_borrow`.
+    ///
+    /// The borrow lasts until the returned `PyRef` is dropped. Multiple immutable borrows can
+    /// be taken out at the same time, but this fails with a `RuntimeError` if the value is
+    /// currently mutably borrowed through a `PyRefMut`.
+    pub fn try_borrow(&self) -> PyResult<PyRef<T>> {
+        match self.borrow_flag.get() {
+            BorrowFlag::HAS_MUTABLE_BORROW => {
+                Err(exceptions::RuntimeError::py_err("Already mutably borrowed"))
+            }
+            flag => {
+                self.borrow_flag.set(flag.increment());
+                Ok(PyRef { shell: self })
+            }
+        }
+    }
+
+    /// Mutably borrows the value, like `RefCell::try_borrow_mut`.
+    ///
+    /// The borrow lasts until the returned `PyRefMut` is dropped. This fails with a
+    /// `RuntimeError` if the value is currently borrowed through a `PyRef` or `PyRefMut`,
+    /// and with a `TypeError` if `T` is a `#[pyclass(frozen)]`.
+    pub fn try_borrow_mut(&self) -> PyResult<PyRefMut<T>> {
+        crate::derive_utils::ensure_mutable::<T>()?;
+        if self.borrow_flag.get() != BorrowFlag::UNUSED {
+            return Err(exceptions::RuntimeError::py_err("Already borrowed"));
+        }
+        self.borrow_flag.set(BorrowFlag::HAS_MUTABLE_BORROW);
+        Ok(PyRefMut { shell: self })
+    }
+
     /// Get the reference of base object.
     pub fn get_super(&self) -> &<T::BaseType as PyTypeInfo>::ConcreteLayout {
         &self.ob_base
@@ -155,100 +297,251 @@ impl<T: PyClass> PyClassShell<T> {
         <T::BaseType as PyTypeInfo>::ConcreteLayout:
             crate::type_object::PyObjectSizedLayout<T::BaseType>,
     {
-        let base = T::alloc(py);
+        Self::new_with_subtype(py, <T as PyTypeInfo>::type_object().as_ptr())
+    }
+
+    /// Allocates an object of `subtype`, which is `T` or a subclass of `T` defined in Python.
+    ///
+    /// Only objects of `T` itself are allocated with [PyClassAlloc::alloc], since custom
+    /// allocators like free lists assume the size and type of `T`.
+    pub(crate) unsafe fn new_with_subtype(
+        py: Python,
+        subtype: *mut ffi::PyTypeObject,
+    ) -> PyResult<*mut Self>
+    where
+        <T::BaseType as PyTypeInfo>::ConcreteLayout:
+            crate::type_object::PyObjectSizedLayout<T::BaseType>,
+    {
+        let base = if subtype == <T as PyTypeInfo>::type_object().as_ptr() {
+            T::alloc(py)
+        } else {
+            alloc_with_subtype::<T>(subtype) as _
+        };
         if base.is_null() {
             return Err(PyErr::fetch(py));
         }
         let self_ = base as *mut Self;
+        (*self_).borrow_flag = Cell::new(BorrowFlag::UNUSED);
+        (*self_).slots = UnsafeCell::new(T::Slots::new());
+        (*self_).vectorcall = T::Vectorcall::new();
         (*self_).dict = T::Dict::new();
         (*self_).weakref = T::WeakRef::new();
         Ok(self_)
     }
+
+    /// A raw pointer to the value. Accesses through it must respect the borrow flag.
+    fn get_ptr(&self) -> *mut T {
+        self.pyclass.get() as *mut T
+    }
+
+    /// Calls `visit` for each value of `__slots__`, as part of `tp_traverse`.
+    pub(crate) unsafe fn traverse_slots(&self, visit: ffi::visitproc, arg: *mut c_void) -> c_int {
+        (*self.slots.get()).traverse_slots(visit, arg)
+    }
+
+    /// Releases the values of `__slots__`, as part of `tp_clear`.
+    ///
+    /// Python writes the slots through the member descriptors, so they are only accessed
+    /// through the `UnsafeCell`.
+    pub(crate) unsafe fn clear_slots(&self, py: Python) {
+        (*self.slots.get()).clear_slots(py)
+    }
+
+    /// The offset of the `__slots__` values from the start of the object.
+    fn slots_offset() -> ffi::Py_ssize_t {
+        // `PyCell` is `#[repr(C)]`, so the offsets of its fields follow from the sizes and
+        // alignments of the fields before them, without touching any memory
+        let offset = mem::size_of::<<T::BaseType as PyTypeInfo>::ConcreteLayout>();
+        let offset = align_to::<Cell<BorrowFlag>>(offset) + mem::size_of::<Cell<BorrowFlag>>();
+        let offset = align_to::<UnsafeCell<ManuallyDrop<T>>>(offset)
+            + mem::size_of::<UnsafeCell<ManuallyDrop<T>>>();
+        align_to::<UnsafeCell<T::Slots>>(offset) as ffi::Py_ssize_t
+    }
+
+    /// The offset of the vectorcall function from the start of the object.
+    #[cfg(all(Py_3_8, not(Py_LIMITED_API)))]
+    fn vectorcall_offset() -> ffi::Py_ssize_t {
+        let offset = Self::slots_offset() as usize + mem::size_of::<UnsafeCell<T::Slots>>();
+        align_to::<T::Vectorcall>(offset) as ffi::Py_ssize_t
+    }
+}
+
+/// Rounds `offset` up to the alignment of `T`, like the field layout of a `#[repr(C)]` struct.
+fn align_to<T>(offset: usize) -> usize {
+    let align = mem::align_of::<T>();
+    (offset + align - 1) / align * align
+}
+
+impl<T: PyFrozenClass> PyCell<T> {
+    /// Gets a shared reference to the value of a frozen class.
+    ///
+    /// Unlike [PyCell::try_borrow], this can't fail, since the value is never mutated.
+    pub fn get(&self) -> &T {
+        unsafe { &*self.get_ptr() }
+    }
 }
 
-impl<T: PyClass> PyObjectLayout<T> for PyClassShell<T> {
+impl<T: PyClass> PyObjectLayout<T> for PyCell<T> {
     const IS_NATIVE_TYPE: bool = false;
     fn get_super_or(&mut self) -> Option<&mut <T::BaseType as PyTypeInfo>::ConcreteLayout> {
         Some(&mut self.ob_base)
     }
     unsafe fn internal_ref_cast(obj: &PyAny) -> &T {
         let shell = obj.as_ptr() as *const Self;
-        &(*shell).pyclass
+        &*(*shell).get_ptr()
     }
     unsafe fn internal_mut_cast(obj: &PyAny) -> &mut T {
-        let shell = obj.as_ptr() as *const _ as *mut Self;
-        &mut (*shell).pyclass
+        let shell = obj.as_ptr() as *const Self;
+        &mut *(*shell).get_ptr()
     }
     unsafe fn py_drop(&mut self, py: Python) {
-        ManuallyDrop::drop(&mut self.pyclass);
+        ManuallyDrop::drop(self.pyclass.get_mut());
+        self.slots.get_mut().clear_slots(py);
         self.dict.clear_dict(py);
         self.weakref.clear_weakrefs(self.as_ptr(), py);
         self.ob_base.py_drop(py);
     }
     unsafe fn py_init(&mut self, value: T) {
-        self.pyclass = ManuallyDrop::new(value);
+        self.pyclass = UnsafeCell::new(ManuallyDrop::new(value));
     }
 }
 
-impl<T: PyClass> PyObjectSizedLayout<T> for PyClassShell<T> {}
+impl<T: PyClass> PyObjectSizedLayout<T> for PyCell<T> {}
 
-impl<T: PyClass> AsPyPointer for PyClassShell<T> {
+impl<T: PyClass> AsPyPointer for PyCell<T> {
     fn as_ptr(&self) -> *mut ffi::PyObject {
         (self as *const _) as *mut _
     }
 }
 
-impl<T: PyClass> std::ops::Deref for PyClassShell<T> {
+unsafe impl<T: PyClass> PyDowncastImpl for PyCell<T> {
+    unsafe fn unchecked_downcast(obj: &PyAny) -> &Self {
+        &*(obj.as_ptr() as *const Self)
+    }
+}
+
+/// The borrow state of a `PyCell`: the number of live `PyRef`s, or
+/// `HAS_MUTABLE_BORROW` while a `PyRefMut` is live.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct BorrowFlag(usize);
+
+impl BorrowFlag {
+    const UNUSED: BorrowFlag = BorrowFlag(0);
+    const HAS_MUTABLE_BORROW: BorrowFlag = BorrowFlag(usize::max_value());
+
+    fn increment(self) -> Self {
+        BorrowFlag(self.0 + 1)
+    }
+
+    fn decrement(self) -> Self {
+        BorrowFlag(self.0 - 1)
+    }
+}
+
+/// A wrapper type for an immutably borrowed value from a `PyCell<T>`.
+///
+/// Obtained from [PyCell::try_borrow] or [Py::try_borrow](../struct.Py.html#method.try_borrow).
+pub struct PyRef<'p, T: PyClass> {
+    shell: &'p PyCell<T>,
+}
+
+impl<'p, T: PyClass> std::ops::Deref for PyRef<'p, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.shell.get_ptr() }
+    }
+}
+
+impl<'p, T: PyClass> Clone for PyRef<'p, T> {
+    fn clone(&self) -> Self {
+        let flag = self.shell.borrow_flag.get();
+        self.shell.borrow_flag.set(flag.increment());
+        PyRef { shell: self.shell }
+    }
+}
+
+impl<'p, T: PyClass + fmt::Debug> fmt::Debug for PyRef<'p, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'p, T: PyClass> Drop for PyRef<'p, T> {
+    fn drop(&mut self) {
+        let flag = self.shell.borrow_flag.get();
+        self.shell.borrow_flag.set(flag.decrement())
+    }
+}
+
+/// A wrapper type for a mutably borrowed value from a `PyCell<T>`.
+///
+/// Obtained from [PyCell::try_borrow_mut] or
+/// [Py::try_borrow_mut](../struct.Py.html#method.try_borrow_mut).
+pub struct PyRefMut<'p, T: PyClass> {
+    shell: &'p PyCell<T>,
+}
+
+impl<'p, T: PyClass> std::ops::Deref for PyRefMut<'p, T> {
     type Target = T;
     fn deref(&self) -> &T {
-        self.pyclass.deref()
+        unsafe { &*self.shell.get_ptr() }
     }
 }
 
-impl<T: PyClass> std::ops::DerefMut for PyClassShell<T> {
+impl<'p, T: PyClass> std::ops::DerefMut for PyRefMut<'p, T> {
     fn deref_mut(&mut self) -> &mut T {
-        self.pyclass.deref_mut()
+        // The borrow flag guarantees that this is the only live reference to the value
+        unsafe { &mut *self.shell.get_ptr() }
+    }
+}
+
+impl<'p, T: PyClass + fmt::Debug> fmt::Debug for PyRefMut<'p, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
     }
 }
 
-impl<T: PyClass> ToPyObject for &PyClassShell<T> {
+impl<'p, T: PyClass> Drop for PyRefMut<'p, T> {
+    fn drop(&mut self) {
+        self.shell.borrow_flag.set(BorrowFlag::UNUSED)
+    }
+}
+
+impl<T: PyClass> ToPyObject for &PyCell<T> {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         unsafe { PyObject::from_borrowed_ptr(py, self.as_ptr()) }
     }
 }
 
-impl<T: PyClass> ToPyObject for &mut PyClassShell<T> {
+impl<T: PyClass> ToPyObject for &mut PyCell<T> {
     fn to_object(&self, py: Python<'_>) -> PyObject {
         unsafe { PyObject::from_borrowed_ptr(py, self.as_ptr()) }
     }
 }
 
-unsafe impl<'p, T> FromPyPointer<'p> for &'p PyClassShell<T>
+unsafe impl<'p, T> FromPyPointer<'p> for &'p PyCell<T>
 where
     T: PyClass,
 {
     unsafe fn from_owned_ptr_or_opt(py: Python<'p>, ptr: *mut ffi::PyObject) -> Option<Self> {
-        NonNull::new(ptr).map(|p| &*(gil::register_owned(py, p).as_ptr() as *const PyClassShell<T>))
+        NonNull::new(ptr).map(|p| &*(gil::register_owned(py, p).as_ptr() as *const PyCell<T>))
     }
     unsafe fn from_borrowed_ptr_or_opt(py: Python<'p>, ptr: *mut ffi::PyObject) -> Option<Self> {
-        NonNull::new(ptr)
-            .map(|p| &*(gil::register_borrowed(py, p).as_ptr() as *const PyClassShell<T>))
+        NonNull::new(ptr).map(|p| &*(gil::register_borrowed(py, p).as_ptr() as *const PyCell<T>))
     }
 }
 
-unsafe impl<'p, T> FromPyPointer<'p> for &'p mut PyClassShell<T>
+unsafe impl<'p, T> FromPyPointer<'p> for &'p mut PyCell<T>
 where
     T: PyClass,
 {
     unsafe fn from_owned_ptr_or_opt(py: Python<'p>, ptr: *mut ffi::PyObject) -> Option<Self> {
-        NonNull::new(ptr).map(|p| {
-            &mut *(gil::register_owned(py, p).as_ptr() as *const _ as *mut PyClassShell<T>)
-        })
+        NonNull::new(ptr)
+            .map(|p| &mut *(gil::register_owned(py, p).as_ptr() as *const _ as *mut PyCell<T>))
     }
     unsafe fn from_borrowed_ptr_or_opt(py: Python<'p>, ptr: *mut ffi::PyObject) -> Option<Self> {
-        NonNull::new(ptr).map(|p| {
-            &mut *(gil::register_borrowed(py, p).as_ptr() as *const _ as *mut PyClassShell<T>)
-        })
+        NonNull::new(ptr)
+            .map(|p| &mut *(gil::register_borrowed(py, p).as_ptr() as *const _ as *mut PyCell<T>))
     }
 }
 
@@ -313,6 +606,15 @@ where
 
     // GC support
     <T as class::gc::PyGCProtocolImpl>::update_type_object(type_object);
+    // `__slots__` values can form reference cycles, even without `PyGCProtocol`
+    if T::Slots::LEN != 0 {
+        if type_object.tp_traverse.is_none() {
+            type_object.tp_traverse = Some(class::gc::tp_traverse_slots::<T>);
+        }
+        if type_object.tp_clear.is_none() {
+            type_object.tp_clear = Some(class::gc::tp_clear_slots::<T>);
+        }
+    }
 
     // descriptor protocol
     <T as class::descr::PyDescrProtocolImpl>::tp_as_descr(type_object);
@@ -322,6 +624,13 @@ where
 
     // basic methods
     <T as class::basic::PyObjectProtocolImpl>::tp_as_object(type_object);
+    // slots derived with `#[pyclass(hash, eq, ord)]`
+    if type_object.tp_hash.is_none() {
+        type_object.tp_hash = T::DERIVED_HASH;
+    }
+    if type_object.tp_richcompare.is_none() {
+        type_object.tp_richcompare = T::DERIVED_RICHCMP;
+    }
 
     fn to_ptr<T>(value: Option<T>) -> *mut T {
         value
@@ -343,7 +652,7 @@ where
     type_object.tp_as_buffer = to_ptr(<T as class::buffer::PyBufferProtocolImpl>::tp_as_buffer());
 
     // normal methods
-    let (new, call, mut methods) = py_class_method_defs::<T>();
+    let (new, init, call, mut methods) = py_class_method_defs::<T>();
     if !methods.is_empty() {
         methods.push(ffi::PyMethodDef_INIT);
         type_object.tp_methods = Box::into_raw(methods.into_boxed_slice()) as *mut _;
@@ -351,9 +660,22 @@ where
 
     // __new__ method
     type_object.tp_new = new;
+    // __init__ method, which takes precedence over `PyObjectProtocol::__init__`
+    if init.is_some() {
+        type_object.tp_init = init;
+    }
     // __call__ method
     type_object.tp_call = call;
 
+    // vectorcall support
+    if let Some(call) = T::Vectorcall::TP_CALL {
+        type_object.tp_call = Some(call);
+        #[cfg(all(Py_3_8, not(Py_LIMITED_API)))]
+        {
+            type_object.tp_vectorcall_offset = PyCell::<T>::vectorcall_offset();
+        }
+    }
+
     // properties
     let mut props = py_class_properties::<T>();
 
@@ -365,17 +687,115 @@ where
         type_object.tp_getset = Box::into_raw(props.into_boxed_slice()) as *mut _;
     }
 
+    // __slots__
+    let members = py_class_members::<T>();
+    if !members.is_empty() {
+        type_object.tp_members = Box::into_raw(members.into_boxed_slice()) as *mut _;
+    }
+
     // set type flags
     py_class_flags::<T>(type_object);
 
     // register type object
     unsafe {
-        if ffi::PyType_Ready(type_object) == 0 {
-            Ok(boxed)
-        } else {
-            PyErr::fetch(py).into()
+        if ffi::PyType_Ready(type_object) != 0 {
+            return PyErr::fetch(py).into();
+        }
+        if !T::SLOT_NAMES.is_empty() {
+            let slots = crate::types::PyTuple::new(py, T::SLOT_NAMES);
+            if ffi::PyDict_SetItemString(
+                type_object.tp_dict,
+                "__slots__\0".as_ptr() as *const _,
+                slots.as_ptr(),
+            ) != 0
+            {
+                return PyErr::fetch(py).into();
+            }
+            ffi::PyType_Modified(type_object);
+        }
+        if T::FLAGS & type_flags::MAPPING_MIXIN != 0 {
+            py_class_mapping_mixin(py, type_object)?;
+        }
+        Ok(boxed)
+    }
+}
+
+/// Adds the mixin methods of `collections.abc.Mapping` that the class doesn't define itself,
+/// and registers the class as a virtual subclass of `Mapping`.
+#[cfg(not(Py_LIMITED_API))]
+fn py_class_mapping_mixin(py: Python, type_object: &mut ffi::PyTypeObject) -> PyResult<()> {
+    let mapping = py.import("collections.abc")?.get("Mapping")?;
+    let dict: &PyDict = unsafe { py.from_borrowed_ptr(type_object.tp_dict) };
+    for name in &["keys", "values", "items", "get"] {
+        if dict.get_item_with_error(*name)?.is_none() {
+            dict.set_item(*name, mapping.getattr(*name)?)?;
         }
     }
+    unsafe { ffi::PyType_Modified(type_object) };
+    let ty: &PyType = unsafe { py.from_borrowed_ptr(type_object as *mut _ as *mut ffi::PyObject) };
+    mapping.call_method1("register", (ty,))?;
+    Ok(())
+}
+
+/// Adds the `#[classattr]` values to the type's `__dict__`.
+///
+/// This runs after the type object has been stored, so that class attributes can be
+/// instances of the class itself.
+#[cfg(not(Py_LIMITED_API))]
+pub(crate) fn initialize_class_attributes<T: PyMethodsProtocol>(
+    py: Python,
+    type_object: *mut ffi::PyTypeObject,
+) -> PyResult<()> {
+    let mut modified = false;
+    for def in T::py_methods() {
+        if let PyMethodDefType::ClassAttribute(ref attr) = *def {
+            let key =
+                CString::new(attr.name).expect("Class attribute name must not contain NUL byte");
+            let value = (attr.meth)(py);
+            unsafe {
+                if ffi::PyDict_SetItemString((*type_object).tp_dict, key.as_ptr(), value.as_ptr())
+                    != 0
+                {
+                    return Err(PyErr::fetch(py));
+                }
+            }
+            modified = true;
+        }
+    }
+    if modified {
+        unsafe { ffi::PyType_Modified(type_object) };
+    }
+    Ok(())
+}
+
+fn py_class_members<T: PyClass>() -> Vec<ffi::structmember::PyMemberDef> {
+    debug_assert_eq!(T::SLOT_NAMES.len(), T::Slots::LEN);
+    if T::SLOT_NAMES.is_empty() {
+        return Vec::new();
+    }
+    let offset = PyCell::<T>::slots_offset();
+    let pointer_size = std::mem::size_of::<*mut ffi::PyObject>() as ffi::Py_ssize_t;
+    let mut members: Vec<_> = T::SLOT_NAMES
+        .iter()
+        .enumerate()
+        .map(|(i, name)| ffi::structmember::PyMemberDef {
+            name: CString::new(*name)
+                .expect("Slot name must not contain NUL byte")
+                .into_raw(),
+            type_code: ffi::structmember::T_OBJECT_EX,
+            offset: offset + i as ffi::Py_ssize_t * pointer_size,
+            flags: 0,
+            doc: ptr::null_mut(),
+        })
+        .collect();
+    members.push(ffi::structmember::PyMemberDef {
+        name: ptr::null_mut(),
+        type_code: 0,
+        offset: 0,
+        flags: 0,
+        doc: ptr::null_mut(),
+    });
+    members
 }
 
 fn py_class_flags<T: PyTypeInfo>(type_object: &mut ffi::PyTypeObject) {
@@ -390,16 +810,36 @@ fn py_class_flags<T: PyTypeInfo>(type_object: &mut ffi::PyTypeObject) {
     if T::FLAGS & type_flags::BASETYPE != 0 {
         type_object.tp_flags |= ffi::Py_TPFLAGS_BASETYPE;
     }
+    if type_object.tp_finalize.is_some() {
+        type_object.tp_flags |= ffi::Py_TPFLAGS_HAVE_FINALIZE;
+    }
+    #[cfg(all(Py_3_8, not(Py_LIMITED_API)))]
+    {
+        if type_object.tp_vectorcall_offset != 0 {
+            type_object.tp_flags |= ffi::_Py_TPFLAGS_HAVE_VECTORCALL;
+        }
+    }
+    #[cfg(Py_3_10)]
+    {
+        if T::FLAGS & type_flags::MAPPING != 0 {
+            type_object.tp_flags |= ffi::Py_TPFLAGS_MAPPING;
+        }
+        if T::FLAGS & type_flags::SEQUENCE != 0 {
+            type_object.tp_flags |= ffi::Py_TPFLAGS_SEQUENCE;
+        }
+    }
 }
 
 fn py_class_method_defs<T: PyMethodsProtocol>() -> (
     Option<ffi::newfunc>,
+    Option<ffi::initproc>,
     Option<ffi::PyCFunctionWithKeywords>,
     Vec<ffi::PyMethodDef>,
 ) {
     let mut defs = Vec::new();
     let mut call = None;
     let mut new = None;
+    let mut init = None;
 
     for def in T::py_methods() {
         match *def {
@@ -408,6 +848,11 @@ fn py_class_method_defs<T: PyMethodsProtocol>() -> (
                     new = Some(meth)
                 }
             }
+            PyMethodDefType::Init(ref def) => {
+                if let class::methods::PyMethodType::PyInitFunc(meth) = def.ml_meth {
+                    init = Some(meth)
+                }
+            }
             PyMethodDefType::Call(ref def) => {
                 if let class::methods::PyMethodType::PyCFunctionWithKeywords(meth) = def.ml_meth {
                     call = Some(meth)
@@ -439,10 +884,13 @@ fn py_class_method_defs<T: PyMethodsProtocol>() -> (
     for def in <T as class::descr::PyDescrProtocolImpl>::methods() {
         defs.push(def.as_method_def());
     }
+    for def in <T as class::iter::PyIterProtocolImpl>::methods() {
+        defs.push(def.as_method_def());
+    }
 
     py_class_async_methods::<T>(&mut defs);
 
-    (new, call, defs)
+    (new, init, call, defs)
 }
 
 fn py_class_async_methods<T>(defs: &mut Vec<ffi::PyMethodDef>) {
@@ -1,5 +1,5 @@
 //! Initialization utilities for `#[pyclass]`.
-use crate::pyclass::{PyClass, PyClassShell};
+use crate::pyclass::{PyCell, PyClass};
 use crate::type_object::{PyObjectLayout, PyObjectSizedLayout, PyTypeInfo};
 use crate::{PyResult, Python};
 use std::marker::PhantomData;
@@ -115,12 +115,28 @@ impl<T: PyClass> PyClassInitializer<T> {
     }
 
     #[doc(hidden)]
-    pub unsafe fn create_shell(self, py: Python) -> PyResult<*mut PyClassShell<T>>
+    pub unsafe fn create_shell(self, py: Python) -> PyResult<*mut PyCell<T>>
     where
         T: PyClass,
         <T::BaseType as PyTypeInfo>::ConcreteLayout: PyObjectSizedLayout<T::BaseType>,
     {
-        let shell = PyClassShell::new(py)?;
+        let shell = PyCell::new(py)?;
+        self.init_class(&mut *shell);
+        Ok(shell)
+    }
+
+    /// Creates an object of `subtype`, which is `T` or a subclass of `T` defined in Python.
+    #[doc(hidden)]
+    pub unsafe fn create_shell_from_subtype(
+        self,
+        py: Python,
+        subtype: *mut crate::ffi::PyTypeObject,
+    ) -> PyResult<*mut PyCell<T>>
+    where
+        T: PyClass,
+        <T::BaseType as PyTypeInfo>::ConcreteLayout: PyObjectSizedLayout<T::BaseType>,
+    {
+        let shell = PyCell::new_with_subtype(py, subtype)?;
         self.init_class(&mut *shell);
         Ok(shell)
     }
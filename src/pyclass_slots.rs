@@ -1,6 +1,9 @@
 //! This module contains additional fields for `#[pyclass]`..
 //! Mainly used by our proc-macro codes.
+use crate::class::vectorcall::{self, PyVectorcallProtocol};
 use crate::{ffi, Python};
+use std::marker::PhantomData;
+use std::os::raw::{c_int, c_void};
 
 const POINTER_SIZE: isize = std::mem::size_of::<*mut ffi::PyObject>() as _;
 
@@ -20,6 +23,27 @@ pub trait PyClassWeakRef {
     private_decl! {}
 }
 
+/// Represents the `__slots__` fields for `#[pyclass]`.
+pub trait PyClassSlots {
+    /// The number of slots.
+    const LEN: usize = 0;
+    fn new() -> Self;
+    /// Calls `visit` for each value of the slots, like a `tp_traverse` function.
+    unsafe fn traverse_slots(&self, _visit: ffi::visitproc, _arg: *mut c_void) -> c_int {
+        0
+    }
+    unsafe fn clear_slots(&mut self, _py: Python) {}
+    private_decl! {}
+}
+
+/// Represents the per-instance vectorcall function for `#[pyclass]`.
+pub trait PyClassVectorcall {
+    /// The `tp_call` slot, which is only set for `#[pyclass(vectorcall)]`.
+    const TP_CALL: Option<ffi::PyCFunctionWithKeywords> = None;
+    fn new() -> Self;
+    private_decl! {}
+}
+
 /// Zero-sized dummy field.
 pub struct PyClassDummySlot;
 
@@ -37,6 +61,20 @@ impl PyClassWeakRef for PyClassDummySlot {
     }
 }
 
+impl PyClassSlots for PyClassDummySlot {
+    private_impl! {}
+    fn new() -> Self {
+        PyClassDummySlot
+    }
+}
+
+impl PyClassVectorcall for PyClassDummySlot {
+    private_impl! {}
+    fn new() -> Self {
+        PyClassDummySlot
+    }
+}
+
 /// Actual dict field, which holds the pointer to `__dict__`.
 ///
 /// `#[pyclass(dict)]` automatically adds this.
@@ -74,3 +112,66 @@ impl PyClassWeakRef for PyClassWeakRefSlot {
         }
     }
 }
+
+/// Actual slots field, which holds the pointers to the values of `__slots__`.
+///
+/// `#[pyclass(slots = ["x", "y"])]` automatically adds this, with an array of
+/// as many pointers as there are slot names.
+#[repr(transparent)]
+pub struct PyClassSlotArray<A>(A);
+
+macro_rules! slot_array_impls {
+    ($($n:expr),*) => {$(
+        impl PyClassSlots for PyClassSlotArray<[*mut ffi::PyObject; $n]> {
+            private_impl! {}
+            const LEN: usize = $n;
+            fn new() -> Self {
+                Self([std::ptr::null_mut(); $n])
+            }
+            unsafe fn traverse_slots(&self, visit: ffi::visitproc, arg: *mut c_void) -> c_int {
+                for slot in self.0.iter() {
+                    if !slot.is_null() {
+                        let r = visit(*slot, arg);
+                        if r != 0 {
+                            return r;
+                        }
+                    }
+                }
+                0
+            }
+            unsafe fn clear_slots(&mut self, _py: Python) {
+                for slot in self.0.iter_mut() {
+                    // The slot is emptied first, since releasing the value may run Python code
+                    let value = std::mem::replace(slot, std::ptr::null_mut());
+                    ffi::Py_XDECREF(value);
+                }
+            }
+        }
+    )*}
+}
+
+slot_array_impls!(
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32
+);
+
+type VectorcallFunc = unsafe extern "C" fn(
+    *mut ffi::PyObject,
+    *const *mut ffi::PyObject,
+    libc::size_t,
+    *mut ffi::PyObject,
+) -> *mut ffi::PyObject;
+
+/// Actual vectorcall field, which holds the pointer to the vectorcall function of `T`.
+///
+/// `#[pyclass(vectorcall)]` automatically adds this.
+#[repr(transparent)]
+pub struct PyClassVectorcallSlot<T>(VectorcallFunc, PhantomData<T>);
+
+impl<T: PyVectorcallProtocol> PyClassVectorcall for PyClassVectorcallSlot<T> {
+    private_impl! {}
+    const TP_CALL: Option<ffi::PyCFunctionWithKeywords> = Some(vectorcall::tp_call::<T>);
+    fn new() -> Self {
+        Self(vectorcall::vectorcall::<T>, PhantomData)
+    }
+}
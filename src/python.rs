@@ -10,7 +10,7 @@ use crate::object::PyObject;
 use crate::type_object::{PyObjectLayout, PyTypeInfo, PyTypeObject};
 use crate::types::{PyAny, PyDict, PyModule, PyType};
 use crate::AsPyPointer;
-use crate::{FromPyPointer, IntoPyPointer, PyTryFrom};
+use crate::{FromPyPointer, IntoPyPointer, PyTryFrom, ToPyObject};
 use std::ffi::CString;
 use std::marker::PhantomData;
 use std::os::raw::c_int;
@@ -176,7 +176,7 @@ impl<'p> Python<'p> {
     ///    None,
     ///    Some(locals),
     /// ).unwrap();
-    /// let ret = locals.get_item("ret").unwrap();
+    /// let ret = locals.get_item_with_error("ret").unwrap().unwrap();
     /// let b64: &PyBytes = ret.downcast_ref().unwrap();
     /// assert_eq!(b64.as_bytes(), b"SGVsbG8gUnVzdCE=");
     /// ```
@@ -192,6 +192,47 @@ impl<'p> Python<'p> {
         })
     }
 
+    /// Evaluates a Python expression with the given local variables and returns the result.
+    ///
+    /// The locals dict is built from the `(name, value)` pairs in `locals`;
+    /// globals default to Python module `__main__`.
+    ///
+    /// # Example:
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// # let gil = pyo3::Python::acquire_gil();
+    /// # let py = gil.python();
+    /// let result = py.eval_with_locals("a + b", &[("a", &1), ("b", &2)]).unwrap();
+    /// assert_eq!(result.extract::<i32>(py).unwrap(), 3);
+    /// ```
+    pub fn eval_with_locals(
+        self,
+        code: &str,
+        locals: &[(&str, &dyn ToPyObject)],
+    ) -> PyResult<PyObject> {
+        let locals = self.locals_dict(locals)?;
+        self.eval(code, None, Some(locals))
+            .map(|obj| obj.to_object(self))
+    }
+
+    /// Executes one or more Python statements with the given local variables.
+    ///
+    /// The locals dict is built from the `(name, value)` pairs in `locals`;
+    /// globals default to Python module `__main__`.
+    pub fn run_with_locals(self, code: &str, locals: &[(&str, &dyn ToPyObject)]) -> PyResult<()> {
+        let locals = self.locals_dict(locals)?;
+        self.run(code, None, Some(locals))
+    }
+
+    /// Builds a dict from `(name, value)` pairs to be used as a locals namespace.
+    fn locals_dict(self, locals: &[(&str, &dyn ToPyObject)]) -> PyResult<&'p PyDict> {
+        let dict = PyDict::new(self);
+        for (name, value) in locals {
+            dict.set_item(*name, value)?;
+        }
+        Ok(dict)
+    }
+
     /// Runs code in the given context.
     /// `start` indicates the type of input expected:
     /// one of `Py_single_input`, `Py_file_input`, or `Py_eval_input`.
@@ -415,7 +456,7 @@ impl<'p> Python<'p> {
 mod test {
     use crate::objectprotocol::ObjectProtocol;
     use crate::types::{IntoPyDict, PyAny, PyBool, PyInt, PyList};
-    use crate::Python;
+    use crate::{Python, ToPyObject};
 
     #[test]
     fn test_eval() {
@@ -458,6 +499,25 @@ mod test {
         assert_eq!(v, 2);
     }
 
+    #[test]
+    fn test_eval_with_locals() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let foo = 13.to_object(py);
+        let locals: [(&str, &dyn ToPyObject); 2] = [("foo", &foo), ("bar", &29)];
+        let v: i32 = py
+            .eval_with_locals("foo + bar", &locals)
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert_eq!(v, 42);
+
+        py.run_with_locals("assert foo + bar == 42", &locals)
+            .unwrap();
+        assert!(py.run_with_locals("assert foo == bar", &locals).is_err());
+    }
+
     #[test]
     fn test_is_instance() {
         let gil = Python::acquire_gil();
@@ -0,0 +1,234 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! `serde` support for Python objects, enabled with the `serde` feature.
+//!
+//! `PyAny` and `PyObject` implement `Serialize`, and `PyObject` implements `Deserialize`,
+//! using the following mapping:
+//!
+//! | Python            | serde data model     |
+//! |-------------------|----------------------|
+//! | `None`            | unit / none          |
+//! | `bool`            | bool                 |
+//! | `int`             | i64 or u64           |
+//! | `float`           | f64                  |
+//! | `str`             | string               |
+//! | `bytes`           | bytes                |
+//! | `list`, `tuple`   | seq (`list` when deserializing) |
+//! | `dict`            | map                  |
+//!
+//! Serializing any other type fails. This allows e.g. round-tripping Python data through
+//! `serde_json` without an intermediate Rust type:
+//!
+//! ```
+//! use pyo3::prelude::*;
+//!
+//! let gil = Python::acquire_gil();
+//! let py = gil.python();
+//!
+//! let obj = py.eval("{'a': [1, 2.5, None]}", None, None).unwrap();
+//! let json = serde_json::to_string(obj).unwrap();
+//! assert_eq!(json, r#"{"a":[1,2.5,null]}"#);
+//!
+//! let obj: PyObject = serde_json::from_str(&json).unwrap();
+//! assert_eq!(obj.as_ref(py).repr().unwrap().to_string().unwrap(), "{'a': [1, 2.5, None]}");
+//! ```
+
+use crate::err::error_message;
+use crate::instance::PyNativeType;
+use crate::types::{PyAny, PyBool, PyBytes, PyDict, PyFloat, PyList, PyLong, PyString, PyTuple};
+use crate::{AsPyRef, ObjectProtocol, PyObject, PyTryFrom, Python, ToPyObject};
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, Serializer};
+use std::fmt;
+
+impl Serialize for PyAny {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_none() {
+            serializer.serialize_unit()
+        } else if let Ok(b) = <PyBool as PyTryFrom>::try_from(self) {
+            serializer.serialize_bool(b.is_true())
+        } else if <PyLong as PyTryFrom>::try_from(self).is_ok() {
+            if let Ok(i) = self.extract::<i64>() {
+                serializer.serialize_i64(i)
+            } else {
+                let u = self
+                    .extract::<u64>()
+                    .map_err(|e| ser::Error::custom(error_message(self.py(), e)))?;
+                serializer.serialize_u64(u)
+            }
+        } else if let Ok(f) = <PyFloat as PyTryFrom>::try_from(self) {
+            serializer.serialize_f64(f.value())
+        } else if let Ok(s) = <PyString as PyTryFrom>::try_from(self) {
+            let s = s
+                .to_string()
+                .map_err(|e| ser::Error::custom(error_message(self.py(), e)))?;
+            serializer.serialize_str(&s)
+        } else if let Ok(b) = <PyBytes as PyTryFrom>::try_from(self) {
+            serializer.serialize_bytes(b.as_bytes())
+        } else if let Ok(list) = <PyList as PyTryFrom>::try_from(self) {
+            let mut seq = serializer.serialize_seq(Some(list.len()))?;
+            for item in list.iter() {
+                seq.serialize_element(item)?;
+            }
+            seq.end()
+        } else if let Ok(tuple) = <PyTuple as PyTryFrom>::try_from(self) {
+            let mut seq = serializer.serialize_seq(Some(tuple.len()))?;
+            for item in tuple.iter() {
+                seq.serialize_element(item)?;
+            }
+            seq.end()
+        } else if let Ok(dict) = <PyDict as PyTryFrom>::try_from(self) {
+            let mut map = serializer.serialize_map(Some(dict.len()))?;
+            for (key, value) in dict.iter() {
+                map.serialize_entry(key, value)?;
+            }
+            map.end()
+        } else {
+            Err(ser::Error::custom(format!(
+                "can't serialize objects of type '{}'",
+                self.get_type().name()
+            )))
+        }
+    }
+}
+
+/// Acquires the GIL, since a `PyObject` can be serialized without holding it.
+impl Serialize for PyObject {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let gil = Python::acquire_gil();
+        self.as_ref(gil.python()).serialize(serializer)
+    }
+}
+
+/// Acquires the GIL to create the Python objects.
+impl<'de> serde::Deserialize<'de> for PyObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let gil = Python::acquire_gil();
+        PyObjectSeed(gil.python()).deserialize(deserializer)
+    }
+}
+
+/// Deserializes a `PyObject` while holding the GIL.
+struct PyObjectSeed<'py>(Python<'py>);
+
+impl<'de, 'py> DeserializeSeed<'de> for PyObjectSeed<'py> {
+    type Value = PyObject;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<PyObject, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'py> Visitor<'de> for PyObjectSeed<'py> {
+    type Value = PyObject;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a value representable as a Python object")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<PyObject, E> {
+        Ok(v.to_object(self.0))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<PyObject, E> {
+        Ok(v.to_object(self.0))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<PyObject, E> {
+        Ok(v.to_object(self.0))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<PyObject, E> {
+        Ok(v.to_object(self.0))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<PyObject, E> {
+        Ok(v.to_object(self.0))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<PyObject, E> {
+        Ok(PyBytes::new(self.0, v).to_object(self.0))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<PyObject, E> {
+        Ok(self.0.None())
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<PyObject, E> {
+        Ok(self.0.None())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<PyObject, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PyObject, A::Error> {
+        let py = self.0;
+        let list = PyList::empty(py);
+        while let Some(item) = seq.next_element_seed(PyObjectSeed(py))? {
+            list.append(item)
+                .map_err(|e| de::Error::custom(error_message(py, e)))?;
+        }
+        Ok(list.to_object(py))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<PyObject, A::Error> {
+        let py = self.0;
+        let dict = PyDict::new(py);
+        while let Some((key, value)) = map.next_entry_seed(PyObjectSeed(py), PyObjectSeed(py))? {
+            dict.set_item(key, value)
+                .map_err(|e| de::Error::custom(error_message(py, e)))?;
+        }
+        Ok(dict.to_object(py))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::IntoPyDict;
+    use crate::{PyObject, Python};
+
+    #[test]
+    fn test_roundtrip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let obj = py
+            .eval(
+                "{'s': 'x', 'i': -1, 'big': 2**64 - 1, 'f': 0.5, 'b': True, 'n': None, 'l': [1, (2, 3)]}",
+                None,
+                None,
+            )
+            .unwrap();
+        let json = serde_json::to_value(obj).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "s": "x", "i": -1, "big": u64::max_value(), "f": 0.5, "b": true, "n": null,
+                "l": [1, [2, 3]],
+            })
+        );
+
+        let back: PyObject = serde_json::from_value(json).unwrap();
+        let locals = [("back", back)].into_py_dict(py);
+        py.run(
+            "assert back == {'s': 'x', 'i': -1, 'big': 2**64 - 1, 'f': 0.5, 'b': True, 'n': None, 'l': [1, [2, 3]]}",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_unsupported_type() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let obj = py.eval("{'a': object()}", None, None).unwrap();
+        let err = serde_json::to_string(obj).unwrap_err();
+        assert_eq!(err.to_string(), "can't serialize objects of type 'object'");
+
+        let obj = py.eval("2**64", None, None).unwrap();
+        assert!(serde_json::to_string(obj).is_err());
+    }
+}
@@ -1,17 +1,20 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 //! Python type object information
 
-use crate::err::PyResult;
+use crate::err::{PyErr, PyResult};
 use crate::instance::Py;
-use crate::pyclass::{create_type_object, PyClass};
+use crate::pyclass::{create_type_object, initialize_class_attributes, PyClass};
 use crate::pyclass_init::PyObjectInit;
 use crate::types::{PyAny, PyType};
-use crate::{ffi, AsPyPointer, Python};
+use crate::{ffi, AsPyPointer, GILProtected, Python};
 use once_cell::sync::OnceCell;
+use std::cell::RefCell;
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, ThreadId};
 
 /// `T: PyObjectLayout<U>` represents that `T` is a concrete representaion of `U` in Python heap.
-/// E.g., `PyClassShell` is a concrete representaion of all `pyclass`es, and `ffi::PyObject`
+/// E.g., `PyCell` is a concrete representaion of all `pyclass`es, and `ffi::PyObject`
 /// is of `PyAny`.
 ///
 /// This trait is intended to be used internally.
@@ -42,6 +45,15 @@ pub trait PyObjectLayout<T: PyTypeInfo> {
 /// `pyclass`es need this trait for their base class.
 pub trait PyObjectSizedLayout<T: PyTypeInfo>: PyObjectLayout<T> + Sized {}
 
+/// The type that [AsPyRef::as_ref](../instance/trait.AsPyRef.html) returns for a `Py<T>`.
+///
+/// This is the type itself for native types like `PyDict`, and `PyCell<T>` for a
+/// `#[pyclass]`, whose value has to be borrowed through the cell.
+pub unsafe trait PyDowncastImpl {
+    /// Casts `obj` to `&Self` without checking its type.
+    unsafe fn unchecked_downcast(obj: &PyAny) -> &Self;
+}
+
 /// Our custom type flags
 #[doc(hidden)]
 pub mod type_flags {
@@ -59,6 +71,19 @@ pub mod type_flags {
 
     /// The class declared by #[pyclass(extends=~)]
     pub const EXTENDED: usize = 1 << 4;
+
+    /// The class declared by #[pyclass(frozen)], whose instances can't be mutated
+    pub const FROZEN: usize = 1 << 5;
+
+    /// The class declared by #[pyclass(mapping)], which sets `Py_TPFLAGS_MAPPING` on Python 3.10+
+    pub const MAPPING: usize = 1 << 6;
+
+    /// The class declared by #[pyclass(sequence)], which sets `Py_TPFLAGS_SEQUENCE` on Python 3.10+
+    pub const SEQUENCE: usize = 1 << 7;
+
+    /// The class declared by #[pyclass(mapping_mixin)], which gets the `collections.abc.Mapping`
+    /// mixin methods
+    pub const MAPPING_MIXIN: usize = 1 << 8;
 }
 
 /// Python type information.
@@ -92,6 +117,9 @@ pub unsafe trait PyTypeInfo: Sized {
     /// Initializer for layout
     type Initializer: PyObjectInit<Self>;
 
+    /// The type that `Py<Self>::as_ref` returns
+    type AsRefTarget: PyDowncastImpl;
+
     /// PyTypeObject instance for this type, guaranteed to be global and initialized.
     fn type_object() -> NonNull<ffi::PyTypeObject>;
 
@@ -132,12 +160,18 @@ where
 #[doc(hidden)]
 pub struct LazyTypeObject {
     cell: OnceCell<NonNull<ffi::PyTypeObject>>,
+    // Whether the `#[classattr]`s of a pyclass have been added
+    class_attributes_initialized: AtomicBool,
+    // The threads that are creating the `#[classattr]` values
+    initializing_threads: GILProtected<RefCell<Vec<ThreadId>>>,
 }
 
 impl LazyTypeObject {
     pub const fn new() -> Self {
         Self {
             cell: OnceCell::new(),
+            class_attributes_initialized: AtomicBool::new(false),
+            initializing_threads: GILProtected::new(RefCell::new(Vec::new())),
         }
     }
 
@@ -149,19 +183,61 @@ impl LazyTypeObject {
     }
 
     pub fn get_pyclass_type<T: PyClass>(&self) -> NonNull<ffi::PyTypeObject> {
-        self.get_or_init(|| {
-            // automatically initialize the class on-demand
+        let type_object = self
+            .get_or_init(|| {
+                // automatically initialize the class on-demand
+                let gil = Python::acquire_gil();
+                let py = gil.python();
+                let boxed = create_type_object::<T>(py, T::MODULE)?;
+                Ok(unsafe { NonNull::new_unchecked(Box::into_raw(boxed)) })
+            })
+            .unwrap_or_else(|e| Self::initialization_failed::<T>(e));
+
+        if !self.class_attributes_initialized.load(Ordering::SeqCst) {
             let gil = Python::acquire_gil();
-            let py = gil.python();
-            let boxed = create_type_object::<T>(py, T::MODULE)?;
-            Ok(unsafe { NonNull::new_unchecked(Box::into_raw(boxed)) })
-        })
-        .unwrap_or_else(|e| {
-            let gil = Python::acquire_gil();
-            let py = gil.python();
-            e.print(py);
-            panic!("An error occurred while initializing class {}", T::NAME)
-        })
+            self.initialize_class_attributes::<T>(gil.python(), type_object);
+        }
+        type_object
+    }
+
+    /// Adds the class attributes once the type object is available, since they may be
+    /// instances of the class itself.
+    ///
+    /// Recursive calls made while creating the values get the type without them. Other
+    /// threads only get the type once the attributes have been added: creating the values may
+    /// release the GIL, so rather than waiting for the first thread while holding the GIL,
+    /// each thread creates and adds the values itself until one of them has finished.
+    fn initialize_class_attributes<T: PyClass>(
+        &self,
+        py: Python,
+        type_object: NonNull<ffi::PyTypeObject>,
+    ) {
+        let thread_id = thread::current().id();
+        {
+            let mut threads = self.initializing_threads.get(py).borrow_mut();
+            if threads.contains(&thread_id) {
+                return;
+            }
+            threads.push(thread_id);
+        }
+        let result = initialize_class_attributes::<T>(py, type_object.as_ptr());
+        self.initializing_threads
+            .get(py)
+            .borrow_mut()
+            .retain(|id| *id != thread_id);
+        match result {
+            Ok(()) => self
+                .class_attributes_initialized
+                .store(true, Ordering::SeqCst),
+            Err(e) => Self::initialization_failed::<T>(e),
+        }
+    }
+
+    fn initialization_failed<T: PyClass>(e: PyErr) -> ! {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        e.print(py);
+        panic!("An error occurred while initializing class {}", T::NAME)
     }
 }
 
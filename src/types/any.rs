@@ -1,7 +1,10 @@
 use crate::conversion::PyTryFrom;
-use crate::err::PyDowncastError;
+use crate::err::{PyDowncastError, PyResult};
+use crate::exceptions::ValueError;
 use crate::internal_tricks::Unsendable;
-use crate::{ffi, PyObject};
+use crate::type_object::PyTypeInfo;
+use crate::types::{PyDict, PyTuple};
+use crate::{ffi, AsPyPointer, ObjectProtocol, PyNativeType, PyObject};
 
 /// Represents a python's [Any](https://docs.python.org/3/library/typing.html#typing.Any) type.
 /// We can convert all python objects as `PyAny`.
@@ -9,6 +12,10 @@ use crate::{ffi, PyObject};
 /// In addition, if the inner object is an instance of type `T`, we can downcast
 /// `PyAny` into `T`.
 ///
+/// Like all native types, `PyAny` implements `Display` by calling Python's `str()`, and
+/// `Debug` by calling `repr()`. If that raises an exception, formatting fails with
+/// `fmt::Error`, which makes `format!` and `to_string` panic.
+///
 /// # Example
 ///
 /// ```
@@ -20,6 +27,7 @@ use crate::{ffi, PyObject};
 /// let any = dict.as_ref();
 /// assert!(any.downcast_ref::<PyDict>().is_ok());
 /// assert!(any.downcast_ref::<PyList>().is_err());
+/// assert_eq!(format!("{}", any), "{}");
 /// ```
 #[repr(transparent)]
 pub struct PyAny(PyObject, Unsendable);
@@ -49,4 +57,166 @@ impl PyAny {
     {
         T::try_from_mut(self)
     }
+
+    /// Downcasts to `T` only if the type of this object is exactly `T`.
+    ///
+    /// Unlike [PyAny::downcast_ref], instances of subclasses of `T` are rejected, like
+    /// `PyList_CheckExact` compared to `PyList_Check`.
+    pub fn downcast_exact<T>(&self) -> Result<&T, PyDowncastError>
+    where
+        T: for<'gil> PyTryFrom<'gil>,
+    {
+        T::try_from_exact(self)
+    }
+
+    /// Checks whether the type of this object is exactly `T`, like `PyList_CheckExact`.
+    ///
+    /// This only compares the type pointer with `T`'s type object, so it is the cheapest
+    /// type check available. It never calls `__instancecheck__` and cannot fail.
+    pub fn type_is<T: PyTypeInfo>(&self) -> bool {
+        unsafe { ffi::Py_TYPE(self.as_ptr()) == T::type_object().as_ptr() }
+    }
+
+    /// Checks whether the type of this object is exactly `T`, not counting subclasses.
+    ///
+    /// This is equivalent to the Python expression: `type(self) is T`.
+    pub fn is_exact_instance_of<T: PyTypeInfo>(&self) -> bool {
+        T::is_exact_instance(self)
+    }
+
+    /// Calls the object using the vectorcall protocol (PEP 590) when the object supports it.
+    ///
+    /// `args` holds the positional arguments followed by the values of the keyword arguments,
+    /// whose names are given in `kwargs_keys`, just like in the vectorcall calling convention.
+    /// If the object doesn't support vectorcall, this falls back to a regular call with an
+    /// argument tuple and a keyword dict.
+    ///
+    /// This is equivalent to the Python expression: `self(*args, **kwargs)`.
+    pub fn call_with_vectorcall(
+        &self,
+        args: &[PyObject],
+        kwargs_keys: Option<&[&str]>,
+    ) -> PyResult<PyObject> {
+        let py = self.py();
+        let kwargs_keys = kwargs_keys.unwrap_or(&[]);
+        if kwargs_keys.len() > args.len() {
+            return Err(ValueError::py_err(
+                "call_with_vectorcall(): more keyword names than arguments",
+            ));
+        }
+        let nargs = args.len() - kwargs_keys.len();
+
+        #[cfg(all(Py_3_8, not(Py_LIMITED_API), not(PyPy)))]
+        unsafe {
+            if let Some(func) = ffi::_PyVectorcall_Function(self.as_ptr()) {
+                let kwnames = if kwargs_keys.is_empty() {
+                    std::ptr::null_mut()
+                } else {
+                    PyTuple::new(py, kwargs_keys).as_ptr()
+                };
+                let args: Vec<*mut ffi::PyObject> = args.iter().map(|arg| arg.as_ptr()).collect();
+                let result = func(self.as_ptr(), args.as_ptr(), nargs, kwnames);
+                return PyObject::from_owned_ptr_or_err(py, result);
+            }
+        }
+
+        let tuple = PyTuple::new(py, &args[..nargs]);
+        let kwargs = if kwargs_keys.is_empty() {
+            None
+        } else {
+            let dict = PyDict::new(py);
+            for (key, value) in kwargs_keys.iter().zip(&args[nargs..]) {
+                dict.set_item(*key, value)?;
+            }
+            Some(dict)
+        };
+        self.call(tuple, kwargs).map(|obj| obj.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{PyBool, PyDict, PyList, PyLong};
+    use crate::{Python, ToPyObject};
+
+    #[test]
+    fn test_call_with_vectorcall() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let builtins = py.import("builtins").unwrap();
+        let sorted = builtins.get("sorted").unwrap();
+
+        let list = vec![3, 1, 2].to_object(py);
+        let result = sorted.call_with_vectorcall(&[list], None).unwrap();
+        assert_eq!(result.extract::<Vec<i32>>(py).unwrap(), vec![1, 2, 3]);
+
+        let list = vec![3, 1, 2].to_object(py);
+        let reverse = true.to_object(py);
+        let result = sorted
+            .call_with_vectorcall(&[list, reverse], Some(&["reverse"][..]))
+            .unwrap();
+        assert_eq!(result.extract::<Vec<i32>>(py).unwrap(), vec![3, 2, 1]);
+
+        // Instances of Python classes don't support vectorcall and use the fallback path
+        let locals = PyDict::new(py);
+        py.run(
+            "class Adder:\n    def __call__(self, a, b=0):\n        return a + b",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        let adder = py.eval("Adder()", None, Some(locals)).unwrap();
+        let result = adder
+            .call_with_vectorcall(&[1.to_object(py), 2.to_object(py)], Some(&["b"][..]))
+            .unwrap();
+        assert_eq!(result.extract::<i32>(py).unwrap(), 3);
+
+        assert!(sorted
+            .call_with_vectorcall(&[], Some(&["reverse"][..]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_type_is() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let list = py.eval("[1, 2]", None, None).unwrap();
+        assert!(list.type_is::<PyList>());
+        assert!(!list.type_is::<PyDict>());
+
+        let boolean = py.eval("True", None, None).unwrap();
+        assert!(boolean.type_is::<PyBool>());
+        // Subclasses don't match
+        assert!(!boolean.type_is::<PyLong>());
+    }
+
+    #[test]
+    fn test_is_exact_instance_of() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let int = py.eval("5", None, None).unwrap();
+        assert!(int.is_exact_instance_of::<PyLong>());
+        assert!(!int.is_exact_instance_of::<PyBool>());
+
+        let boolean = py.eval("True", None, None).unwrap();
+        assert!(boolean.is_exact_instance_of::<PyBool>());
+        assert!(!boolean.is_exact_instance_of::<PyLong>());
+        assert!(py.is_instance::<PyLong, _>(boolean).unwrap());
+    }
+
+    #[test]
+    fn test_downcast_exact() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let boolean = py.eval("True", None, None).unwrap();
+        assert!(boolean.downcast_exact::<PyBool>().is_ok());
+        assert!(boolean.downcast_ref::<PyLong>().is_ok());
+        assert!(boolean.downcast_exact::<PyLong>().is_err());
+
+        let int = py.eval("5", None, None).unwrap();
+        assert!(int.downcast_exact::<PyLong>().is_ok());
+    }
 }
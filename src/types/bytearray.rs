@@ -4,6 +4,7 @@ use crate::ffi;
 use crate::instance::PyNativeType;
 use crate::internal_tricks::Unsendable;
 use crate::object::PyObject;
+use crate::types::PyBytes;
 use crate::AsPyPointer;
 use crate::Python;
 use std::os::raw::c_char;
@@ -45,6 +46,43 @@ impl PyByteArray {
         self.len() == 0
     }
 
+    /// Gets the contents of the bytearray as a slice, without copying.
+    ///
+    /// # Safety
+    ///
+    /// The bytearray can be modified or resized by Python code, which may move its contents.
+    /// No Python code that could do so may run while the slice is alive. Prefer
+    /// [PyByteArray::to_vec] if that can't be guaranteed.
+    pub unsafe fn data(&self) -> &[u8] {
+        let buffer = ffi::PyByteArray_AsString(self.0.as_ptr()) as *const u8;
+        let length = ffi::PyByteArray_Size(self.0.as_ptr()) as usize;
+        if length == 0 {
+            // The buffer pointer may be null for an empty bytearray
+            &[]
+        } else {
+            slice::from_raw_parts(buffer, length)
+        }
+    }
+
+    /// Gets the contents of the bytearray as a mutable slice, to modify it in place
+    /// without copying.
+    ///
+    /// # Safety
+    ///
+    /// Like [PyByteArray::data], and additionally no other slice of this bytearray may be
+    /// alive at the same time.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn data_mut(&self) -> &mut [u8] {
+        let buffer = ffi::PyByteArray_AsString(self.0.as_ptr()) as *mut u8;
+        let length = ffi::PyByteArray_Size(self.0.as_ptr()) as usize;
+        if length == 0 {
+            // The buffer pointer may be null for an empty bytearray
+            &mut []
+        } else {
+            slice::from_raw_parts_mut(buffer, length)
+        }
+    }
+
     /// Copies the contents of the bytearray to a rust vector
     ///
     /// # Example
@@ -67,12 +105,17 @@ impl PyByteArray {
     /// py.run("assert bytearray == b'Hello World.'", None, Some(locals)).unwrap();
     /// ```
     pub fn to_vec(&self) -> Vec<u8> {
-        let slice = unsafe {
-            let buffer = ffi::PyByteArray_AsString(self.0.as_ptr()) as *mut u8;
-            let length = ffi::PyByteArray_Size(self.0.as_ptr()) as usize;
-            slice::from_raw_parts_mut(buffer, length)
-        };
-        slice.to_vec()
+        unsafe { self.data() }.to_vec()
+    }
+
+    /// Copies the contents of this bytearray into a new, immutable Python byte string.
+    ///
+    /// This is equivalent to the Python expression: `bytes(self)`.
+    pub fn to_bytes(&self) -> PyResult<&PyBytes> {
+        unsafe {
+            self.py()
+                .from_owned_ptr_or_err(ffi::PyBytes_FromObject(self.as_ptr()))
+        }
     }
 
     /// Resize bytearray object to `len`.
@@ -122,4 +165,29 @@ mod test {
         }
         drop(none);
     }
+
+    #[test]
+    fn test_data_mut() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let bytearray = PyByteArray::new(py, b"Hello");
+        unsafe { bytearray.data_mut() }.copy_from_slice(b"Jelly");
+        assert_eq!(unsafe { bytearray.data() }, b"Jelly");
+        assert_eq!(bytearray.to_vec(), b"Jelly".to_vec());
+
+        let empty = PyByteArray::new(py, b"");
+        assert!(unsafe { empty.data_mut() }.is_empty());
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let bytearray = PyByteArray::new(py, b"Hello");
+        let bytes = bytearray.to_bytes().unwrap();
+        bytearray.resize(0).unwrap();
+        assert_eq!(bytes.as_bytes(), b"Hello");
+    }
 }
@@ -1,12 +1,14 @@
 use crate::conversion::FromPyObject;
 use crate::conversion::{PyTryFrom, ToPyObject};
 use crate::err::PyResult;
+use crate::instance::PyNativeType;
 use crate::internal_tricks::Unsendable;
 use crate::object::PyObject;
-use crate::types::PyAny;
+use crate::types::{PyAny, PyByteArray, PyString};
 use crate::AsPyPointer;
 use crate::Python;
 use crate::{ffi, FromPy};
+use std::ffi::CString;
 use std::ops::Index;
 use std::os::raw::c_char;
 use std::slice::SliceIndex;
@@ -51,6 +53,34 @@ impl PyBytes {
             std::slice::from_raw_parts(buffer, length)
         }
     }
+
+    /// Decodes the byte string using the codec registered for `encoding`.
+    ///
+    /// `errors` selects the error handling scheme, e.g. `"strict"` or `"replace"`.
+    ///
+    /// This is equivalent to the Python expression: `self.decode(encoding, errors)`.
+    pub fn decode(&self, encoding: &str, errors: &str) -> PyResult<&PyString> {
+        let encoding = CString::new(encoding)?;
+        let errors = CString::new(errors)?;
+        unsafe {
+            self.py()
+                .from_owned_ptr_or_err(ffi::PyUnicode_FromEncodedObject(
+                    self.as_ptr(),
+                    encoding.as_ptr(),
+                    errors.as_ptr(),
+                ))
+        }
+    }
+
+    /// Copies the contents of this byte string into a new, mutable Python bytearray.
+    ///
+    /// This is equivalent to the Python expression: `bytearray(self)`.
+    pub fn to_bytearray(&self) -> PyResult<&PyByteArray> {
+        unsafe {
+            self.py()
+                .from_owned_ptr_or_err(ffi::PyByteArray_FromObject(self.as_ptr()))
+        }
+    }
 }
 
 /// This is the same way [Vec] is indexed
@@ -79,6 +109,31 @@ mod test {
     use crate::FromPyObject;
     use crate::Python;
 
+    #[test]
+    fn test_decode() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let bytes = PyBytes::new(py, b"caf\xe9");
+        let s = bytes.decode("latin-1", "strict").unwrap();
+        assert_eq!(s.to_string().unwrap(), "caf\u{e9}");
+        let s = bytes.decode("utf-8", "replace").unwrap();
+        assert_eq!(s.to_string().unwrap(), "caf\u{fffd}");
+        assert!(bytes.decode("utf-8", "strict").is_err());
+    }
+
+    #[test]
+    fn test_to_bytearray() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let bytes = PyBytes::new(py, b"Hello");
+        let bytearray = bytes.to_bytearray().unwrap();
+        bytearray.resize(4).unwrap();
+        assert_eq!(bytearray.to_vec(), b"Hell");
+        assert_eq!(bytes.as_bytes(), b"Hello");
+    }
+
     #[test]
     fn test_extract_bytes() {
         let gil = Python::acquire_gil();
@@ -0,0 +1,196 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::err::{PyErr, PyResult};
+use crate::ffi;
+use crate::instance::PyNativeType;
+use crate::internal_tricks::Unsendable;
+use crate::object::PyObject;
+use crate::AsPyPointer;
+use crate::Python;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_void;
+
+/// Represents a Python `PyCapsule`, which wraps an opaque pointer so that it can be shared
+/// between extension modules.
+///
+/// See the [capsule documentation](https://docs.python.org/3/c-api/capsule.html).
+///
+/// # Example
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::PyCapsule;
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+///
+/// let value = Box::into_raw(Box::new(42u32));
+/// let capsule = PyCapsule::new(py, value, "example.value", None).unwrap();
+/// assert_eq!(capsule.name(), "example.value");
+///
+/// let pointer = capsule.pointer::<u32>().unwrap();
+/// assert_eq!(unsafe { *pointer }, 42);
+/// # drop(unsafe { Box::from_raw(value) });
+/// ```
+#[repr(transparent)]
+pub struct PyCapsule(PyObject, Unsendable);
+
+pyobject_native_var_type!(PyCapsule, ffi::PyCapsule_Type, ffi::PyCapsule_CheckExact);
+
+/// Stored as the capsule's context, since CPython neither copies the name nor runs
+/// more than one destructor.
+struct CapsuleContext {
+    _name: CString,
+    destructor: Option<ffi::PyCapsule_Destructor>,
+}
+
+impl PyCapsule {
+    /// Creates a new capsule holding `value`, which must not be null.
+    ///
+    /// `name` should be the full import path of the capsule, e.g. `"module.attribute"`,
+    /// so that other extensions can get the pointer with [PyCapsule::import].
+    /// The optional `destructor` is called with the capsule when it is destroyed, and
+    /// is responsible for freeing the value.
+    pub fn new<'p, T>(
+        py: Python<'p>,
+        value: *mut T,
+        name: &str,
+        destructor: Option<ffi::PyCapsule_Destructor>,
+    ) -> PyResult<&'p PyCapsule> {
+        let name = CString::new(name)?;
+        unsafe {
+            let capsule: &PyCapsule = py.from_owned_ptr_or_err(ffi::PyCapsule_New(
+                value as *mut c_void,
+                name.as_ptr(),
+                Some(capsule_destructor),
+            ))?;
+            let context = Box::new(CapsuleContext {
+                _name: name,
+                destructor,
+            });
+            if ffi::PyCapsule_SetContext(capsule.as_ptr(), Box::into_raw(context) as _) != 0 {
+                return Err(PyErr::fetch(py));
+            }
+            Ok(capsule)
+        }
+    }
+
+    /// Imports the pointer of the capsule at `name`, e.g. `"module.attribute"`.
+    ///
+    /// This imports `module` and checks that the capsule at `attribute` has the same name.
+    pub fn import<T>(py: Python, name: &str) -> PyResult<*mut T> {
+        let name = CString::new(name)?;
+        let pointer = unsafe { ffi::PyCapsule_Import(name.as_ptr(), 0) };
+        if pointer.is_null() {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(pointer as *mut T)
+        }
+    }
+
+    /// Returns the name of the capsule.
+    ///
+    /// Returns an empty string if the capsule has no name or its name is not valid UTF-8.
+    pub fn name(&self) -> &str {
+        unsafe {
+            let name = ffi::PyCapsule_GetName(self.as_ptr());
+            if name.is_null() {
+                ""
+            } else {
+                CStr::from_ptr(name).to_str().unwrap_or("")
+            }
+        }
+    }
+
+    /// Returns the pointer stored in the capsule.
+    ///
+    /// This can't check that `T` is the type of the stored value; use the name of the capsule
+    /// to identify its contents.
+    pub fn pointer<T>(&self) -> PyResult<*mut T> {
+        unsafe {
+            let name = ffi::PyCapsule_GetName(self.as_ptr());
+            let pointer = ffi::PyCapsule_GetPointer(self.as_ptr(), name);
+            if pointer.is_null() {
+                Err(PyErr::fetch(self.py()))
+            } else {
+                Ok(pointer as *mut T)
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn capsule_destructor(capsule: *mut ffi::PyObject) {
+    let context = ffi::PyCapsule_GetContext(capsule) as *mut CapsuleContext;
+    if context.is_null() {
+        return;
+    }
+    let context = Box::from_raw(context);
+    if let Some(destructor) = context.destructor {
+        destructor(capsule);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ffi;
+    use crate::types::{PyCapsule, PyModule};
+    use crate::{ObjectProtocol, Python};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_capsule() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let mut value = 7i32;
+        let capsule = PyCapsule::new(py, &mut value as *mut i32, "test.value", None).unwrap();
+        assert_eq!(capsule.name(), "test.value");
+        let pointer = capsule.pointer::<i32>().unwrap();
+        assert_eq!(pointer, &mut value as *mut i32);
+        assert_eq!(unsafe { *pointer }, 7);
+    }
+
+    #[test]
+    fn test_capsule_null_pointer() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(PyCapsule::new(py, std::ptr::null_mut::<i32>(), "test.null", None).is_err());
+    }
+
+    #[test]
+    fn test_capsule_destructor() {
+        static DESTROYED: AtomicBool = AtomicBool::new(false);
+
+        unsafe extern "C" fn destructor(capsule: *mut ffi::PyObject) {
+            let name = ffi::PyCapsule_GetName(capsule);
+            let value = ffi::PyCapsule_GetPointer(capsule, name) as *mut u64;
+            drop(Box::from_raw(value));
+            DESTROYED.store(true, Ordering::SeqCst);
+        }
+
+        {
+            let gil = Python::acquire_gil();
+            let py = gil.python();
+            let value = Box::into_raw(Box::new(1u64));
+            PyCapsule::new(py, value, "test.boxed", Some(destructor)).unwrap();
+        }
+
+        assert!(DESTROYED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_capsule_import() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let mut value = 3u8;
+        let capsule = PyCapsule::new(py, &mut value as *mut u8, "sys.pyo3_capsule", None).unwrap();
+        let sys = PyModule::import(py, "sys").unwrap();
+        sys.add("pyo3_capsule", capsule).unwrap();
+
+        let pointer = PyCapsule::import::<u8>(py, "sys.pyo3_capsule").unwrap();
+        assert_eq!(pointer, &mut value as *mut u8);
+        assert!(PyCapsule::import::<u8>(py, "sys.not_a_capsule").is_err());
+
+        sys.delattr("pyo3_capsule").unwrap();
+    }
+}
@@ -21,6 +21,12 @@ pyobject_native_type!(
 );
 
 impl PyComplex {
+    /// Creates a new Python `complex` object, from its real and imaginary values.
+    ///
+    /// This is the same as [PyComplex::from_doubles].
+    pub fn new(py: Python, real: c_double, imag: c_double) -> &PyComplex {
+        Self::from_doubles(py, real, imag)
+    }
     /// Creates a new Python `PyComplex` object, from its real and imaginary values.
     pub fn from_doubles(py: Python, real: c_double, imag: c_double) -> &PyComplex {
         unsafe {
@@ -246,6 +252,22 @@ mod test {
         assert_approx_eq!(complex.imag(), 1.2);
     }
 
+    #[test]
+    fn test_new_roundtrip() {
+        use crate::{ObjectProtocol, ToPyObject};
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = PyComplex::new(py, 1.5, -2.0).to_object(py);
+        let complex: &PyComplex = obj.extract(py).unwrap();
+        assert_approx_eq!(complex.real(), 1.5);
+        assert_approx_eq!(complex.imag(), -2.0);
+        let imaginary_unit = py.eval("1j", None, None).unwrap();
+        assert!(imaginary_unit.extract::<&PyComplex>().is_ok());
+        let float = py.eval("1.0", None, None).unwrap();
+        assert!(float.extract::<&PyComplex>().is_err());
+    }
+
     #[cfg(not(Py_LIMITED_API))]
     #[test]
     fn test_add() {
@@ -379,3 +379,277 @@ unsafe fn opt_to_pyobj(py: Python, opt: Option<&PyObject>) -> *mut ffi::PyObject
         None => py.None().as_ptr(),
     }
 }
+
+/// Conversions between the types of the `chrono` crate and the `datetime` module.
+///
+/// Python's `datetime` has a resolution of microseconds, so nanoseconds are truncated and leap
+/// seconds are clamped to the end of the previous second.
+#[cfg(feature = "chrono")]
+mod chrono_conversion {
+    use super::*;
+    use crate::exceptions::{TypeError, ValueError};
+    use crate::types::PyAny;
+    use crate::{FromPyObject, IntoPy, ObjectProtocol, PyTryFrom};
+    use chrono::{
+        DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+    };
+    use std::convert::TryFrom;
+
+    fn microsecond(time: &impl Timelike) -> u32 {
+        std::cmp::min(time.nanosecond() / 1000, 999_999)
+    }
+
+    fn ensure_naive(obj: &PyAny) -> PyResult<()> {
+        if obj.getattr("tzinfo")?.is_none() {
+            Ok(())
+        } else {
+            Err(TypeError::py_err("expected a naive object without tzinfo"))
+        }
+    }
+
+    fn naive_datetime(dt: &PyDateTime) -> PyResult<NaiveDateTime> {
+        NaiveDate::from_ymd_opt(dt.get_year(), dt.get_month().into(), dt.get_day().into())
+            .and_then(|date| {
+                date.and_hms_micro_opt(
+                    dt.get_hour().into(),
+                    dt.get_minute().into(),
+                    dt.get_second().into(),
+                    dt.get_microsecond(),
+                )
+            })
+            .ok_or_else(|| ValueError::py_err("invalid datetime"))
+    }
+
+    fn naive_datetime_to_py(py: Python, dt: &NaiveDateTime, tzinfo: Option<&PyObject>) -> PyObject {
+        PyDateTime::new(
+            py,
+            dt.year(),
+            dt.month() as u8,
+            dt.day() as u8,
+            dt.hour() as u8,
+            dt.minute() as u8,
+            dt.second() as u8,
+            microsecond(dt),
+            tzinfo,
+        )
+        .expect("failed to construct datetime.datetime")
+        .into()
+    }
+
+    macro_rules! to_object_via_into_py {
+        ($ty: ty) => {
+            impl ToPyObject for $ty {
+                #[inline]
+                fn to_object(&self, py: Python) -> PyObject {
+                    (*self).into_py(py)
+                }
+            }
+        };
+    }
+
+    to_object_via_into_py!(NaiveDate);
+    to_object_via_into_py!(NaiveTime);
+    to_object_via_into_py!(NaiveDateTime);
+    to_object_via_into_py!(DateTime<Utc>);
+    to_object_via_into_py!(Duration);
+
+    /// Converts to a `datetime.date`.
+    impl IntoPy<PyObject> for NaiveDate {
+        fn into_py(self, py: Python) -> PyObject {
+            PyDate::new(py, self.year(), self.month() as u8, self.day() as u8)
+                .expect("failed to construct datetime.date")
+                .into()
+        }
+    }
+
+    impl<'source> FromPyObject<'source> for NaiveDate {
+        fn extract(obj: &'source PyAny) -> PyResult<Self> {
+            let date = <PyDate as PyTryFrom>::try_from(obj)?;
+            NaiveDate::from_ymd_opt(
+                date.get_year(),
+                date.get_month().into(),
+                date.get_day().into(),
+            )
+            .ok_or_else(|| ValueError::py_err("invalid date"))
+        }
+    }
+
+    /// Converts to a `datetime.time` without tzinfo.
+    impl IntoPy<PyObject> for NaiveTime {
+        fn into_py(self, py: Python) -> PyObject {
+            PyTime::new(
+                py,
+                self.hour() as u8,
+                self.minute() as u8,
+                self.second() as u8,
+                microsecond(&self),
+                None,
+            )
+            .expect("failed to construct datetime.time")
+            .into()
+        }
+    }
+
+    /// Only accepts a `datetime.time` without tzinfo.
+    impl<'source> FromPyObject<'source> for NaiveTime {
+        fn extract(obj: &'source PyAny) -> PyResult<Self> {
+            let time = <PyTime as PyTryFrom>::try_from(obj)?;
+            ensure_naive(obj)?;
+            NaiveTime::from_hms_micro_opt(
+                time.get_hour().into(),
+                time.get_minute().into(),
+                time.get_second().into(),
+                time.get_microsecond(),
+            )
+            .ok_or_else(|| ValueError::py_err("invalid time"))
+        }
+    }
+
+    /// Converts to a `datetime.datetime` without tzinfo.
+    impl IntoPy<PyObject> for NaiveDateTime {
+        fn into_py(self, py: Python) -> PyObject {
+            naive_datetime_to_py(py, &self, None)
+        }
+    }
+
+    /// Only accepts a `datetime.datetime` without tzinfo.
+    impl<'source> FromPyObject<'source> for NaiveDateTime {
+        fn extract(obj: &'source PyAny) -> PyResult<Self> {
+            let dt = <PyDateTime as PyTryFrom>::try_from(obj)?;
+            ensure_naive(obj)?;
+            naive_datetime(dt)
+        }
+    }
+
+    /// Converts to a `datetime.datetime` with `datetime.timezone.utc` as tzinfo.
+    impl IntoPy<PyObject> for DateTime<Utc> {
+        fn into_py(self, py: Python) -> PyObject {
+            let utc: PyObject = py
+                .import("datetime")
+                .and_then(|datetime| datetime.get("timezone"))
+                .and_then(|timezone| timezone.getattr("utc"))
+                .expect("failed to get datetime.timezone.utc")
+                .into();
+            naive_datetime_to_py(py, &self.naive_utc(), Some(&utc))
+        }
+    }
+
+    /// Accepts a `datetime.datetime` with any tzinfo and converts it to UTC.
+    impl<'source> FromPyObject<'source> for DateTime<Utc> {
+        fn extract(obj: &'source PyAny) -> PyResult<Self> {
+            let dt = <PyDateTime as PyTryFrom>::try_from(obj)?;
+            let offset = obj.call_method0("utcoffset")?;
+            if offset.is_none() {
+                return Err(TypeError::py_err("expected a datetime with tzinfo"));
+            }
+            let offset: Duration = offset.extract()?;
+            Ok(Utc.from_utc_datetime(&(naive_datetime(dt)? - offset)))
+        }
+    }
+
+    /// Converts to a `datetime.timedelta`.
+    impl IntoPy<PyObject> for Duration {
+        fn into_py(self, py: Python) -> PyObject {
+            let days = self.num_days();
+            let rest = self - Duration::days(days);
+            let seconds = rest.num_seconds();
+            // Less than a second, so this can't overflow
+            let microseconds = (rest - Duration::seconds(seconds))
+                .num_microseconds()
+                .unwrap();
+            let days = i32::try_from(days).expect("Duration is too large for datetime.timedelta");
+            PyDelta::new(py, days, seconds as i32, microseconds as i32, true)
+                .expect("failed to construct datetime.timedelta")
+                .into()
+        }
+    }
+
+    impl<'source> FromPyObject<'source> for Duration {
+        fn extract(obj: &'source PyAny) -> PyResult<Self> {
+            let delta = <PyDelta as PyTryFrom>::try_from(obj)?;
+            Ok(Duration::days(delta.get_days().into())
+                + Duration::seconds(delta.get_seconds().into())
+                + Duration::microseconds(delta.get_microseconds().into()))
+        }
+    }
+
+    #[test]
+    fn test_naive_roundtrip() {
+        use crate::AsPyRef;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let date = NaiveDate::from_ymd(2020, 2, 29);
+        let obj = date.to_object(py);
+        assert!(<PyDate as PyTryFrom>::try_from(obj.as_ref(py)).is_ok());
+        assert_eq!(obj.extract::<NaiveDate>(py).unwrap(), date);
+
+        let time = NaiveTime::from_hms_micro(23, 59, 1, 123_456);
+        assert_eq!(time.to_object(py).extract::<NaiveTime>(py).unwrap(), time);
+
+        let dt = date.and_time(time);
+        let obj = dt.to_object(py);
+        assert!(<PyDateTime as PyTryFrom>::try_from(obj.as_ref(py)).is_ok());
+        assert_eq!(obj.extract::<NaiveDateTime>(py).unwrap(), dt);
+        assert!(obj.extract::<DateTime<Utc>>(py).is_err());
+
+        // Leap seconds are clamped
+        let leap = NaiveTime::from_hms_milli(23, 59, 59, 1_500);
+        assert_eq!(
+            leap.to_object(py).extract::<NaiveTime>(py).unwrap(),
+            NaiveTime::from_hms_micro(23, 59, 59, 999_999)
+        );
+    }
+
+    #[test]
+    fn test_utc_datetime() {
+        use crate::types::IntoPyDict;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let dt = Utc.ymd(2020, 1, 1).and_hms_micro(12, 30, 0, 5);
+        let obj = dt.to_object(py);
+        assert_eq!(obj.extract::<DateTime<Utc>>(py).unwrap(), dt);
+        assert!(obj.extract::<NaiveDateTime>(py).is_err());
+        let locals = [("dt", obj)].into_py_dict(py);
+        py.run(
+            "import datetime; assert dt.tzinfo is datetime.timezone.utc",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+
+        let aware = py
+            .eval(
+                "__import__('datetime').datetime(2020, 1, 1, 14, 30, 0, 5, \
+                 tzinfo=__import__('datetime').timezone(__import__('datetime').timedelta(hours=2)))",
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(aware.extract::<DateTime<Utc>>().unwrap(), dt);
+    }
+
+    #[test]
+    fn test_duration() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        for duration in &[
+            Duration::zero(),
+            Duration::days(3) + Duration::microseconds(12),
+            Duration::seconds(-1),
+            -(Duration::days(2) + Duration::seconds(5) + Duration::microseconds(7)),
+        ] {
+            let obj = duration.to_object(py);
+            assert_eq!(obj.extract::<Duration>(py).unwrap(), *duration);
+        }
+
+        let delta = py
+            .eval("__import__('datetime').timedelta(minutes=-1)", None, None)
+            .unwrap();
+        assert_eq!(delta.extract::<Duration>().unwrap(), Duration::minutes(-1));
+    }
+}
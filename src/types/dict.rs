@@ -1,10 +1,10 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
-use crate::err::{self, PyErr, PyResult};
+use crate::err::{self, extract_item, PyErr, PyResult};
 use crate::instance::PyNativeType;
 use crate::internal_tricks::Unsendable;
 use crate::object::PyObject;
-use crate::types::{PyAny, PyList};
+use crate::types::{PyAny, PyList, PyString};
 use crate::AsPyPointer;
 #[cfg(not(PyPy))]
 use crate::IntoPyPointer;
@@ -93,6 +93,10 @@ impl PyDict {
 
     /// Gets an item from the dictionary.
     /// Returns None if the item is not present, or if an error occurs.
+    ///
+    /// Exceptions raised by the `__hash__` or `__eq__` of the key are silently discarded,
+    /// use [`get_item_with_error`](#method.get_item_with_error) to handle them.
+    #[deprecated(since = "0.9.0", note = "Please use `get_item_with_error` instead")]
     pub fn get_item<K>(&self, key: K) -> Option<&PyAny>
     where
         K: ToBorrowedObject,
@@ -103,6 +107,24 @@ impl PyDict {
         })
     }
 
+    /// Gets an item from the dictionary.
+    ///
+    /// Returns `Ok(None)` if the item is not present, and `Err` if computing the hash of
+    /// the key or comparing it with the keys of the dictionary raises an exception.
+    pub fn get_item_with_error<K>(&self, key: K) -> PyResult<Option<&PyAny>>
+    where
+        K: ToBorrowedObject,
+    {
+        key.with_borrowed_ptr(self.py(), |key| unsafe {
+            let ptr = ffi::PyDict_GetItemWithError(self.as_ptr(), key);
+            if ptr.is_null() && !ffi::PyErr_Occurred().is_null() {
+                Err(PyErr::fetch(self.py()))
+            } else {
+                Ok(self.py().from_borrowed_ptr_or_opt(ptr))
+            }
+        })
+    }
+
     /// Sets an item value.
     /// This is equivalent to the Python expression `self[key] = value`.
     pub fn set_item<K, V>(&self, key: K, value: V) -> PyResult<()>
@@ -117,6 +139,34 @@ impl PyDict {
         })
     }
 
+    /// Sets the value of `key` to the result of `updater`, which is passed the current value
+    /// or `None` if the key is absent.
+    ///
+    /// The key is only converted to a Python object once. Errors raised while looking up the
+    /// key, e.g. by its `__eq__`, are returned. If `updater` returns an error, the dict is
+    /// left unchanged.
+    pub fn set_item_or_update<K, F>(&self, key: K, updater: F) -> PyResult<()>
+    where
+        K: ToBorrowedObject,
+        F: FnOnce(Option<&PyAny>) -> PyResult<PyObject>,
+    {
+        key.with_borrowed_ptr(self.py(), |key| unsafe {
+            let ptr = ffi::PyDict_GetItemWithError(self.as_ptr(), key);
+            if ptr.is_null() && !ffi::PyErr_Occurred().is_null() {
+                return Err(PyErr::fetch(self.py()));
+            }
+            // The dict only holds a borrowed reference, which `updater` could invalidate by
+            // modifying the dict
+            ffi::Py_XINCREF(ptr);
+            let current = self.py().from_owned_ptr_or_opt(ptr);
+            let value = updater(current)?;
+            err::error_on_minusone(
+                self.py(),
+                ffi::PyDict_SetItem(self.as_ptr(), key, value.as_ptr()),
+            )
+        })
+    }
+
     /// Deletes an item.
     /// This is equivalent to the Python expression `del self[key]`.
     pub fn del_item<K>(&self, key: K) -> PyResult<()>
@@ -164,6 +214,29 @@ impl PyDict {
             pos: 0,
         }
     }
+
+    /// Returns the keys of this dictionary, which must all be strings, as `&str`.
+    ///
+    /// Returns a `TypeError` if a key is not a `str`.
+    pub fn string_keys(&self) -> PyResult<Vec<&str>> {
+        self.iter().map(|(key, _)| key_as_str(key)).collect()
+    }
+
+    /// Returns the (key, value) pairs of this dictionary, whose keys must all be strings,
+    /// with the keys as `&str`.
+    ///
+    /// Returns a `TypeError` if a key is not a `str`.
+    pub fn string_items(&self) -> PyResult<Vec<(&str, &PyAny)>> {
+        self.iter()
+            .map(|(key, value)| Ok((key_as_str(key)?, value)))
+            .collect()
+    }
+}
+
+fn key_as_str(key: &PyAny) -> PyResult<&str> {
+    let bytes = key.downcast_ref::<PyString>()?.as_bytes()?;
+    // `PyUnicode_AsUTF8AndSize` always returns valid UTF-8
+    Ok(unsafe { std::str::from_utf8_unchecked(bytes) })
 }
 
 pub struct PyDictIterator<'py> {
@@ -317,7 +390,7 @@ where
         let dict = <PyDict as PyTryFrom>::try_from(ob)?;
         let mut ret = HashMap::default();
         for (k, v) in dict.iter() {
-            ret.insert(K::extract(k)?, V::extract(v)?);
+            ret.insert(extract_item(k, "dict key")?, extract_item(v, "dict value")?);
         }
         Ok(ret)
     }
@@ -332,20 +405,23 @@ where
         let dict = <PyDict as PyTryFrom>::try_from(ob)?;
         let mut ret = BTreeMap::new();
         for (k, v) in dict.iter() {
-            ret.insert(K::extract(k)?, V::extract(v)?);
+            ret.insert(extract_item(k, "dict key")?, extract_item(v, "dict value")?);
         }
         Ok(ret)
     }
 }
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod test {
     use crate::conversion::IntoPy;
+    use crate::err::error_message;
+    use crate::exceptions::{TypeError, ValueError};
     use crate::instance::AsPyRef;
     use crate::types::dict::IntoPyDict;
-    use crate::types::{PyDict, PyList, PyTuple};
+    use crate::types::{PyAny, PyDict, PyList, PyTuple};
     use crate::Python;
-    use crate::{ObjectProtocol, PyObject};
+    use crate::{ObjectProtocol, PyObject, PyResult};
     use crate::{PyTryFrom, ToPyObject};
     use std::collections::{BTreeMap, HashMap};
 
@@ -433,6 +509,28 @@ mod test {
         assert_eq!(None, dict.get_item(8i32));
     }
 
+    #[test]
+    fn test_get_item_with_error() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = PyDict::new(py);
+        dict.set_item(7i32, 32i32).unwrap();
+        assert_eq!(
+            32,
+            dict.get_item_with_error(7i32)
+                .unwrap()
+                .unwrap()
+                .extract::<i32>()
+                .unwrap()
+        );
+        assert_eq!(None, dict.get_item_with_error(8i32).unwrap());
+
+        // Unhashable keys raise TypeError instead of being reported as missing
+        let key = PyList::empty(py);
+        let err = dict.get_item_with_error(key).unwrap_err();
+        assert!(err.is_instance::<TypeError>(py));
+    }
+
     #[test]
     fn test_set_item() {
         let gil = Python::acquire_gil();
@@ -484,6 +582,80 @@ mod test {
         assert_eq!(None, v.get(&8i32));
     }
 
+    #[test]
+    fn test_set_item_or_update() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = PyDict::new(py);
+        let increment = |value: Option<&PyAny>| -> PyResult<PyObject> {
+            let count = value.map_or(Ok(0), |v| v.extract::<i32>())?;
+            Ok((count + 1).to_object(py))
+        };
+        dict.set_item_or_update("a", increment).unwrap();
+        dict.set_item_or_update("a", increment).unwrap();
+        assert_eq!(2, dict.get_item("a").unwrap().extract::<i32>().unwrap());
+
+        dict.set_item("b", "not a number").unwrap();
+        assert!(dict.set_item_or_update("b", increment).is_err());
+        assert_eq!(
+            "not a number",
+            dict.get_item("b").unwrap().extract::<&str>().unwrap()
+        );
+
+        // The current value stays alive if `updater` removes it from the dict
+        dict.set_item_or_update("b", |value| {
+            dict.clear();
+            Ok(value.unwrap().to_object(py))
+        })
+        .unwrap();
+        assert_eq!(dict.len(), 1);
+    }
+
+    #[test]
+    fn test_set_item_or_update_lookup_error() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let locals = PyDict::new(py);
+        py.run(
+            "class Key:\n    def __hash__(self): return 0\n    def __eq__(self, other): raise ValueError()\n\
+             d = {Key(): 1}\n\
+             key = Key()",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        let dict: &PyDict = locals.get_item("d").unwrap().downcast_ref().unwrap();
+        let key = locals.get_item("key").unwrap();
+        let err = dict
+            .set_item_or_update(key, |_| panic!("updater must not be called"))
+            .unwrap_err();
+        assert!(err.is_instance::<ValueError>(py));
+        assert_eq!(dict.len(), 1);
+    }
+
+    #[test]
+    fn test_string_keys() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = [("a", 1), ("b", 2)].into_py_dict(py);
+        let mut keys = dict.string_keys().unwrap();
+        keys.sort();
+        assert_eq!(keys, vec!["a", "b"]);
+
+        let mut items: Vec<(&str, i32)> = dict
+            .string_items()
+            .unwrap()
+            .into_iter()
+            .map(|(k, v)| (k, v.extract().unwrap()))
+            .collect();
+        items.sort();
+        assert_eq!(items, vec![("a", 1), ("b", 2)]);
+
+        dict.set_item(3, 3).unwrap();
+        assert!(dict.string_keys().is_err());
+        assert!(dict.string_items().is_err());
+    }
+
     #[test]
     fn test_del_item() {
         let gil = Python::acquire_gil();
@@ -697,6 +869,31 @@ mod test {
         assert_eq!(py_map.get_item(1).unwrap().extract::<i32>().unwrap(), 1);
     }
 
+    #[test]
+    fn test_btreemap_extract_err() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let dict = vec![("a", 1.to_object(py)), ("b", "x".to_object(py))].into_py_dict(py);
+        let mut err = dict.extract::<BTreeMap<String, i32>>().unwrap_err();
+        assert!(err.is_instance::<TypeError>(py));
+        let cause = err.cause(py).unwrap();
+        assert_eq!(
+            error_message(py, cause),
+            "TypeError: failed to extract dict value 'x'"
+        );
+        // The original error is kept
+        let expected = "x".to_object(py).extract::<i32>(py).unwrap_err();
+        assert_eq!(error_message(py, err), error_message(py, expected));
+
+        let dict = vec![(1, 1)].into_py_dict(py);
+        let mut err = dict.extract::<HashMap<String, i32>>().unwrap_err();
+        assert_eq!(
+            error_message(py, err.cause(py).unwrap()),
+            "TypeError: failed to extract dict key 1"
+        );
+    }
+
     #[test]
     fn test_vec_into_dict() {
         let gil = Python::acquire_gil();
@@ -0,0 +1,159 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::callback::PyObjectCallbackConverter;
+use crate::err::PyResult;
+use crate::ffi;
+use crate::instance::PyNativeType;
+use crate::internal_tricks::Unsendable;
+use crate::object::PyObject;
+use crate::types::{PyCapsule, PyTuple};
+use crate::AsPyPointer;
+use crate::Python;
+use std::os::raw::c_char;
+
+/// Represents a Python built-in function, like the functions created with `#[pyfunction]`.
+#[repr(transparent)]
+pub struct PyCFunction(PyObject, Unsendable);
+
+pyobject_native_var_type!(PyCFunction, ffi::PyCFunction_Type, ffi::PyCFunction_Check);
+
+type Closure = dyn Fn(Python, Vec<PyObject>) -> PyResult<PyObject> + Send;
+
+const CLOSURE_CAPSULE_NAME: &str = "pyo3.closure";
+
+/// Shared by all closures, which differ only in the capsule passed as `self`.
+static CLOSURE_DEF: ffi::PyMethodDef = ffi::PyMethodDef {
+    ml_name: "closure\0".as_ptr() as *const c_char,
+    ml_meth: Some(run_closure),
+    ml_flags: ffi::METH_VARARGS,
+    ml_doc: std::ptr::null(),
+};
+
+impl PyCFunction {
+    /// Creates a Python callable from a Rust closure.
+    ///
+    /// The closure receives the positional arguments of each call, keyword arguments are
+    /// rejected with a `TypeError`. It is dropped when the function object is destroyed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::{IntoPyDict, PyCFunction};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    ///
+    /// let add_one = PyCFunction::new_closure(py, |py, args| {
+    ///     let x: i64 = args[0].extract(py)?;
+    ///     Ok((x + 1).to_object(py))
+    /// })
+    /// .unwrap();
+    /// let locals = [("add_one", add_one)].into_py_dict(py);
+    /// py.run("assert add_one(41) == 42", None, Some(locals)).unwrap();
+    /// ```
+    pub fn new_closure<F>(py: Python, f: F) -> PyResult<&PyCFunction>
+    where
+        F: Fn(Python, Vec<PyObject>) -> PyResult<PyObject> + Send + 'static,
+    {
+        // Boxed twice, since a capsule can only hold a thin pointer
+        let closure: Box<Box<Closure>> = Box::new(Box::new(f));
+        let closure = Box::into_raw(closure);
+        let capsule = match PyCapsule::new(py, closure, CLOSURE_CAPSULE_NAME, Some(drop_closure)) {
+            Ok(capsule) => capsule,
+            Err(e) => {
+                drop(unsafe { Box::from_raw(closure) });
+                return Err(e);
+            }
+        };
+        unsafe {
+            py.from_owned_ptr_or_err(ffi::PyCFunction_NewEx(
+                &CLOSURE_DEF as *const ffi::PyMethodDef as *mut ffi::PyMethodDef,
+                capsule.as_ptr(),
+                std::ptr::null_mut(),
+            ))
+        }
+    }
+}
+
+unsafe extern "C" fn run_closure(
+    capsule: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let py = Python::assume_gil_acquired();
+    let _pool = crate::GILPool::new(py);
+    let capsule = py.from_borrowed_ptr::<PyCapsule>(capsule);
+    let args = py.from_borrowed_ptr::<PyTuple>(args);
+
+    let result = capsule.pointer::<Box<Closure>>().and_then(|closure| {
+        let args = args.iter().map(|arg| arg.into()).collect();
+        (*closure)(py, args)
+    });
+    crate::callback::cb_convert(PyObjectCallbackConverter, py, result)
+}
+
+unsafe extern "C" fn drop_closure(capsule: *mut ffi::PyObject) {
+    let name = ffi::PyCapsule_GetName(capsule);
+    let closure = ffi::PyCapsule_GetPointer(capsule, name) as *mut Box<Closure>;
+    drop(Box::from_raw(closure));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::{IntoPyDict, PyCFunction};
+    use crate::{ObjectProtocol, PyObject, Python, ToPyObject};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_closure() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let prefix = String::from("Hello, ");
+        let greet = PyCFunction::new_closure(py, move |py, args| {
+            let name: String = args[0].extract(py)?;
+            Ok(format!("{}{}", prefix, name).to_object(py))
+        })
+        .unwrap();
+        let locals = [("greet", greet)].into_py_dict(py);
+        py.run(
+            "assert greet('World') == 'Hello, World'",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        assert!(py
+            .run("greet(name='World')", None, Some(locals))
+            .unwrap_err()
+            .is_instance::<crate::exceptions::TypeError>(py));
+        assert!(py
+            .run("greet(1)", None, Some(locals))
+            .unwrap_err()
+            .is_instance::<crate::exceptions::TypeError>(py));
+    }
+
+    #[test]
+    fn test_closure_dropped() {
+        struct SetOnDrop(Arc<AtomicBool>);
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        {
+            let gil = Python::acquire_gil();
+            let py = gil.python();
+            let guard = SetOnDrop(Arc::clone(&dropped));
+            let f = PyCFunction::new_closure(py, move |py, _: Vec<PyObject>| {
+                let _ = &guard;
+                Ok(py.None())
+            })
+            .unwrap();
+            assert!(f.call0().unwrap().is_none());
+        }
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
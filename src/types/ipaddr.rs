@@ -0,0 +1,251 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use crate::conversion::{FromPyObject, PyTryFrom, ToPyObject};
+use crate::err::PyResult;
+use crate::exceptions;
+use crate::instance::PyNativeType;
+use crate::object::PyObject;
+use crate::types::{PyAny, PyBytes, PyString, PyTuple};
+use crate::{FromPy, ObjectProtocol, Python};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+
+/// Creates an `ipaddress.IPv4Address` or `ipaddress.IPv6Address` from the packed octets.
+///
+/// `ToPyObject` can't fail, so if the `ipaddress` module can't be imported the Python error
+/// is printed before panicking.
+fn new_ip_address(py: Python, class: &str, octets: &[u8]) -> PyObject {
+    match py
+        .import("ipaddress")
+        .and_then(|module| module.call1(class, (PyBytes::new(py, octets),)))
+    {
+        Ok(address) => address.to_object(py),
+        Err(e) => {
+            e.print(py);
+            panic!("failed to create ipaddress.{}", class)
+        }
+    }
+}
+
+/// Converts Rust `Ipv4Addr` to Python `ipaddress.IPv4Address`.
+///
+/// # Panics
+///
+/// Panics if the `ipaddress` module can't be imported.
+impl ToPyObject for Ipv4Addr {
+    fn to_object(&self, py: Python) -> PyObject {
+        new_ip_address(py, "IPv4Address", &self.octets())
+    }
+}
+
+impl FromPy<Ipv4Addr> for PyObject {
+    fn from_py(other: Ipv4Addr, py: Python) -> Self {
+        other.to_object(py)
+    }
+}
+
+/// Converts Rust `Ipv6Addr` to Python `ipaddress.IPv6Address`.
+///
+/// # Panics
+///
+/// Panics if the `ipaddress` module can't be imported.
+impl ToPyObject for Ipv6Addr {
+    fn to_object(&self, py: Python) -> PyObject {
+        new_ip_address(py, "IPv6Address", &self.octets())
+    }
+}
+
+impl FromPy<Ipv6Addr> for PyObject {
+    fn from_py(other: Ipv6Addr, py: Python) -> Self {
+        other.to_object(py)
+    }
+}
+
+/// Converts Rust `IpAddr` to Python `ipaddress.IPv4Address` or `ipaddress.IPv6Address`.
+///
+/// # Panics
+///
+/// Panics if the `ipaddress` module can't be imported.
+impl ToPyObject for IpAddr {
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            IpAddr::V4(addr) => addr.to_object(py),
+            IpAddr::V6(addr) => addr.to_object(py),
+        }
+    }
+}
+
+impl FromPy<IpAddr> for PyObject {
+    fn from_py(other: IpAddr, py: Python) -> Self {
+        other.to_object(py)
+    }
+}
+
+/// Allows extracting `IpAddr` from Python objects.
+///
+/// Accepts `ipaddress.IPv4Address`, `ipaddress.IPv6Address` and `str` in the formats
+/// supported by `IpAddr::from_str`.
+impl<'source> FromPyObject<'source> for IpAddr {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if let Ok(s) = <PyString as PyTryFrom>::try_from(ob) {
+            return Ok(s.to_string()?.parse()?);
+        }
+        let packed = ob
+            .getattr("packed")
+            .ok()
+            .and_then(|packed| <PyBytes as PyTryFrom>::try_from(packed).ok());
+        match packed.map(PyBytes::as_bytes) {
+            Some(&[a, b, c, d]) => Ok(Ipv4Addr::new(a, b, c, d).into()),
+            Some(octets) if octets.len() == 16 => {
+                let mut array = [0; 16];
+                array.copy_from_slice(octets);
+                Ok(Ipv6Addr::from(array).into())
+            }
+            _ => Err(exceptions::TypeError::py_err(format!(
+                "'{}' object cannot be converted to an IP address",
+                ob.get_type().name()
+            ))),
+        }
+    }
+}
+
+/// Allows extracting `Ipv4Addr` from Python objects.
+/// See `FromPyObject for IpAddr` for the accepted types.
+impl<'source> FromPyObject<'source> for Ipv4Addr {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        match IpAddr::extract(ob)? {
+            IpAddr::V4(addr) => Ok(addr),
+            IpAddr::V6(_) => Err(exceptions::ValueError::py_err("expected an IPv4 address")),
+        }
+    }
+}
+
+/// Allows extracting `Ipv6Addr` from Python objects.
+/// See `FromPyObject for IpAddr` for the accepted types.
+impl<'source> FromPyObject<'source> for Ipv6Addr {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        match IpAddr::extract(ob)? {
+            IpAddr::V4(_) => Err(exceptions::ValueError::py_err("expected an IPv6 address")),
+            IpAddr::V6(addr) => Ok(addr),
+        }
+    }
+}
+
+/// Converts Rust `SocketAddr` to a tuple like the addresses of the `socket` module:
+/// `(host, port)` for IPv4 and `(host, port, flowinfo, scope_id)` for IPv6,
+/// where `host` is a `str`.
+impl ToPyObject for SocketAddr {
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            SocketAddr::V4(addr) => (addr.ip().to_string(), addr.port()).to_object(py),
+            SocketAddr::V6(addr) => (
+                addr.ip().to_string(),
+                addr.port(),
+                addr.flowinfo(),
+                addr.scope_id(),
+            )
+                .to_object(py),
+        }
+    }
+}
+
+impl FromPy<SocketAddr> for PyObject {
+    fn from_py(other: SocketAddr, py: Python) -> Self {
+        other.to_object(py)
+    }
+}
+
+/// Allows extracting `SocketAddr` from a `(host, port)` or `(host, port, flowinfo, scope_id)`
+/// tuple, where `host` is extracted like `IpAddr`. The 4-tuple form requires an IPv6 host.
+impl<'source> FromPyObject<'source> for SocketAddr {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        let py = ob.py();
+        let tuple = <PyTuple as PyTryFrom>::try_from(ob)?;
+        match tuple.as_slice() {
+            [host, port] => Ok(SocketAddr::new(host.extract(py)?, port.extract(py)?)),
+            [host, port, flowinfo, scope_id] => Ok(SocketAddrV6::new(
+                host.extract(py)?,
+                port.extract(py)?,
+                flowinfo.extract(py)?,
+                scope_id.extract(py)?,
+            )
+            .into()),
+            _ => Err(exceptions::ValueError::py_err(
+                "expected a (host, port) or (host, port, flowinfo, scope_id) tuple",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::IntoPyDict;
+    use crate::{ObjectProtocol, Python, ToPyObject};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    #[test]
+    fn test_ip_addr_roundtrip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let v4: IpAddr = Ipv4Addr::new(127, 0, 0, 1).into();
+        let v6: IpAddr = "2001:db8::1".parse().unwrap();
+        let locals = [("v4", v4.to_object(py)), ("v6", v6.to_object(py))].into_py_dict(py);
+        py.run(
+            "import ipaddress; \
+             assert v4 == ipaddress.IPv4Address('127.0.0.1'); \
+             assert v6 == ipaddress.IPv6Address('2001:db8::1')",
+            None,
+            Some(locals),
+        )
+        .unwrap();
+
+        assert_eq!(v4.to_object(py).extract::<IpAddr>(py).unwrap(), v4);
+        assert_eq!(v6.to_object(py).extract::<IpAddr>(py).unwrap(), v6);
+    }
+
+    #[test]
+    fn test_extract_ip_addr() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let s = "10.0.0.1".to_object(py);
+        assert_eq!(
+            s.extract::<Ipv4Addr>(py).unwrap(),
+            Ipv4Addr::new(10, 0, 0, 1)
+        );
+        assert!(s.extract::<Ipv6Addr>(py).is_err());
+        let s = "::1".to_object(py);
+        assert_eq!(s.extract::<Ipv6Addr>(py).unwrap(), Ipv6Addr::LOCALHOST);
+
+        assert!("not an address"
+            .to_object(py)
+            .extract::<IpAddr>(py)
+            .is_err());
+        assert!(1.to_object(py).extract::<IpAddr>(py).is_err());
+    }
+
+    #[test]
+    fn test_socket_addr() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let v4: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+        let obj = v4.to_object(py);
+        assert_eq!(
+            obj.extract::<(String, u16)>(py).unwrap(),
+            ("127.0.0.1".to_string(), 8080)
+        );
+        assert_eq!(obj.extract::<SocketAddr>(py).unwrap(), v4);
+
+        let v6: SocketAddr = "[::1]:443".parse().unwrap();
+        let obj = v6.to_object(py);
+        assert_eq!(
+            obj.extract::<(String, u16, u32, u32)>(py).unwrap(),
+            ("::1".to_string(), 443, 0, 0)
+        );
+        assert_eq!(obj.extract::<SocketAddr>(py).unwrap(), v6);
+
+        let obj = ("127.0.0.1", 1, 0, 0).to_object(py);
+        assert!(obj.extract::<SocketAddr>(py).is_err());
+    }
+}
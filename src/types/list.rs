@@ -2,7 +2,7 @@
 //
 // based on Daniel Grunwald's https://github.com/dgrunwald/rust-cpython
 
-use crate::err::{self, PyResult};
+use crate::err::{self, PyErr, PyResult};
 use crate::ffi::{self, Py_ssize_t};
 use crate::instance::PyNativeType;
 use crate::internal_tricks::Unsendable;
@@ -10,8 +10,9 @@ use crate::object::PyObject;
 use crate::types::PyAny;
 use crate::IntoPyPointer;
 use crate::Python;
-use crate::{AsPyPointer, IntoPy};
+use crate::{AsPyPointer, FromPyObject, IntoPy, ObjectProtocol};
 use crate::{ToBorrowedObject, ToPyObject};
+use std::cmp::Ordering;
 
 /// Represents a Python `list`.
 #[repr(transparent)]
@@ -101,6 +102,71 @@ impl PyList {
         })
     }
 
+    /// Appends all items of a Rust iterator at the end of the list.
+    ///
+    /// The list is grown once for the lower bound of the iterator's `size_hint`,
+    /// any further items are appended one by one.
+    pub fn extend<I, T>(&self, elements: I) -> PyResult<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: ToPyObject,
+    {
+        let py = self.py();
+        let mut elements = elements.into_iter();
+        let (hint, _) = elements.size_hint();
+        if hint > 0 {
+            unsafe {
+                let chunk = py.from_owned_ptr::<PyList>(ffi::PyList_New(hint as Py_ssize_t));
+                let mut filled = 0;
+                for e in elements.by_ref().take(hint) {
+                    let obj = e.to_object(py).into_ptr();
+                    ffi::PyList_SetItem(chunk.as_ptr(), filled, obj);
+                    filled += 1;
+                }
+                // The hint is not trusted, drop the slots that were never filled.
+                if filled < hint as Py_ssize_t {
+                    err::error_on_minusone(
+                        py,
+                        ffi::PyList_SetSlice(
+                            chunk.as_ptr(),
+                            filled,
+                            hint as Py_ssize_t,
+                            std::ptr::null_mut(),
+                        ),
+                    )?;
+                }
+                err::error_on_minusone(
+                    py,
+                    ffi::PyList_SetSlice(
+                        self.as_ptr(),
+                        ffi::PY_SSIZE_T_MAX,
+                        ffi::PY_SSIZE_T_MAX,
+                        chunk.as_ptr(),
+                    ),
+                )?;
+            }
+        }
+        for e in elements {
+            self.append(e)?;
+        }
+        Ok(())
+    }
+
+    /// Appends all items of a Python iterable at the end of the list, like `list.extend`.
+    pub fn append_many(&self, iterable: &PyAny) -> PyResult<()> {
+        unsafe {
+            err::error_on_minusone(
+                self.py(),
+                ffi::PyList_SetSlice(
+                    self.as_ptr(),
+                    ffi::PY_SSIZE_T_MAX,
+                    ffi::PY_SSIZE_T_MAX,
+                    iterable.as_ptr(),
+                ),
+            )
+        }
+    }
+
     /// Inserts an item at the specified index.
     ///
     /// Panics if the index is out of range.
@@ -121,15 +187,121 @@ impl PyList {
         }
     }
 
+    /// Returns an iterator over all contiguous windows of length `size` of this list.
+    ///
+    /// The windows overlap; if the list is shorter than `size`, the iterator yields nothing.
+    ///
+    /// Panics if `size` is 0.
+    pub fn windows(&self, size: usize) -> PyListWindows {
+        assert!(size != 0, "window size must be non-zero");
+        PyListWindows {
+            list: self,
+            size,
+            index: 0,
+        }
+    }
+
+    /// Returns an iterator over non-overlapping chunks of `size` elements of this list,
+    /// starting at the beginning of the list.
+    ///
+    /// The last chunk will be shorter than `size` if the length of the list is not
+    /// divisible by `size`.
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> PyListChunks {
+        assert!(size != 0, "chunk size must be non-zero");
+        PyListChunks {
+            list: self,
+            size,
+            index: 0,
+        }
+    }
+
     /// Sorts the list in-place. Equivalent to python `l.sort()`
     pub fn sort(&self) -> PyResult<()> {
         unsafe { err::error_on_minusone(self.py(), ffi::PyList_Sort(self.as_ptr())) }
     }
 
+    /// Sorts the list in-place with a Rust comparator, like `slice::sort_by`.
+    ///
+    /// All items are extracted as `T` first, so no Python code runs for the comparisons.
+    /// The sort is stable, and the list keeps the original Python objects.
+    pub fn sort_by<'a, T, F>(&'a self, mut compare: F) -> PyResult<()>
+    where
+        T: FromPyObject<'a>,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let py = self.py();
+        // The items are held as owned references, because `set_item` releases the reference
+        // of the list to the item it replaces, which may be written back later.
+        let mut items = self
+            .iter()
+            .map(|item| Ok((item.extract::<T>()?, item.to_object(py))))
+            .collect::<PyResult<Vec<_>>>()?;
+        items.sort_by(|a, b| compare(&a.0, &b.0));
+        for (index, (_, item)) in items.iter().enumerate() {
+            self.set_item(index as isize, item)?;
+        }
+        Ok(())
+    }
+
+    /// Sorts the list in-place with a Rust key function, like `slice::sort_by_key`.
+    ///
+    /// See [PyList::sort_by].
+    pub fn sort_by_key<'a, T, K, F>(&'a self, mut f: F) -> PyResult<()>
+    where
+        T: FromPyObject<'a>,
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a: &T, b: &T| f(a).cmp(&f(b)))
+    }
+
     /// Reverses the list in-place. Equivalent to python `l.reverse()`
     pub fn reverse(&self) -> PyResult<()> {
         unsafe { err::error_on_minusone(self.py(), ffi::PyList_Reverse(self.as_ptr())) }
     }
+
+    /// Removes consecutive repeated elements in-place, comparing them with Python `==`.
+    ///
+    /// If a comparison raises an exception, the error is returned and the list is left
+    /// partially deduplicated.
+    pub fn dedup(&self) -> PyResult<()> {
+        let py = self.py();
+        let mut write = 1;
+        let mut read = 1;
+        while read < self.len() {
+            let item = self.get_item(read as isize);
+            let prev = self.get_item(write as isize - 1);
+            let equal =
+                unsafe { ffi::PyObject_RichCompareBool(item.as_ptr(), prev.as_ptr(), ffi::Py_EQ) };
+            if equal < 0 {
+                return Err(PyErr::fetch(py));
+            }
+            if equal == 0 {
+                if write != read {
+                    self.set_item(write as isize, item)?;
+                }
+                write += 1;
+            }
+            read += 1;
+        }
+        let len = self.len();
+        if write < len {
+            unsafe {
+                err::error_on_minusone(
+                    py,
+                    ffi::PyList_SetSlice(
+                        self.as_ptr(),
+                        write as Py_ssize_t,
+                        len as Py_ssize_t,
+                        std::ptr::null_mut(),
+                    ),
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Used by `PyList::iter()`.
@@ -153,6 +325,54 @@ impl<'a> Iterator for PyListIterator<'a> {
     }
 }
 
+/// Used by `PyList::windows()`.
+pub struct PyListWindows<'a> {
+    list: &'a PyList,
+    size: usize,
+    index: usize,
+}
+
+impl<'a> Iterator for PyListWindows<'a> {
+    type Item = Vec<&'a PyAny>;
+
+    fn next(&mut self) -> Option<Vec<&'a PyAny>> {
+        if self.index + self.size <= self.list.len() {
+            let window = (self.index..self.index + self.size)
+                .map(|i| self.list.get_item(i as isize))
+                .collect();
+            self.index += 1;
+            Some(window)
+        } else {
+            None
+        }
+    }
+}
+
+/// Used by `PyList::chunks()`.
+pub struct PyListChunks<'a> {
+    list: &'a PyList,
+    size: usize,
+    index: usize,
+}
+
+impl<'a> Iterator for PyListChunks<'a> {
+    type Item = Vec<&'a PyAny>;
+
+    fn next(&mut self) -> Option<Vec<&'a PyAny>> {
+        let len = self.list.len();
+        if self.index < len {
+            let end = std::cmp::min(self.index + self.size, len);
+            let chunk = (self.index..end)
+                .map(|i| self.list.get_item(i as isize))
+                .collect();
+            self.index = end;
+            Some(chunk)
+        } else {
+            None
+        }
+    }
+}
+
 impl<'a> std::iter::IntoIterator for &'a PyList {
     type Item = &'a PyAny;
     type IntoIter = PyListIterator<'a>;
@@ -205,11 +425,12 @@ where
 
 #[cfg(test)]
 mod test {
+    use crate::exceptions::ZeroDivisionError;
     use crate::instance::AsPyRef;
     use crate::objectprotocol::ObjectProtocol;
     use crate::types::PyList;
     use crate::Python;
-    use crate::{PyTryFrom, ToPyObject};
+    use crate::{AsPyPointer, PyTryFrom, ToPyObject};
 
     #[test]
     fn test_new() {
@@ -336,6 +557,59 @@ mod test {
         assert_eq!(3, list.get_item(1).extract::<i32>().unwrap());
     }
 
+    #[test]
+    fn test_extend() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[1]);
+        list.extend((2..5).map(|i| i * 2)).unwrap();
+        list.extend(Vec::<i32>::new()).unwrap();
+        assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![1, 4, 6, 8]);
+        // Only the first two items are covered by the size hint.
+        list.extend(
+            vec![10, 12]
+                .into_iter()
+                .chain((7..9).filter(|i| i % 2 == 0)),
+        )
+        .unwrap();
+        assert_eq!(
+            list.extract::<Vec<i32>>().unwrap(),
+            vec![1, 4, 6, 8, 10, 12, 8]
+        );
+    }
+
+    #[test]
+    fn test_extend_overstated_size_hint() {
+        struct Overstated(Option<i32>);
+        impl Iterator for Overstated {
+            type Item = i32;
+            fn next(&mut self) -> Option<i32> {
+                self.0.take()
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (3, None)
+            }
+        }
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[1]);
+        list.extend(Overstated(Some(2))).unwrap();
+        assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_append_many() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[1]);
+        list.append_many(py.eval("range(2, 4)", None, None).unwrap())
+            .unwrap();
+        list.append_many(PyList::new(py, &[4]).as_ref()).unwrap();
+        assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![1, 2, 3, 4]);
+        assert!(list.append_many(py.eval("1", None, None).unwrap()).is_err());
+    }
+
     #[test]
     fn test_append_refcnt() {
         let gil = Python::acquire_gil();
@@ -407,6 +681,36 @@ mod test {
         assert_eq!(7, list.get_item(3).extract::<i32>().unwrap());
     }
 
+    #[test]
+    fn test_sort_by() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[7, 3, 2, 5]);
+        let first = list.get_item(0).as_ptr();
+        list.sort_by(|a: &i32, b: &i32| b.cmp(a)).unwrap();
+        assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![7, 5, 3, 2]);
+        assert_eq!(list.get_item(0).as_ptr(), first);
+
+        let list = PyList::new(py, &["ccc", "a", "bb", "d"]);
+        list.sort_by_key(|s: &String| s.len()).unwrap();
+        assert_eq!(
+            list.extract::<Vec<String>>().unwrap(),
+            vec!["a", "d", "bb", "ccc"]
+        );
+
+        // Strings that are only referenced by the list, unlike small ints which are cached
+        let strings: Vec<String> = (0..4).rev().map(|i| i.to_string().repeat(1000)).collect();
+        let list = PyList::new(py, &strings);
+        list.sort_by(|a: &String, b: &String| a.cmp(b)).unwrap();
+        let mut sorted = strings.clone();
+        sorted.sort();
+        assert_eq!(list.extract::<Vec<String>>().unwrap(), sorted);
+
+        let list = PyList::new(py, &[1.to_object(py), "a".to_object(py)]);
+        assert!(list.sort_by(|a: &i32, b: &i32| a.cmp(b)).is_err());
+        assert_eq!(list.get_item(0).extract::<i32>().unwrap(), 1);
+    }
+
     #[test]
     fn test_reverse() {
         let gil = Python::acquire_gil();
@@ -423,4 +727,61 @@ mod test {
         assert_eq!(3, list.get_item(2).extract::<i32>().unwrap());
         assert_eq!(2, list.get_item(3).extract::<i32>().unwrap());
     }
+
+    #[test]
+    fn test_windows() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[1, 2, 3, 4]);
+        let windows: Vec<Vec<i32>> = list
+            .windows(3)
+            .map(|w| w.iter().map(|x| x.extract().unwrap()).collect())
+            .collect();
+        assert_eq!(windows, vec![vec![1, 2, 3], vec![2, 3, 4]]);
+        assert_eq!(list.windows(5).count(), 0);
+    }
+
+    #[test]
+    fn test_chunks() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[1, 2, 3, 4, 5]);
+        let chunks: Vec<Vec<i32>> = list
+            .chunks(2)
+            .map(|c| c.iter().map(|x| x.extract().unwrap()).collect())
+            .collect();
+        assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(PyList::empty(py).chunks(2).count(), 0);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[1, 1, 2, 3, 3, 3, 1, 2, 2]);
+        list.dedup().unwrap();
+        assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![1, 2, 3, 1, 2]);
+
+        let list = PyList::empty(py);
+        list.dedup().unwrap();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_dedup_eq_raises() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list: &PyList = py
+            .eval(
+                "[type('BadEq', (), {'__eq__': lambda a, b: 1 / 0})() for _ in range(2)]",
+                None,
+                None,
+            )
+            .unwrap()
+            .downcast_ref()
+            .unwrap();
+        let err = list.dedup().unwrap_err();
+        assert!(err.is_instance::<ZeroDivisionError>(py));
+        assert_eq!(list.len(), 2);
+    }
 }
@@ -0,0 +1,165 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::buffer::{ElementType, PyBuffer};
+use crate::err::PyResult;
+use crate::exceptions;
+use crate::ffi;
+use crate::instance::PyNativeType;
+use crate::internal_tricks::Unsendable;
+use crate::object::PyObject;
+use crate::types::PyAny;
+use crate::AsPyPointer;
+use crate::Python;
+use std::slice;
+
+/// Represents a Python `memoryview`, a view of the memory of an object that supports the
+/// buffer protocol, like `bytes`, `bytearray`, `array.array` or numpy arrays.
+#[repr(transparent)]
+pub struct PyMemoryView(PyObject, Unsendable);
+
+pyobject_native_var_type!(
+    PyMemoryView,
+    ffi::PyMemoryView_Type,
+    ffi::PyMemoryView_Check
+);
+
+impl PyMemoryView {
+    /// Creates a new memoryview of `obj`, which must support the buffer protocol.
+    ///
+    /// This is equivalent to the Python expression `memoryview(obj)`.
+    pub fn from_object<'p>(py: Python<'p>, obj: &PyAny) -> PyResult<&'p PyMemoryView> {
+        unsafe { py.from_owned_ptr_or_err(ffi::PyMemoryView_FromObject(obj.as_ptr())) }
+    }
+
+    /// Returns the contents of the view as a byte slice.
+    ///
+    /// The view must be C-contiguous and its format must be a single byte (`'B'`, `'b'` or `'c'`),
+    /// otherwise a `BufferError` is returned. Views of other types can be cast with
+    /// `memoryview.cast('B')` first.
+    ///
+    /// # Safety
+    ///
+    /// The memory may be shared with other Python objects. It must not be mutated, e.g. by
+    /// running Python code, while the returned slice is alive.
+    pub unsafe fn as_bytes(&self) -> PyResult<&[u8]> {
+        let buffer = self.byte_buffer()?;
+        Ok(slice::from_raw_parts(
+            buffer.buf_ptr() as *const u8,
+            buffer.len_bytes(),
+        ))
+    }
+
+    /// Returns the contents of the view as a mutable byte slice.
+    ///
+    /// Returns a `BufferError` if the view is read-only, and otherwise has the same
+    /// requirements as [PyMemoryView::as_bytes].
+    ///
+    /// # Safety
+    ///
+    /// The memory may be shared with other Python objects. It must not be accessed by anything
+    /// else, including other slices returned by this method, while the returned slice is alive.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_bytes_mut(&self) -> PyResult<&mut [u8]> {
+        let buffer = self.byte_buffer()?;
+        if buffer.readonly() {
+            return Err(exceptions::BufferError::py_err("memoryview is read-only"));
+        }
+        Ok(slice::from_raw_parts_mut(
+            buffer.buf_ptr() as *mut u8,
+            buffer.len_bytes(),
+        ))
+    }
+
+    /// Copies the contents of the view into a new `Vec<u8>`.
+    ///
+    /// Has the same requirements for the view as [PyMemoryView::as_bytes].
+    pub fn to_vec(&self) -> PyResult<Vec<u8>> {
+        unsafe { self.as_bytes().map(<[u8]>::to_vec) }
+    }
+
+    /// Gets the buffer of the view and checks that it is a contiguous sequence of bytes.
+    ///
+    /// The buffer is taken from a new memoryview that lives in the GIL pool, like `self`.
+    /// That keeps the memory exported for the lifetime of `&self`, even if `self` is released
+    /// from Python.
+    fn byte_buffer(&self) -> PyResult<PyBuffer> {
+        let py = self.py();
+        let view: &PyAny =
+            unsafe { py.from_owned_ptr_or_err(ffi::PyMemoryView_FromObject(self.as_ptr()))? };
+        let buffer = PyBuffer::get(py, view)?;
+        let is_byte = match ElementType::from_format(buffer.format()) {
+            ElementType::UnsignedInteger { bytes } | ElementType::SignedInteger { bytes } => {
+                bytes == 1
+            }
+            _ => false,
+        };
+        if !is_byte {
+            return Err(exceptions::BufferError::py_err(format!(
+                "memoryview format '{}' is not a byte format",
+                buffer.format().to_string_lossy()
+            )));
+        }
+        if !buffer.is_c_contiguous() {
+            return Err(exceptions::BufferError::py_err(
+                "memoryview is not C-contiguous",
+            ));
+        }
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::exceptions;
+    use crate::types::{PyByteArray, PyBytes, PyMemoryView};
+    use crate::{AsPyRef, ObjectProtocol, Python};
+
+    #[test]
+    fn test_bytes() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let bytes = PyBytes::new(py, b"abc");
+        let view = PyMemoryView::from_object(py, bytes.as_ref()).unwrap();
+        assert_eq!(unsafe { view.as_bytes() }.unwrap(), b"abc");
+        assert_eq!(view.to_vec().unwrap(), b"abc".to_vec());
+
+        let err = unsafe { view.as_bytes_mut() }.unwrap_err();
+        assert!(err.is_instance::<exceptions::BufferError>(py));
+    }
+
+    #[test]
+    fn test_bytearray_mut() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let bytearray = PyByteArray::new(py, b"abc");
+        let view = PyMemoryView::from_object(py, bytearray.as_ref()).unwrap();
+        unsafe { view.as_bytes_mut() }.unwrap()[0] = b'x';
+        assert_eq!(bytearray.to_vec(), b"xbc".to_vec());
+
+        // The slice keeps the memory exported even if the view is released
+        let slice = unsafe { view.as_bytes() }.unwrap();
+        view.call_method0("release").unwrap();
+        assert!(bytearray.resize(10).is_err());
+        assert_eq!(slice, b"xbc");
+    }
+
+    #[test]
+    fn test_format() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let array = py
+            .eval("__import__('array').array('i', [1, 2])", None, None)
+            .unwrap();
+        let view = PyMemoryView::from_object(py, array).unwrap();
+        let err = view.to_vec().unwrap_err();
+        assert!(err.is_instance::<exceptions::BufferError>(py));
+
+        let cast = view.call_method1("cast", ("B",)).unwrap();
+        let cast = cast.downcast_ref::<PyMemoryView>().unwrap();
+        assert_eq!(cast.to_vec().unwrap().len(), 2 * std::mem::size_of::<i32>());
+
+        assert!(PyMemoryView::from_object(py, py.None().as_ref(py)).is_err());
+    }
+}
@@ -6,6 +6,7 @@ pub use self::any::PyAny;
 pub use self::boolobject::PyBool;
 pub use self::bytearray::PyByteArray;
 pub use self::bytes::PyBytes;
+pub use self::capsule::PyCapsule;
 pub use self::complex::PyComplex;
 pub use self::datetime::PyDeltaAccess;
 pub use self::datetime::{
@@ -13,8 +14,10 @@ pub use self::datetime::{
 };
 pub use self::dict::{IntoPyDict, PyDict};
 pub use self::floatob::PyFloat;
+pub use self::function::PyCFunction;
 pub use self::iterator::PyIterator;
 pub use self::list::PyList;
+pub use self::memoryview::PyMemoryView;
 pub use self::module::PyModule;
 pub use self::num::PyLong;
 pub use self::num::PyLong as PyInt;
@@ -22,6 +25,8 @@ pub use self::sequence::PySequence;
 pub use self::set::{PyFrozenSet, PySet};
 pub use self::slice::{PySlice, PySliceIndices};
 pub use self::string::{PyString, PyString as PyUnicode};
+pub use self::structseq::PyStructSequenceType;
+pub use self::traceback::PyTraceback;
 pub use self::tuple::PyTuple;
 pub use self::typeobject::PyType;
 
@@ -37,6 +42,12 @@ macro_rules! pyobject_native_type_named (
 
         unsafe impl<$($type_param,)*> $crate::PyNativeType for $name {}
 
+        unsafe impl<$($type_param,)*> $crate::type_object::PyDowncastImpl for $name {
+            unsafe fn unchecked_downcast(obj: &$crate::types::PyAny) -> &Self {
+                &*(obj as *const $crate::types::PyAny as *const Self)
+            }
+        }
+
         impl<$($type_param,)*> $crate::AsPyPointer for $name {
             /// Gets the underlying FFI pointer, returns a borrowed pointer.
             #[inline]
@@ -119,6 +130,7 @@ macro_rules! pyobject_native_type_convert(
             type BaseType = $crate::types::PyAny;
             type ConcreteLayout = $layout;
             type Initializer = $crate::pyclass_init::PyNativeTypeInitializer<Self>;
+            type AsRefTarget = Self;
 
             const NAME: &'static str = stringify!($name);
             const MODULE: Option<&'static str> = $module;
@@ -170,17 +182,24 @@ mod any;
 mod boolobject;
 mod bytearray;
 mod bytes;
+mod capsule;
 mod complex;
 mod datetime;
 mod dict;
 mod floatob;
+mod function;
+mod ipaddr;
 mod iterator;
 mod list;
+mod memoryview;
 mod module;
 mod num;
+mod osstr;
 mod sequence;
 mod set;
 mod slice;
 mod string;
+mod structseq;
+mod traceback;
 mod tuple;
 mod typeobject;
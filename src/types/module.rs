@@ -2,6 +2,7 @@
 //
 // based on Daniel Grunwald's https://github.com/dgrunwald/rust-cpython
 
+use crate::class::methods::PyMethodDef;
 use crate::err::{PyErr, PyResult};
 use crate::exceptions;
 use crate::ffi;
@@ -174,6 +175,25 @@ impl PyModule {
         self.add(T::NAME, <T as PyTypeObject>::type_object())
     }
 
+    /// Adds a function described by `def` to the module, using `def.ml_name` as its name.
+    ///
+    /// The function's `__module__` is set to this module's name.
+    pub fn add_fn(&self, def: &PyMethodDef) -> PyResult<()> {
+        let py = self.py();
+        let module_name = self.name()?.to_object(py);
+        let function = unsafe {
+            PyObject::from_owned_ptr_or_err(
+                py,
+                ffi::PyCFunction_NewEx(
+                    Box::into_raw(Box::new(def.as_method_def())),
+                    std::ptr::null_mut(),
+                    module_name.as_ptr(),
+                ),
+            )?
+        };
+        self.add(def.ml_name, function)
+    }
+
     /// Adds a function or a (sub)module to a module, using the functions __name__ as name.
     ///
     /// Use this together with the`#[pyfunction]` and [wrap_pyfunction!] or `#[pymodule]` and
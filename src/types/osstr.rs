@@ -0,0 +1,229 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use crate::conversion::{FromPyObject, PyTryFrom, ToPyObject};
+use crate::err::PyResult;
+use crate::instance::PyNativeType;
+use crate::object::PyObject;
+use crate::types::{PyAny, PyBytes, PyString};
+use crate::{ffi, AsPyPointer, FromPy, IntoPy, Python};
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+/// Converts Rust `OsStr` to a Python `str`.
+///
+/// On Unix, the bytes are decoded like `os.fsdecode`, using the file system encoding and the
+/// `surrogateescape` error handler, so that every path round-trips.
+/// On other platforms, invalid Unicode is replaced with U+FFFD REPLACEMENT CHARACTER.
+impl ToPyObject for OsStr {
+    fn to_object(&self, py: Python) -> PyObject {
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let bytes = self.as_bytes();
+            unsafe {
+                PyObject::from_owned_ptr_or_panic(
+                    py,
+                    ffi::PyUnicode_DecodeFSDefaultAndSize(
+                        bytes.as_ptr() as *const std::os::raw::c_char,
+                        bytes.len() as ffi::Py_ssize_t,
+                    ),
+                )
+            }
+        }
+
+        #[cfg(not(unix))]
+        {
+            PyString::new(py, &self.to_string_lossy()).into()
+        }
+    }
+}
+
+impl<'a> IntoPy<PyObject> for &'a OsStr {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+/// Converts Rust `OsString` to a Python `str`.
+/// See `ToPyObject for OsStr` for details on the conversion.
+impl ToPyObject for OsString {
+    #[inline]
+    fn to_object(&self, py: Python) -> PyObject {
+        self.as_os_str().to_object(py)
+    }
+}
+
+impl FromPy<OsString> for PyObject {
+    fn from_py(other: OsString, py: Python) -> Self {
+        other.as_os_str().to_object(py)
+    }
+}
+
+impl<'a> IntoPy<PyObject> for &'a OsString {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.as_os_str().to_object(py)
+    }
+}
+
+/// Allows extracting `OsString` from Python objects.
+///
+/// Accepts Python `str`, `bytes` and `os.PathLike` objects.
+/// On Unix, `str` is encoded like `os.fsencode` and `bytes` are taken as they are.
+/// On other platforms, both must be valid Unicode, otherwise a `UnicodeDecodeError` is raised.
+impl<'source> FromPyObject<'source> for OsString {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        let path = fspath(ob)?;
+        let bytes = if let Ok(s) = <PyString as PyTryFrom>::try_from(path) {
+            encode_str(s)?
+        } else {
+            <PyBytes as PyTryFrom>::try_from(path)?
+        };
+        os_string_from_bytes(ob.py(), bytes.as_bytes())
+    }
+}
+
+/// Converts Rust `Path` to a Python `str`.
+/// See `ToPyObject for OsStr` for details on the conversion.
+impl ToPyObject for Path {
+    #[inline]
+    fn to_object(&self, py: Python) -> PyObject {
+        self.as_os_str().to_object(py)
+    }
+}
+
+impl<'a> IntoPy<PyObject> for &'a Path {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.as_os_str().to_object(py)
+    }
+}
+
+/// Converts Rust `PathBuf` to a Python `str`.
+/// See `ToPyObject for OsStr` for details on the conversion.
+impl ToPyObject for PathBuf {
+    #[inline]
+    fn to_object(&self, py: Python) -> PyObject {
+        self.as_os_str().to_object(py)
+    }
+}
+
+impl FromPy<PathBuf> for PyObject {
+    fn from_py(other: PathBuf, py: Python) -> Self {
+        other.as_os_str().to_object(py)
+    }
+}
+
+impl<'a> IntoPy<PyObject> for &'a PathBuf {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.as_os_str().to_object(py)
+    }
+}
+
+/// Allows extracting `PathBuf` from Python objects.
+/// See `FromPyObject for OsString` for the accepted types.
+impl<'source> FromPyObject<'source> for PathBuf {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        OsString::extract(ob).map(PathBuf::from)
+    }
+}
+
+/// Returns the file system representation of `ob`, which is a `str` or `bytes`,
+/// like `os.fspath`.
+#[cfg(Py_3_6)]
+fn fspath(ob: &PyAny) -> PyResult<&PyAny> {
+    unsafe { ob.py().from_owned_ptr_or_err(ffi::PyOS_FSPath(ob.as_ptr())) }
+}
+
+/// `os.PathLike` was added in Python 3.6, so only `str` and `bytes` are paths.
+#[cfg(not(Py_3_6))]
+fn fspath(ob: &PyAny) -> PyResult<&PyAny> {
+    Ok(ob)
+}
+
+#[cfg(unix)]
+fn encode_str(s: &PyString) -> PyResult<&PyBytes> {
+    unsafe {
+        s.py()
+            .from_owned_ptr_or_err(ffi::PyUnicode_EncodeFSDefault(s.as_ptr()))
+    }
+}
+
+/// Keeps unpaired surrogates, so that they are reported by `os_string_from_bytes`.
+#[cfg(not(unix))]
+fn encode_str(s: &PyString) -> PyResult<&PyBytes> {
+    s.encode("utf-8", "surrogatepass")
+}
+
+#[cfg(unix)]
+fn os_string_from_bytes(_py: Python, bytes: &[u8]) -> PyResult<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(OsString::from_vec(bytes.to_vec()))
+}
+
+#[cfg(not(unix))]
+fn os_string_from_bytes(py: Python, bytes: &[u8]) -> PyResult<OsString> {
+    use crate::exceptions::UnicodeDecodeError;
+    use crate::PyErr;
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok(OsString::from(s)),
+        Err(e) => Err(PyErr::from_instance(UnicodeDecodeError::new_utf8(
+            py, bytes, e,
+        )?)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Python;
+    use crate::{ObjectProtocol, ToPyObject};
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_path_roundtrip() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let path = PathBuf::from("/tmp/caf\u{e9}.txt");
+        let obj = path.to_object(py);
+        assert_eq!(obj.extract::<String>(py).unwrap(), "/tmp/caf\u{e9}.txt");
+        assert_eq!(obj.extract::<PathBuf>(py).unwrap(), path);
+    }
+
+    #[test]
+    #[cfg(Py_3_6)]
+    fn test_extract_path_like() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py
+            .eval("__import__('pathlib').PurePosixPath('a/b')", None, None)
+            .unwrap();
+        assert_eq!(obj.extract::<PathBuf>().unwrap(), PathBuf::from("a/b"));
+    }
+
+    #[test]
+    fn test_extract_invalid() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = 1.to_object(py);
+        assert!(obj.extract::<PathBuf>(py).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_non_utf8_roundtrip() {
+        use crate::types::PyBytes;
+        use std::os::unix::ffi::OsStringExt;
+
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let os_string = OsString::from_vec(b"caf\xe9".to_vec());
+        let obj = os_string.to_object(py);
+        assert_eq!(obj.extract::<OsString>(py).unwrap(), os_string);
+
+        let bytes = PyBytes::new(py, b"caf\xe9");
+        assert_eq!(bytes.extract::<OsString>().unwrap(), os_string);
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
 use crate::buffer;
-use crate::err::{self, PyDowncastError, PyErr, PyResult};
+use crate::err::{self, extract_item, PyDowncastError, PyErr, PyResult};
+use crate::exceptions;
 use crate::ffi::{self, Py_ssize_t};
 use crate::instance::PyNativeType;
 use crate::internal_tricks::Unsendable;
@@ -277,11 +278,64 @@ where
     let seq = <PySequence as PyTryFrom>::try_from(obj)?;
     let mut v = Vec::with_capacity(seq.len().unwrap_or(0) as usize);
     for item in seq.iter()? {
-        v.push(item?.extract::<T>()?);
+        v.push(extract_item(item?, "sequence item")?);
     }
     Ok(v)
 }
 
+/// Extracts exactly `len` items of `seq` into `out`, dropping the items written so far on error.
+unsafe fn extract_array<'s, T>(obj: &'s PyAny, out: *mut T, len: usize) -> PyResult<()>
+where
+    T: FromPyObject<'s>,
+{
+    let seq = <PySequence as PyTryFrom>::try_from(obj)?;
+    let seq_len = seq.len()? as usize;
+    if seq_len != len {
+        return Err(exceptions::ValueError::py_err(format!(
+            "expected a sequence of length {}, got {}",
+            len, seq_len
+        )));
+    }
+    for i in 0..len {
+        match seq
+            .get_item(i as isize)
+            .and_then(|item| extract_item(item, "sequence item"))
+        {
+            Ok(item) => std::ptr::write(out.add(i), item),
+            Err(e) => {
+                for j in 0..i {
+                    std::ptr::drop_in_place(out.add(j));
+                }
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+macro_rules! array_impls {
+    ($($n:expr),*) => {$(
+        /// Extracts a Python sequence of exactly this length, raising `ValueError` otherwise.
+        impl<'a, T> FromPyObject<'a> for [T; $n]
+        where
+            T: FromPyObject<'a>,
+        {
+            fn extract(obj: &'a PyAny) -> PyResult<Self> {
+                let mut array = std::mem::MaybeUninit::<[T; $n]>::uninit();
+                unsafe {
+                    extract_array(obj, array.as_mut_ptr() as *mut T, $n)?;
+                    Ok(array.assume_init())
+                }
+            }
+        }
+    )*}
+}
+
+array_impls!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31, 32
+);
+
 impl<'v> PyTryFrom<'v> for PySequence {
     fn try_from<V: Into<&'v PyAny>>(value: V) -> Result<&'v PySequence, PyDowncastError> {
         let value = value.into();
@@ -330,6 +384,7 @@ impl<'v> PyTryFrom<'v> for PySequence {
 
 #[cfg(test)]
 mod test {
+    use crate::err::error_message;
     use crate::instance::AsPyRef;
     use crate::object::PyObject;
     use crate::objectprotocol::ObjectProtocol;
@@ -676,4 +731,42 @@ mod test {
         let empty_seq = empty_list.cast_as::<PySequence>(py).unwrap();
         assert_eq!(empty_seq.is_empty().unwrap(), true);
     }
+
+    #[test]
+    fn test_extract_array() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let list = vec![1, 2, 3].to_object(py);
+        let array: [i32; 3] = list.extract(py).unwrap();
+        assert_eq!(array, [1, 2, 3]);
+
+        let err = list.extract::<[i32; 2]>(py).unwrap_err();
+        assert!(err.is_instance::<crate::exceptions::ValueError>(py));
+
+        let mixed = py.eval("('a', 1)", None, None).unwrap();
+        assert!(mixed.extract::<[String; 2]>().is_err());
+        let empty: [String; 0] = py.eval("()", None, None).unwrap().extract().unwrap();
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn test_extract_nested_vec_err() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let nested = py.eval("[[1], [2, 'x']]", None, None).unwrap();
+        let mut err = nested.extract::<Vec<Vec<i32>>>().unwrap_err();
+        assert!(err.is_instance::<crate::exceptions::TypeError>(py));
+        // The outermost item is at the end of the chain, which Python prints first
+        let mut inner = err.cause(py).unwrap();
+        assert_eq!(
+            error_message(py, inner.cause(py).unwrap()),
+            "TypeError: failed to extract sequence item [2, 'x']"
+        );
+        assert_eq!(
+            error_message(py, inner),
+            "TypeError: failed to extract sequence item 'x'"
+        );
+    }
 }
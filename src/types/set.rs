@@ -1,15 +1,17 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 //
 
-use crate::err::{self, PyErr, PyResult};
+use crate::err::{self, extract_item, PyErr, PyResult};
 use crate::ffi;
-use crate::instance::PyNativeType;
+use crate::instance::{Py, PyNativeType};
 use crate::internal_tricks::Unsendable;
 use crate::object::PyObject;
 use crate::types::PyAny;
 use crate::AsPyPointer;
 use crate::Python;
+use crate::{FromPyObject, ObjectProtocol, PyTryFrom};
 use crate::{ToBorrowedObject, ToPyObject};
+use std::collections::BTreeSet;
 use std::ptr;
 use std::{collections, hash};
 
@@ -106,6 +108,54 @@ impl PySet {
         }
     }
 
+    /// Returns a new set with the elements of both sets.
+    /// This is equivalent to the Python expression: `self | other`.
+    pub fn union(&self, other: &PySet) -> PyResult<Py<PySet>> {
+        self.binary_op(other, ffi::PyNumber_Or)
+    }
+
+    /// Returns a new set with the elements common to both sets.
+    /// This is equivalent to the Python expression: `self & other`.
+    pub fn intersection(&self, other: &PySet) -> PyResult<Py<PySet>> {
+        self.binary_op(other, ffi::PyNumber_And)
+    }
+
+    /// Returns a new set with the elements of `self` that are not in `other`.
+    /// This is equivalent to the Python expression: `self - other`.
+    pub fn difference(&self, other: &PySet) -> PyResult<Py<PySet>> {
+        self.binary_op(other, ffi::PyNumber_Subtract)
+    }
+
+    /// Returns a new set with the elements that are in exactly one of the sets.
+    /// This is equivalent to the Python expression: `self ^ other`.
+    pub fn symmetric_difference(&self, other: &PySet) -> PyResult<Py<PySet>> {
+        self.binary_op(other, ffi::PyNumber_Xor)
+    }
+
+    /// Adds the elements of `other` to this set.
+    /// This is equivalent to the Python statement: `self |= other`.
+    pub fn update(&self, other: &PySet) -> PyResult<()> {
+        self.inplace_op(other, ffi::PyNumber_InPlaceOr)
+    }
+
+    /// Keeps only the elements that are also in `other`.
+    /// This is equivalent to the Python statement: `self &= other`.
+    pub fn intersection_update(&self, other: &PySet) -> PyResult<()> {
+        self.inplace_op(other, ffi::PyNumber_InPlaceAnd)
+    }
+
+    /// Removes the elements that are in `other`.
+    /// This is equivalent to the Python statement: `self -= other`.
+    pub fn difference_update(&self, other: &PySet) -> PyResult<()> {
+        self.inplace_op(other, ffi::PyNumber_InPlaceSubtract)
+    }
+
+    /// Keeps only the elements that are in exactly one of the sets.
+    /// This is equivalent to the Python statement: `self ^= other`.
+    pub fn symmetric_difference_update(&self, other: &PySet) -> PyResult<()> {
+        self.inplace_op(other, ffi::PyNumber_InPlaceXor)
+    }
+
     /// Returns an iterator of values in this set.
     ///
     /// Note that it can be unsafe to use when the set might be changed by other code.
@@ -116,6 +166,26 @@ impl PySet {
             pos: 0,
         }
     }
+
+    fn binary_op(
+        &self,
+        other: &PySet,
+        op: unsafe extern "C" fn(*mut ffi::PyObject, *mut ffi::PyObject) -> *mut ffi::PyObject,
+    ) -> PyResult<Py<PySet>> {
+        unsafe { Py::from_owned_ptr_or_err(self.py(), op(self.as_ptr(), other.as_ptr())) }
+    }
+
+    fn inplace_op(
+        &self,
+        other: &PySet,
+        op: unsafe extern "C" fn(*mut ffi::PyObject, *mut ffi::PyObject) -> *mut ffi::PyObject,
+    ) -> PyResult<()> {
+        // For sets, the in-place operators modify `self` and return a new reference to it
+        unsafe {
+            PyObject::from_owned_ptr_or_err(self.py(), op(self.as_ptr(), other.as_ptr()))?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(not(Py_LIMITED_API))]
@@ -245,12 +315,46 @@ impl<'a> std::iter::IntoIterator for &'a PyFrozenSet {
     }
 }
 
+impl<'source, T, S> FromPyObject<'source> for collections::HashSet<T, S>
+where
+    T: FromPyObject<'source> + hash::Hash + Eq,
+    S: hash::BuildHasher + Default,
+{
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if <PySet as PyTryFrom>::try_from(ob).is_err() {
+            <PyFrozenSet as PyTryFrom>::try_from(ob)?;
+        }
+        let mut ret = collections::HashSet::default();
+        for item in ob.iter()? {
+            ret.insert(extract_item(item?, "set item")?);
+        }
+        Ok(ret)
+    }
+}
+
+impl<'source, T> FromPyObject<'source> for BTreeSet<T>
+where
+    T: FromPyObject<'source> + Ord,
+{
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if <PySet as PyTryFrom>::try_from(ob).is_err() {
+            <PyFrozenSet as PyTryFrom>::try_from(ob)?;
+        }
+        let mut ret = BTreeSet::new();
+        for item in ob.iter()? {
+            ret.insert(extract_item(item?, "set item")?);
+        }
+        Ok(ret)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{PyFrozenSet, PySet};
-    use crate::instance::AsPyRef;
+    use crate::err::error_message;
+    use crate::instance::{AsPyRef, Py};
     use crate::{ObjectProtocol, PyTryFrom, Python, ToPyObject};
-    use std::collections::HashSet;
+    use std::collections::{BTreeSet, HashSet};
 
     #[test]
     fn test_set_new() {
@@ -402,4 +506,79 @@ mod test {
             assert_eq!(1i32, el.extract::<i32>().unwrap());
         }
     }
+
+    #[test]
+    fn test_extract_btreeset() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let expected: BTreeSet<i32> = [1, 2, 3].iter().cloned().collect();
+        let set = PySet::new(py, &[3, 1, 2]).unwrap();
+        assert_eq!(set.extract::<BTreeSet<i32>>().unwrap(), expected);
+        let frozenset = PyFrozenSet::new(py, &[3, 1, 2]).unwrap();
+        assert_eq!(frozenset.extract::<BTreeSet<i32>>().unwrap(), expected);
+
+        let list = vec![1, 2, 3].to_object(py);
+        assert!(list.extract::<BTreeSet<i32>>(py).is_err());
+
+        let set = PySet::new(py, &["a"]).unwrap();
+        let mut err = set.extract::<BTreeSet<i32>>().unwrap_err();
+        assert_eq!(
+            error_message(py, err.cause(py).unwrap()),
+            "TypeError: failed to extract set item 'a'"
+        );
+    }
+
+    #[test]
+    fn test_extract_hashset() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let expected: HashSet<i32> = [1, 2, 3].iter().cloned().collect();
+        let set = PySet::new(py, &[3, 1, 2]).unwrap();
+        assert_eq!(set.extract::<HashSet<i32>>().unwrap(), expected);
+        let frozenset = PyFrozenSet::new(py, &[3, 1, 2]).unwrap();
+        assert_eq!(frozenset.extract::<HashSet<i32>>().unwrap(), expected);
+
+        let set = PySet::new(py, &["a"]).unwrap();
+        let mut err = set.extract::<HashSet<i32>>().unwrap_err();
+        assert_eq!(
+            error_message(py, err.cause(py).unwrap()),
+            "TypeError: failed to extract set item 'a'"
+        );
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let a = PySet::new(py, &[1, 2, 3]).unwrap();
+        let b = PySet::new(py, &[3, 4]).unwrap();
+        let sorted = |set: Py<PySet>| {
+            let mut v: Vec<i32> = set
+                .as_ref(py)
+                .iter()
+                .map(|x| x.extract().unwrap())
+                .collect();
+            v.sort();
+            v
+        };
+
+        assert_eq!(sorted(a.union(b).unwrap()), vec![1, 2, 3, 4]);
+        assert_eq!(sorted(a.intersection(b).unwrap()), vec![3]);
+        assert_eq!(sorted(a.difference(b).unwrap()), vec![1, 2]);
+        assert_eq!(sorted(a.symmetric_difference(b).unwrap()), vec![1, 2, 4]);
+        assert_eq!(a.len(), 3);
+
+        a.update(b).unwrap();
+        assert_eq!(sorted(a.into()), vec![1, 2, 3, 4]);
+        a.difference_update(b).unwrap();
+        assert_eq!(sorted(a.into()), vec![1, 2]);
+        a.symmetric_difference_update(PySet::new(py, &[2, 5]).unwrap())
+            .unwrap();
+        assert_eq!(sorted(a.into()), vec![1, 5]);
+        a.intersection_update(PySet::new(py, &[5]).unwrap())
+            .unwrap();
+        assert_eq!(sorted(a.into()), vec![5]);
+    }
 }
@@ -7,13 +7,13 @@ use crate::gil;
 use crate::instance::PyNativeType;
 use crate::internal_tricks::Unsendable;
 use crate::object::PyObject;
-use crate::types::PyAny;
+use crate::types::{PyAny, PyBytes};
 use crate::AsPyPointer;
 use crate::IntoPy;
 use crate::Python;
 use crate::{ffi, FromPy};
 use std::borrow::Cow;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::ptr::NonNull;
 use std::str;
@@ -36,6 +36,31 @@ impl PyString {
         unsafe { py.from_owned_ptr(ffi::PyUnicode_FromStringAndSize(ptr, len)) }
     }
 
+    /// Creates an interned Python string object, like `sys.intern(s)` in Python.
+    ///
+    /// Equal interned strings are the same object, so they compare (and are looked up
+    /// as dictionary keys) by pointer.
+    ///
+    /// Panics if out of memory.
+    pub fn intern<'p>(py: Python<'p>, s: &str) -> &'p PyString {
+        let ptr = s.as_ptr() as *const c_char;
+        let len = s.len() as ffi::Py_ssize_t;
+        unsafe {
+            let mut string = ffi::PyUnicode_FromStringAndSize(ptr, len);
+            if !string.is_null() {
+                // Replaces the reference with one to the interned string
+                ffi::PyUnicode_InternInPlace(&mut string);
+            }
+            py.from_owned_ptr(string)
+        }
+    }
+
+    /// Returns whether the string is interned.
+    #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+    pub fn is_interned(&self) -> bool {
+        unsafe { ffi::PyUnicode_CHECK_INTERNED(self.as_ptr()) != ffi::SSTATE_NOT_INTERNED }
+    }
+
     pub fn from_object<'p>(src: &'p PyAny, encoding: &str, errors: &str) -> PyResult<&'p PyString> {
         unsafe {
             src.py()
@@ -64,11 +89,38 @@ impl PyString {
         }
     }
 
+    /// Encodes the string using the codec registered for `encoding`.
+    ///
+    /// `errors` selects the error handling scheme, e.g. `"strict"` or `"replace"`.
+    ///
+    /// This is equivalent to the Python expression: `self.encode(encoding, errors)`.
+    pub fn encode(&self, encoding: &str, errors: &str) -> PyResult<&PyBytes> {
+        let encoding = CString::new(encoding)?;
+        let errors = CString::new(errors)?;
+        unsafe {
+            self.py()
+                .from_owned_ptr_or_err(ffi::PyUnicode_AsEncodedString(
+                    self.as_ptr(),
+                    encoding.as_ptr(),
+                    errors.as_ptr(),
+                ))
+        }
+    }
+
+    /// Gets the Python string as a Rust string slice, without copying it.
+    ///
+    /// The UTF-8 representation is cached by the Python string, so the slice lives as long as
+    /// the `PyString` reference.
+    /// Returns a `UnicodeEncodeError` if the input is not valid unicode
+    /// (containing unpaired surrogates); see [PyString::to_string_lossy] for that case.
+    pub fn to_str(&self) -> PyResult<&str> {
+        let bytes = self.as_bytes()?;
+        Ok(std::str::from_utf8(bytes)?)
+    }
+
     /// Convert the `PyString` into a Rust string.
     pub fn to_string(&self) -> PyResult<Cow<str>> {
-        let bytes = self.as_bytes()?;
-        let string = std::str::from_utf8(bytes)?;
-        Ok(Cow::Borrowed(string))
+        self.to_str().map(Cow::Borrowed)
     }
 
     /// Convert the `PyString` into a Rust string.
@@ -161,14 +213,7 @@ impl<'source> crate::FromPyObject<'source> for Cow<'source, str> {
 /// Accepts Python `str` and `unicode` objects.
 impl<'a> crate::FromPyObject<'a> for &'a str {
     fn extract(ob: &'a PyAny) -> PyResult<Self> {
-        let s: Cow<'a, str> = crate::FromPyObject::extract(ob)?;
-        match s {
-            Cow::Borrowed(r) => Ok(r),
-            Cow::Owned(r) => {
-                let r = ob.py().register_any(r);
-                Ok(r.as_str())
-            }
-        }
+        <PyString as PyTryFrom>::try_from(ob)?.to_str()
     }
 }
 
@@ -188,9 +233,41 @@ mod test {
     use crate::instance::AsPyRef;
     use crate::object::PyObject;
     use crate::Python;
-    use crate::{FromPyObject, PyTryFrom, ToPyObject};
+    use crate::{AsPyPointer, FromPyObject, PyTryFrom, ToPyObject};
     use std::borrow::Cow;
 
+    #[test]
+    fn test_intern() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let a = PyString::intern(py, "interned string");
+        let b = PyString::intern(py, "interned string");
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(a.to_str().unwrap(), "interned string");
+
+        let c = PyString::new(py, "interned string");
+        assert_ne!(a.as_ptr(), c.as_ptr());
+        #[cfg(not(any(Py_LIMITED_API, PyPy)))]
+        {
+            assert!(a.is_interned());
+            assert!(!c.is_interned());
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let s = PyString::new(py, "caf\u{e9}");
+        assert_eq!(
+            s.encode("latin-1", "strict").unwrap().as_bytes(),
+            b"caf\xe9"
+        );
+        assert_eq!(s.encode("ascii", "replace").unwrap().as_bytes(), b"caf?");
+        assert!(s.encode("ascii", "strict").is_err());
+        assert!(s.encode("no-such-codec", "strict").is_err());
+    }
+
     #[test]
     fn test_non_bmp() {
         let gil = Python::acquire_gil();
@@ -252,6 +329,21 @@ mod test {
         assert_eq!(Cow::Borrowed(s), py_string.to_string().unwrap());
     }
 
+    #[test]
+    fn test_to_str() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let py_string = PyString::new(py, "caf\u{e9}");
+        let s = py_string.to_str().unwrap();
+        assert_eq!(s, "caf\u{e9}");
+        // The slice points to the UTF-8 buffer cached by the Python string
+        assert_eq!(s.as_ptr(), py_string.as_bytes().unwrap().as_ptr());
+
+        let obj = py.eval(r#"'\ud800'"#, None, None).unwrap();
+        let py_string = <PyString as PyTryFrom>::try_from(obj).unwrap();
+        assert!(py_string.to_str().is_err());
+    }
+
     #[test]
     fn test_to_string_lossy() {
         let gil = Python::acquire_gil();
@@ -0,0 +1,160 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::err::{PyErr, PyResult};
+use crate::exceptions;
+use crate::ffi;
+use crate::instance::{AsPyRef, Py};
+use crate::object::PyObject;
+use crate::types::PyType;
+use crate::AsPyPointer;
+use crate::IntoPyPointer;
+use crate::Python;
+use std::ffi::CString;
+use std::os::raw::c_int;
+
+/// A struct sequence type, like `os.stat_result` or `sys.version_info`.
+///
+/// Instances of a struct sequence are tuples whose items can also be accessed as named
+/// attributes, similar to the classes created by `collections.namedtuple`.
+pub struct PyStructSequenceType {
+    ty: Py<PyType>,
+    len: usize,
+}
+
+impl PyStructSequenceType {
+    /// Creates a new struct sequence type with the given `(name, doc)` pairs as fields.
+    ///
+    /// `name` can be qualified with the module name, e.g. `"mymodule.Point"`.
+    /// The strings are leaked, since the type object refers to them for its whole lifetime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pyo3::prelude::*;
+    /// use pyo3::types::{IntoPyDict, PyStructSequenceType};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    ///
+    /// let point_type = PyStructSequenceType::new(
+    ///     py,
+    ///     "geometry.Point",
+    ///     "A point in the plane",
+    ///     &[("x", "The x coordinate"), ("y", "The y coordinate")],
+    /// )
+    /// .unwrap();
+    /// let point = point_type
+    ///     .instantiate(py, vec![1.to_object(py), 2.to_object(py)])
+    ///     .unwrap();
+    /// let locals = [("point", point)].into_py_dict(py);
+    /// py.run("assert point == (1, 2) and point.y == 2", None, Some(locals))
+    ///     .unwrap();
+    /// ```
+    pub fn new(
+        py: Python,
+        name: &str,
+        doc: &str,
+        fields: &[(&str, &str)],
+    ) -> PyResult<PyStructSequenceType> {
+        // Check all strings before leaking any of them
+        let name = CString::new(name)?;
+        let doc = CString::new(doc)?;
+        let fields = fields
+            .iter()
+            .map(|&(field_name, field_doc)| {
+                Ok((CString::new(field_name)?, CString::new(field_doc)?))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let len = fields.len();
+
+        let mut field_defs: Vec<_> = fields
+            .into_iter()
+            .map(|(field_name, field_doc)| ffi::PyStructSequence_Field {
+                name: field_name.into_raw(),
+                doc: field_doc.into_raw(),
+            })
+            .collect();
+        field_defs.push(ffi::PyStructSequence_Field {
+            name: std::ptr::null_mut(),
+            doc: std::ptr::null_mut(),
+        });
+
+        let mut desc = ffi::PyStructSequence_Desc {
+            name: name.into_raw(),
+            doc: doc.into_raw(),
+            fields: Box::into_raw(field_defs.into_boxed_slice()) as *mut _,
+            n_in_sequence: len as c_int,
+        };
+        let ty = unsafe {
+            Py::from_owned_ptr_or_err(
+                py,
+                ffi::PyStructSequence_NewType(&mut desc) as *mut ffi::PyObject,
+            )?
+        };
+        Ok(PyStructSequenceType { ty, len })
+    }
+
+    /// Gets the type object of the struct sequence.
+    pub fn as_type<'p>(&self, py: Python<'p>) -> &'p PyType {
+        unsafe { py.from_borrowed_ptr(self.ty.as_ptr()) }
+    }
+
+    /// Creates an instance of the struct sequence with one value for each field.
+    ///
+    /// Returns a `ValueError` if the number of values differs from the number of fields.
+    pub fn instantiate(
+        &self,
+        py: Python,
+        values: impl IntoIterator<Item = PyObject>,
+    ) -> PyResult<PyObject> {
+        let values: Vec<PyObject> = values.into_iter().collect();
+        if values.len() != self.len {
+            return Err(exceptions::ValueError::py_err(format!(
+                "{} expects {} values, got {}",
+                self.ty.as_ref(py).name(),
+                self.len,
+                values.len()
+            )));
+        }
+        unsafe {
+            let obj = ffi::PyStructSequence_New(self.ty.as_ptr() as *mut ffi::PyTypeObject);
+            if obj.is_null() {
+                return Err(PyErr::fetch(py));
+            }
+            for (i, value) in values.into_iter().enumerate() {
+                // Steals the reference
+                ffi::PyStructSequence_SetItem(obj, i as ffi::Py_ssize_t, value.into_ptr());
+            }
+            Ok(PyObject::from_owned_ptr(py, obj))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::exceptions;
+    use crate::types::{PyStructSequenceType, PyTuple};
+    use crate::{AsPyRef, ObjectProtocol, Python, ToPyObject};
+
+    #[test]
+    fn test_struct_sequence() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let ty =
+            PyStructSequenceType::new(py, "test.Pair", "A pair", &[("a", ""), ("b", "")]).unwrap();
+        assert_eq!(ty.as_type(py).name(), "test.Pair");
+
+        let pair = ty
+            .instantiate(py, vec![1.to_object(py), "x".to_object(py)])
+            .unwrap();
+        let pair = pair.as_ref(py);
+        assert!(pair.downcast_ref::<PyTuple>().is_ok());
+        assert_eq!(pair.getattr("a").unwrap().extract::<i32>().unwrap(), 1);
+        assert_eq!(pair.getattr("b").unwrap().extract::<String>().unwrap(), "x");
+        assert_eq!(pair.get_item(1).unwrap().extract::<String>().unwrap(), "x");
+        assert!(ty.as_type(py).is_instance(pair).unwrap());
+
+        let err = ty.instantiate(py, vec![1.to_object(py)]).unwrap_err();
+        assert!(err.is_instance::<exceptions::ValueError>(py));
+    }
+}
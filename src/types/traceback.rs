@@ -0,0 +1,53 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+use crate::err::PyResult;
+use crate::ffi;
+use crate::instance::PyNativeType;
+use crate::internal_tricks::Unsendable;
+use crate::object::PyObject;
+use crate::ObjectProtocol;
+
+/// Represents a Python traceback object.
+#[repr(transparent)]
+pub struct PyTraceback(PyObject, Unsendable);
+
+pyobject_native_var_type!(PyTraceback, ffi::PyTraceBack_Type, ffi::PyTraceBack_Check);
+
+impl PyTraceback {
+    /// Formats the traceback as a string, the same way Python prints it
+    /// before the exception itself.
+    ///
+    /// This calls `traceback.format_tb` and prefixes the familiar
+    /// `Traceback (most recent call last):` header.
+    pub fn format(&self) -> PyResult<String> {
+        let py = self.py();
+        let lines = py
+            .import("traceback")?
+            .call1("format_tb", (self,))?
+            .iter()?
+            .map(|line| line.and_then(|line| line.extract::<String>()))
+            .collect::<PyResult<Vec<String>>>()?;
+        Ok(format!(
+            "Traceback (most recent call last):\n{}",
+            lines.concat()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{AsPyRef, Python};
+
+    #[test]
+    fn test_format_traceback() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let err = py
+            .run("raise ValueError('raised exception')", None, None)
+            .unwrap_err();
+        let traceback = err.traceback(py).unwrap();
+        let formatted = traceback.as_ref(py).format().unwrap();
+        assert!(formatted.starts_with("Traceback (most recent call last):\n"));
+        assert!(formatted.contains("line 1"));
+    }
+}
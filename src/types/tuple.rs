@@ -94,6 +94,39 @@ impl PyTuple {
         }
     }
 
+    /// Extracts the items of a tuple of length 2.
+    ///
+    /// Returns a `ValueError` if the tuple has a different length. This is equivalent to
+    /// `self.extract::<(A, B)>()`.
+    pub fn extract_pair<'a, A, B>(&'a self) -> PyResult<(A, B)>
+    where
+        A: FromPyObject<'a>,
+        B: FromPyObject<'a>,
+    {
+        FromPyObject::extract(self.as_ref())
+    }
+
+    /// Extracts the items of a tuple of length 3, see [PyTuple::extract_pair].
+    pub fn extract_triple<'a, A, B, C>(&'a self) -> PyResult<(A, B, C)>
+    where
+        A: FromPyObject<'a>,
+        B: FromPyObject<'a>,
+        C: FromPyObject<'a>,
+    {
+        FromPyObject::extract(self.as_ref())
+    }
+
+    /// Extracts the items of a tuple of length 4, see [PyTuple::extract_pair].
+    pub fn extract_quad<'a, A, B, C, D>(&'a self) -> PyResult<(A, B, C, D)>
+    where
+        A: FromPyObject<'a>,
+        B: FromPyObject<'a>,
+        C: FromPyObject<'a>,
+        D: FromPyObject<'a>,
+    {
+        FromPyObject::extract(self.as_ref())
+    }
+
     /// Returns an iterator over the tuple items.
     pub fn iter(&self) -> PyTupleIterator {
         PyTupleIterator {
@@ -196,6 +229,24 @@ macro_rules! tuple_conversion ({$length:expr,$(($refN:ident, $n:tt, $T:ident)),+
     }
 });
 
+macro_rules! array_conversion {
+    ($($n:expr),*) => {$(
+        /// Converts the array into a Python `tuple`.
+        ///
+        /// Arrays also coerce to slices, whose `ToPyObject` implementation creates a `list`.
+        impl<T: ToPyObject> IntoPy<PyObject> for [T; $n] {
+            fn into_py(self, py: Python) -> PyObject {
+                PyTuple::new(py, self.iter()).to_object(py)
+            }
+        }
+    )*}
+}
+
+array_conversion!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31, 32
+);
+
 tuple_conversion!(1, (ref0, 0, A));
 tuple_conversion!(2, (ref0, 0, A), (ref1, 1, B));
 tuple_conversion!(3, (ref0, 0, A), (ref1, 1, B), (ref2, 2, C));
@@ -250,15 +301,128 @@ tuple_conversion!(
     (ref7, 7, H),
     (ref8, 8, I)
 );
+tuple_conversion!(
+    10,
+    (ref0, 0, A),
+    (ref1, 1, B),
+    (ref2, 2, C),
+    (ref3, 3, D),
+    (ref4, 4, E),
+    (ref5, 5, F),
+    (ref6, 6, G),
+    (ref7, 7, H),
+    (ref8, 8, I),
+    (ref9, 9, J)
+);
+tuple_conversion!(
+    11,
+    (ref0, 0, A),
+    (ref1, 1, B),
+    (ref2, 2, C),
+    (ref3, 3, D),
+    (ref4, 4, E),
+    (ref5, 5, F),
+    (ref6, 6, G),
+    (ref7, 7, H),
+    (ref8, 8, I),
+    (ref9, 9, J),
+    (ref10, 10, K)
+);
+tuple_conversion!(
+    12,
+    (ref0, 0, A),
+    (ref1, 1, B),
+    (ref2, 2, C),
+    (ref3, 3, D),
+    (ref4, 4, E),
+    (ref5, 5, F),
+    (ref6, 6, G),
+    (ref7, 7, H),
+    (ref8, 8, I),
+    (ref9, 9, J),
+    (ref10, 10, K),
+    (ref11, 11, L)
+);
+tuple_conversion!(
+    13,
+    (ref0, 0, A),
+    (ref1, 1, B),
+    (ref2, 2, C),
+    (ref3, 3, D),
+    (ref4, 4, E),
+    (ref5, 5, F),
+    (ref6, 6, G),
+    (ref7, 7, H),
+    (ref8, 8, I),
+    (ref9, 9, J),
+    (ref10, 10, K),
+    (ref11, 11, L),
+    (ref12, 12, M)
+);
+tuple_conversion!(
+    14,
+    (ref0, 0, A),
+    (ref1, 1, B),
+    (ref2, 2, C),
+    (ref3, 3, D),
+    (ref4, 4, E),
+    (ref5, 5, F),
+    (ref6, 6, G),
+    (ref7, 7, H),
+    (ref8, 8, I),
+    (ref9, 9, J),
+    (ref10, 10, K),
+    (ref11, 11, L),
+    (ref12, 12, M),
+    (ref13, 13, N)
+);
+tuple_conversion!(
+    15,
+    (ref0, 0, A),
+    (ref1, 1, B),
+    (ref2, 2, C),
+    (ref3, 3, D),
+    (ref4, 4, E),
+    (ref5, 5, F),
+    (ref6, 6, G),
+    (ref7, 7, H),
+    (ref8, 8, I),
+    (ref9, 9, J),
+    (ref10, 10, K),
+    (ref11, 11, L),
+    (ref12, 12, M),
+    (ref13, 13, N),
+    (ref14, 14, O)
+);
+tuple_conversion!(
+    16,
+    (ref0, 0, A),
+    (ref1, 1, B),
+    (ref2, 2, C),
+    (ref3, 3, D),
+    (ref4, 4, E),
+    (ref5, 5, F),
+    (ref6, 6, G),
+    (ref7, 7, H),
+    (ref8, 8, I),
+    (ref9, 9, J),
+    (ref10, 10, K),
+    (ref11, 11, L),
+    (ref12, 12, M),
+    (ref13, 13, N),
+    (ref14, 14, O),
+    (ref15, 15, P)
+);
 
 #[cfg(test)]
 mod test {
-    use crate::instance::AsPyRef;
+    use crate::instance::{AsPyRef, Py};
+    use crate::object::PyObject;
     use crate::objectprotocol::ObjectProtocol;
     use crate::types::PyAny;
     use crate::types::PyTuple;
     use crate::Python;
-    use crate::{PyTryFrom, ToPyObject};
+    use crate::{IntoPy, PyTryFrom, ToPyObject};
     use std::collections::HashSet;
 
     #[test]
@@ -312,4 +476,65 @@ mod test {
             assert_eq!(i + 1, item.extract().unwrap());
         }
     }
+
+    #[test]
+    fn test_tuple_conversion_arity_16() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let values = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+        let tuple: Py<PyTuple> = values.into_py(py);
+        let tuple = tuple.as_ref(py);
+        assert_eq!(16, tuple.len());
+        for (i, item) in tuple.iter().enumerate() {
+            assert_eq!(i, item.extract::<usize>().unwrap());
+        }
+
+        type Sixteen = (
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+            u8,
+        );
+        let extracted: Sixteen = tuple.extract().unwrap();
+        assert_eq!(0, extracted.0);
+        assert_eq!(15, extracted.15);
+        assert!(tuple.extract::<(u8, u8)>().is_err());
+    }
+
+    #[test]
+    fn test_array_into_tuple() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let obj: PyObject = [1u8, 2, 3].into_py(py);
+        let tuple = <PyTuple as PyTryFrom>::try_from(obj.as_ref(py)).unwrap();
+        assert_eq!(tuple.extract::<(u8, u8, u8)>().unwrap(), (1, 2, 3));
+    }
+
+    #[test]
+    fn test_extract_pair_triple_quad() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let tuple = PyTuple::new(py, &[1, 2, 3]);
+
+        let (a, b, c): (i32, u8, i64) = tuple.extract_triple().unwrap();
+        assert_eq!((a, b, c), (1, 2, 3));
+
+        let err = tuple.extract_pair::<i32, i32>().unwrap_err();
+        assert!(err.is_instance::<crate::exceptions::ValueError>(py));
+        assert!(tuple.extract_quad::<i32, i32, i32, i32>().is_err());
+        assert!(tuple.extract_triple::<i32, String, i32>().is_err());
+    }
 }
@@ -7,7 +7,9 @@ use crate::ffi;
 use crate::instance::{Py, PyNativeType};
 use crate::internal_tricks::Unsendable;
 use crate::object::PyObject;
+use crate::objectprotocol::ObjectProtocol;
 use crate::type_object::PyTypeObject;
+use crate::types::{PyAny, PyDict, PyString, PyTuple};
 use crate::AsPyPointer;
 use crate::Python;
 use std::borrow::Cow;
@@ -25,6 +27,30 @@ impl PyType {
         T::type_object()
     }
 
+    /// Creates a new class with the given name, base classes and namespace.
+    ///
+    /// This is equivalent to the Python expression `type(name, bases, dict)`, so the
+    /// metaclass is derived from `bases` and `__init_subclass__` hooks are run.
+    /// A `#[pyclass]` can only be used as a base if it is declared with `#[pyclass(subclass)]`.
+    pub fn create_subclass(
+        py: Python,
+        name: &str,
+        bases: &PyTuple,
+        dict: &PyDict,
+    ) -> PyResult<Py<PyType>> {
+        let name = PyString::new(py, name);
+        let class: &PyAny = unsafe {
+            py.from_owned_ptr_or_err(ffi::PyObject_CallFunctionObjArgs(
+                py.get_type::<PyType>().as_ptr(),
+                name.as_ptr(),
+                bases.as_ptr(),
+                dict.as_ptr(),
+                std::ptr::null_mut::<ffi::PyObject>(),
+            ))?
+        };
+        Ok(class.downcast_ref::<PyType>()?.into())
+    }
+
     /// Retrieves the underlying FFI pointer associated with this Python object.
     #[inline]
     pub unsafe fn as_type_ptr(&self) -> *mut ffi::PyTypeObject {
@@ -44,6 +70,23 @@ impl PyType {
         unsafe { CStr::from_ptr((*self.as_type_ptr()).tp_name).to_string_lossy() }
     }
 
+    /// Gets the method resolution order of the type, starting with the type itself.
+    /// This is equivalent to the Python expression `self.__mro__`.
+    pub fn mro(&self) -> PyResult<Vec<&PyType>> {
+        let mro: &PyTuple = unsafe {
+            let ptr = (*self.as_type_ptr()).tp_mro;
+            if ptr.is_null() {
+                // `tp_mro` is only filled in by `PyType_Ready`
+                self.getattr("__mro__")?.downcast_ref()?
+            } else {
+                self.py().from_borrowed_ptr(ptr)
+            }
+        };
+        mro.iter()
+            .map(|ty| ty.downcast_ref::<PyType>().map_err(PyErr::from))
+            .collect()
+    }
+
     /// Check whether `self` is subclass of type `T` like Python `issubclass` function
     pub fn is_subclass<T>(&self) -> PyResult<bool>
     where
@@ -59,6 +102,27 @@ impl PyType {
         }
     }
 
+    /// Check whether `self` is a subtype of `other`, following `tp_mro`.
+    ///
+    /// Unlike [PyType::is_subclass], this never calls `__subclasscheck__` and cannot fail.
+    pub fn is_subtype_of(&self, other: &PyType) -> bool {
+        unsafe { ffi::PyType_IsSubtype(self.as_type_ptr(), other.as_type_ptr()) != 0 }
+    }
+
+    /// Check whether `self` is a base type of `other`, i.e. `other` is a subtype of `self`.
+    pub fn is_basetype_of(&self, other: &PyType) -> bool {
+        other.is_subtype_of(self)
+    }
+
+    /// Checks whether the type or one of its base classes defines the attribute `name`.
+    ///
+    /// Only the dicts of the types in the MRO are searched, so unlike `hasattr(self, name)`
+    /// this ignores attributes of the metaclass and never invokes descriptors.
+    pub fn has_method(&self, name: &str) -> bool {
+        let name = PyString::new(self.py(), name);
+        unsafe { !ffi::_PyType_Lookup(self.as_type_ptr(), name.as_ptr()).is_null() }
+    }
+
     // Check whether `obj` is an instance of `self`
     pub fn is_instance<T: AsPyPointer>(&self, obj: &T) -> PyResult<bool> {
         let result = unsafe { ffi::PyObject_IsInstance(obj.as_ptr(), self.as_ptr()) };
@@ -71,3 +135,69 @@ impl PyType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::instance::AsPyRef;
+    use crate::objectprotocol::ObjectProtocol;
+    use crate::types::{PyAny, PyBool, PyDict, PyInt, PyTuple, PyType};
+    use crate::Python;
+
+    #[test]
+    fn test_mro() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mro = py.get_type::<PyBool>().mro().unwrap();
+        let names: Vec<_> = mro.iter().map(|ty| ty.name().into_owned()).collect();
+        assert_eq!(names, vec!["bool", "int", "object"]);
+        assert_eq!(mro[1], py.get_type::<PyInt>());
+        assert_eq!(mro[2], py.get_type::<PyAny>());
+    }
+
+    #[test]
+    fn test_is_subtype_of() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let bool_type = py.get_type::<PyBool>();
+        let int_type = py.get_type::<PyInt>();
+        assert!(bool_type.is_subtype_of(int_type));
+        assert!(bool_type.is_subtype_of(bool_type));
+        assert!(!int_type.is_subtype_of(bool_type));
+        assert!(int_type.is_basetype_of(bool_type));
+        assert!(!bool_type.is_basetype_of(int_type));
+    }
+
+    #[test]
+    fn test_has_method() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let bool_type = py.get_type::<PyBool>();
+        assert!(bool_type.has_method("__and__"));
+        assert!(bool_type.has_method("bit_length"));
+        assert!(!bool_type.has_method("mro"));
+        assert!(!bool_type.has_method("no_such_method"));
+    }
+
+    #[test]
+    fn test_create_subclass() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let int_type = py.get_type::<PyInt>();
+        let dict = PyDict::new(py);
+        dict.set_item("answer", 42).unwrap();
+        let bases = PyTuple::new(py, &[int_type]);
+        let class = PyType::create_subclass(py, "MyInt", bases, dict).unwrap();
+        let class = class.as_ref(py);
+        assert_eq!(class.name(), "MyInt");
+        assert!(class.is_subtype_of(int_type));
+        assert_eq!(
+            class.getattr("answer").unwrap().extract::<i32>().unwrap(),
+            42
+        );
+        let obj = class.call1((5,)).unwrap();
+        assert_eq!(obj.extract::<i32>().unwrap(), 5);
+
+        let bool_bases = PyTuple::new(py, &[py.get_type::<PyBool>()]);
+        assert!(PyType::create_subclass(py, "MyBool", bool_bases, PyDict::new(py)).is_err());
+    }
+}
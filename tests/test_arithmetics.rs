@@ -42,6 +42,30 @@ fn unary_arithmetic() {
     py_run!(py, c, "assert ~c == 'invert'");
 }
 
+#[pyclass]
+struct Index {
+    value: isize,
+}
+
+#[pyproto]
+impl PyNumberProtocol for Index {
+    fn __index__(&self) -> PyResult<isize> {
+        Ok(self.value)
+    }
+}
+
+#[test]
+fn index() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = Py::new(py, Index { value: 2 }).unwrap();
+    py_run!(py, c, "assert [10, 20, 30][c] == 30");
+    py_run!(py, c, "assert list(range(c)) == [0, 1]");
+    py_run!(py, c, "assert bin(c) == '0b10'");
+    py_run!(py, c, "import operator; assert operator.index(c) == 2");
+}
+
 #[pyclass]
 struct BinaryArithmetic {}
 
@@ -105,6 +129,16 @@ impl PyNumberProtocol for InPlaceOperations {
         self.value |= other;
         Ok(())
     }
+
+    fn __imatmul__(&mut self, other: u32) -> PyResult<()> {
+        self.value *= other;
+        Ok(())
+    }
+
+    fn __ifloordiv__(&mut self, other: u32) -> PyResult<()> {
+        self.value /= other;
+        Ok(())
+    }
 }
 
 #[test]
@@ -125,6 +159,8 @@ fn inplace_operations() {
     init(12, "d = c; c &= 10; assert repr(c) == repr(d) == 'IPO(8)'");
     init(12, "d = c; c |= 3; assert repr(c) == repr(d) == 'IPO(15)'");
     init(12, "d = c; c ^= 5; assert repr(c) == repr(d) == 'IPO(9)'");
+    init(3, "d = c; c @= 4; assert repr(c) == repr(d) == 'IPO(12)'");
+    init(14, "d = c; c //= 4; assert repr(c) == repr(d) == 'IPO(3)'");
 }
 
 #[pyproto]
@@ -160,6 +196,18 @@ impl PyNumberProtocol for BinaryArithmetic {
     fn __or__(lhs: &PyAny, rhs: &PyAny) -> PyResult<String> {
         Ok(format!("{:?} | {:?}", lhs, rhs))
     }
+
+    fn __matmul__(lhs: &PyAny, rhs: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} @ {:?}", lhs, rhs))
+    }
+
+    fn __floordiv__(lhs: &PyAny, rhs: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} // {:?}", lhs, rhs))
+    }
+
+    fn __divmod__(lhs: &PyAny, rhs: &PyAny) -> PyResult<String> {
+        Ok(format!("divmod({:?}, {:?})", lhs, rhs))
+    }
 }
 
 #[test]
@@ -186,6 +234,12 @@ fn binary_arithmetic() {
     py_run!(py, c, "assert 1 ^ c == '1 ^ BA'");
     py_run!(py, c, "assert c | 1 == 'BA | 1'");
     py_run!(py, c, "assert 1 | c == '1 | BA'");
+    py_run!(py, c, "assert c @ 1 == 'BA @ 1'");
+    py_run!(py, c, "assert 1 @ c == '1 @ BA'");
+    py_run!(py, c, "assert c // 1 == 'BA // 1'");
+    py_run!(py, c, "assert 1 // c == '1 // BA'");
+    py_run!(py, c, "assert divmod(c, 1) == 'divmod(BA, 1)'");
+    py_run!(py, c, "assert divmod(1, c) == 'divmod(1, BA)'");
 }
 
 #[pyclass]
@@ -196,6 +250,18 @@ impl PyNumberProtocol for RhsArithmetic {
     fn __radd__(&self, other: &PyAny) -> PyResult<String> {
         Ok(format!("{:?} + RA", other))
     }
+
+    fn __rmatmul__(&self, other: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} @ RA", other))
+    }
+
+    fn __rfloordiv__(&self, other: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} // RA", other))
+    }
+
+    fn __rdivmod__(&self, other: &PyAny) -> PyResult<String> {
+        Ok(format!("divmod({:?}, RA)", other))
+    }
 }
 
 #[test]
@@ -205,11 +271,55 @@ fn rhs_arithmetic() {
 
     let c = Py::new(py, RhsArithmetic {}).unwrap();
     py_run!(py, c, "assert c.__radd__(1) == '1 + RA'");
+    py_run!(py, c, "assert c.__rmatmul__(1) == '1 @ RA'");
+    py_run!(py, c, "assert c.__rfloordiv__(1) == '1 // RA'");
+    py_run!(py, c, "assert c.__rdivmod__(1) == 'divmod(1, RA)'");
     // TODO: commented out for now until reflected arithemtics gets fixed.
     // see discussion here: https://github.com/PyO3/pyo3/pull/550
     // py_run!(py, c, "assert 1 + c == '1 + RA'");
 }
 
+#[pyclass]
+struct RhsBitwise {}
+
+#[pyproto]
+impl PyNumberProtocol for RhsBitwise {
+    fn __rlshift__(&self, other: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} << RB", other))
+    }
+
+    fn __rrshift__(&self, other: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} >> RB", other))
+    }
+
+    fn __rand__(&self, other: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} & RB", other))
+    }
+
+    fn __rxor__(&self, other: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} ^ RB", other))
+    }
+
+    fn __ror__(&self, other: &PyAny) -> PyResult<String> {
+        Ok(format!("{:?} | RB", other))
+    }
+}
+
+#[test]
+fn rhs_bitwise() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = Py::new(py, RhsBitwise {}).unwrap();
+    py_run!(py, c, "assert 1 << c == '1 << RB'");
+    py_run!(py, c, "assert 1 >> c == '1 >> RB'");
+    py_run!(py, c, "assert 1 & c == '1 & RB'");
+    py_run!(py, c, "assert 1 ^ c == '1 ^ RB'");
+    py_run!(py, c, "assert 1 | c == '1 | RB'");
+    py_expect_exception!(py, c, "c << 1", TypeError);
+    py_expect_exception!(py, c, "c | 1", TypeError);
+}
+
 #[pyclass]
 struct RichComparisons {}
 
@@ -0,0 +1,92 @@
+// `async fn` needs a newer compiler than the minimum nightly, like tokio itself does,
+// so these tests are only built with the `tokio` feature
+#![cfg(feature = "tokio")]
+
+use pyo3::exceptions::ValueError;
+use pyo3::prelude::*;
+use pyo3::{py_run, wrap_pyfunction};
+
+mod common;
+
+#[pyfunction]
+async fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+#[pyfunction]
+async fn check_positive(n: i64) -> PyResult<i64> {
+    if n > 0 {
+        Ok(n)
+    } else {
+        Err(ValueError::py_err("not positive"))
+    }
+}
+
+#[pyfunction(async_runtime = "tokio")]
+async fn on_tokio(n: u64) -> (u64, bool) {
+    // Spawned tasks run on the worker threads of the runtime, not on the Python thread
+    let thread = std::thread::current();
+    (n, thread.name() == Some("tokio-runtime-worker"))
+}
+
+#[pyfunction(async_runtime = "tokio")]
+async fn panics_on_tokio() -> u64 {
+    panic!("task failed")
+}
+
+#[test]
+fn test_async_fn() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let add = wrap_pyfunction!(add)(py);
+    let check_positive = wrap_pyfunction!(check_positive)(py);
+    py_run!(
+        py,
+        add check_positive,
+        r#"
+import asyncio
+
+async def run():
+    assert await add(1, 2) == 3
+    assert await check_positive(4) == 4
+    try:
+        await check_positive(-1)
+    except ValueError as e:
+        assert str(e) == 'not positive'
+    else:
+        assert False
+
+loop = asyncio.new_event_loop()
+loop.run_until_complete(run())
+loop.close()
+"#
+    );
+}
+
+#[test]
+fn test_async_runtime_tokio() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let on_tokio = wrap_pyfunction!(on_tokio)(py);
+    let panics_on_tokio = wrap_pyfunction!(panics_on_tokio)(py);
+    py_run!(
+        py,
+        on_tokio panics_on_tokio,
+        r#"
+import asyncio
+
+async def run():
+    assert await on_tokio(1) == (1, True)
+    try:
+        await panics_on_tokio()
+    except RuntimeError:
+        pass
+    else:
+        assert False
+
+loop = asyncio.new_event_loop()
+loop.run_until_complete(run())
+loop.close()
+"#
+    );
+}
@@ -1,8 +1,10 @@
+use pyo3::class::buffer::{fill_view_from_mut_slice, fill_view_from_slice};
 use pyo3::class::PyBufferProtocol;
 use pyo3::exceptions::BufferError;
 use pyo3::ffi;
 use pyo3::prelude::*;
 use pyo3::types::IntoPyDict;
+use std::cell::{Cell, RefCell};
 use std::ffi::CStr;
 use std::os::raw::{c_int, c_void};
 use std::ptr;
@@ -76,3 +78,104 @@ fn test_buffer() {
     let d = [("ob", t)].into_py_dict(py);
     py.run("assert bytes(ob) == b' 23'", None, Some(d)).unwrap();
 }
+
+#[pyclass]
+struct ReadOnlyBytes {
+    data: Vec<u8>,
+}
+
+#[pyproto]
+impl PyBufferProtocol for ReadOnlyBytes {
+    fn bf_getbuffer(&self, view: *mut ffi::Py_buffer, flags: c_int) -> PyResult<()> {
+        unsafe { fill_view_from_slice(view, flags, &self.data) }
+    }
+}
+
+#[test]
+fn test_buffer_from_slice() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ob = Py::new(
+        py,
+        ReadOnlyBytes {
+            data: b"abc".to_vec(),
+        },
+    )
+    .unwrap();
+    let d = [("ob", ob)].into_py_dict(py);
+    py.run(
+        "m = memoryview(ob); assert m.readonly and m.obj is ob and m.tobytes() == b'abc'",
+        None,
+        Some(d),
+    )
+    .unwrap();
+    let err = py
+        .run("m = memoryview(ob); m[0] = 0", None, Some(d))
+        .unwrap_err();
+    assert!(err.is_instance::<pyo3::exceptions::TypeError>(py));
+    let err = py
+        .run("__import__('io').BytesIO(b'x').readinto(ob)", None, Some(d))
+        .unwrap_err();
+    assert!(err.is_instance::<BufferError>(py));
+
+    // A failed request doesn't keep a reference to the object
+    py.run(
+        r#"
+import sys
+before = sys.getrefcount(ob)
+try:
+    __import__('io').BytesIO(b'x').readinto(ob)
+except BufferError:
+    pass
+assert sys.getrefcount(ob) == before
+"#,
+        None,
+        Some(d),
+    )
+    .unwrap();
+}
+
+#[pyclass]
+struct WritableBytes {
+    data: RefCell<Vec<u8>>,
+    released: Cell<usize>,
+}
+
+#[pyproto]
+impl PyBufferProtocol for WritableBytes {
+    fn bf_getbuffer(&self, view: *mut ffi::Py_buffer, flags: c_int) -> PyResult<()> {
+        unsafe { fill_view_from_mut_slice(view, flags, &mut self.data.borrow_mut()) }
+    }
+
+    fn bf_releasebuffer(&self, _view: *mut ffi::Py_buffer) -> PyResult<()> {
+        self.released.set(self.released.get() + 1);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_buffer_from_mut_slice() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let ob = Py::new(
+        py,
+        WritableBytes {
+            data: RefCell::new(vec![0; 3]),
+            released: Cell::new(0),
+        },
+    )
+    .unwrap();
+    let d = [("ob", ob.clone_ref(py))].into_py_dict(py);
+    py.run(
+        "m = memoryview(ob); assert not m.readonly; m[1] = 42; m.release()",
+        None,
+        Some(d),
+    )
+    .unwrap();
+
+    let ob = ob.as_ref(py).borrow();
+    assert_eq!(*ob.data.borrow(), vec![0, 42, 0]);
+    assert_eq!(ob.released.get(), 1);
+}
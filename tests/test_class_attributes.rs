@@ -0,0 +1,111 @@
+use pyo3::prelude::*;
+use pyo3::py_run;
+
+mod common;
+
+#[pyclass]
+struct Foo {
+    #[pyo3(get)]
+    x: i32,
+}
+
+#[pyclass]
+struct Bar {
+    #[pyo3(get)]
+    x: i32,
+}
+
+#[pymethods]
+impl Foo {
+    #[classattr]
+    const MY_CONST: &'static str = "foobar";
+
+    #[classattr]
+    fn a() -> i32 {
+        5
+    }
+
+    #[classattr]
+    #[name = "B"]
+    fn b() -> String {
+        "bar".to_string()
+    }
+
+    #[classattr]
+    fn foo() -> Foo {
+        Foo { x: 1 }
+    }
+
+    #[classattr]
+    fn bar() -> Bar {
+        Bar { x: 2 }
+    }
+}
+
+#[test]
+fn class_attributes() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let foo_obj = py.get_type::<Foo>();
+    py_assert!(py, foo_obj, "foo_obj.MY_CONST == 'foobar'");
+    py_assert!(py, foo_obj, "foo_obj.a == 5");
+    py_assert!(py, foo_obj, "foo_obj.B == 'bar'");
+    py_assert!(py, foo_obj, "'a' in foo_obj.__dict__");
+}
+
+#[test]
+fn class_attributes_are_immutable() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let foo_obj = py.get_type::<Foo>();
+    py_expect_exception!(py, foo_obj, "foo_obj.a = 6", TypeError);
+}
+
+#[test]
+fn recursive_class_attributes() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let foo_obj = py.get_type::<Foo>();
+    let bar_obj = py.get_type::<Bar>();
+    py_assert!(py, foo_obj, "foo_obj.foo.x == 1");
+    py_assert!(py, foo_obj, "foo_obj.bar.x == 2");
+    py_run!(py, bar_obj, "assert not hasattr(bar_obj, 'foo')");
+}
+
+#[pyclass]
+struct SlowAttributes {}
+
+#[pymethods]
+impl SlowAttributes {
+    #[classattr]
+    fn first() -> i32 {
+        // Lets other threads run while the class attributes are being added
+        let gil = Python::acquire_gil();
+        gil.python()
+            .allow_threads(|| std::thread::sleep(std::time::Duration::from_millis(50)));
+        1
+    }
+
+    #[classattr]
+    fn second() -> i32 {
+        2
+    }
+}
+
+#[test]
+fn class_attributes_from_multiple_threads() {
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                let gil = Python::acquire_gil();
+                let py = gil.python();
+                let cls = py.get_type::<SlowAttributes>();
+                py_assert!(py, cls, "cls.first == 1 and cls.second == 2");
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
@@ -1,5 +1,6 @@
+use pyo3::class::PySequenceProtocol;
 use pyo3::prelude::*;
-use pyo3::py_run;
+use pyo3::{py_run, PyCell, PyRef};
 
 mod common;
 
@@ -138,3 +139,181 @@ fn empty_class_in_module() {
     // than using whatever calls init first.
     assert_eq!(module, "builtins");
 }
+
+#[pyclass]
+struct Counter {
+    count: u32,
+}
+
+#[test]
+fn py_with_and_with_mut() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let counter = Py::new(py, Counter { count: 1 }).unwrap();
+
+    let doubled = counter.with(py, |c| c.count * 2);
+    assert_eq!(doubled, 2);
+
+    let previous = counter.with_mut(py, |c| {
+        let previous = c.count;
+        c.count += 1;
+        previous
+    });
+    assert_eq!(previous, 1);
+    assert_eq!(counter.with(py, |c| c.count), 2);
+}
+
+#[test]
+fn py_try_borrow() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let counter = Py::new(py, Counter { count: 1 }).unwrap();
+
+    {
+        let first = counter.try_borrow(py).unwrap();
+        let second = counter.try_borrow(py).unwrap();
+        assert_eq!(first.count + second.count, 2);
+        let err = counter.try_borrow_mut(py).err().unwrap();
+        assert!(err.is_instance::<pyo3::exceptions::RuntimeError>(py));
+    }
+
+    {
+        let mut value = counter.try_borrow_mut(py).unwrap();
+        value.count = 5;
+        assert!(counter.try_borrow(py).is_err());
+        assert!(counter.try_borrow_mut(py).is_err());
+    }
+
+    assert_eq!(counter.try_borrow(py).unwrap().count, 5);
+}
+
+#[test]
+fn shell_borrow() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let shell = PyCell::new_ref(py, Counter { count: 1 }).unwrap();
+
+    // No `Python` token is needed once the shell is in scope
+    fn increment(shell: &PyCell<Counter>) {
+        shell.borrow_mut().count += 1;
+    }
+    increment(shell);
+
+    let first = shell.borrow();
+    let second = PyRef::clone(&first);
+    assert_eq!(first.count + second.count, 4);
+    assert!(shell.try_borrow_mut().is_err());
+    drop(first);
+    assert!(shell.try_borrow_mut().is_err());
+    drop(second);
+    assert!(shell.try_borrow_mut().is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Already mutably borrowed")]
+fn shell_borrow_panics_while_mutably_borrowed() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let shell = PyCell::new_ref(py, Counter { count: 1 }).unwrap();
+
+    let _value = shell.borrow_mut();
+    let _ = shell.borrow();
+}
+
+#[test]
+fn py_with_mut_releases_borrow_on_panic() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let counter = Py::new(py, Counter { count: 1 }).unwrap();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        counter.with_mut(py, |_| panic!("oops"))
+    }));
+    assert!(result.is_err());
+    assert!(counter.try_borrow_mut(py).is_ok());
+}
+
+#[pyclass]
+struct Reentrant {
+    count: usize,
+}
+
+#[pymethods]
+impl Reentrant {
+    fn update(&mut self, py: Python, callback: PyObject) -> PyResult<()> {
+        callback.call0(py)?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn get(&self) -> usize {
+        self.count
+    }
+}
+
+#[pyproto]
+impl PySequenceProtocol for Reentrant {
+    fn __len__(&self) -> PyResult<usize> {
+        Ok(self.count)
+    }
+}
+
+#[test]
+fn reentrant_calls_are_borrow_checked() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let r = PyCell::new_ref(py, Reentrant { count: 0 }).unwrap();
+
+    py_expect_exception!(py, r, "r.update(lambda: r.get())", RuntimeError);
+    py_expect_exception!(py, r, "r.update(lambda: len(r))", RuntimeError);
+    py_expect_exception!(
+        py,
+        r,
+        "r.update(lambda: r.update(lambda: None))",
+        RuntimeError
+    );
+    py_run!(
+        py,
+        r,
+        "r.update(lambda: None); assert r.get() == 1 and len(r) == 1"
+    );
+
+    let _value = r.borrow();
+    py_expect_exception!(py, r, "r.update(lambda: None)", RuntimeError);
+    py_run!(py, r, "assert r.get() == 1");
+}
+
+#[pyclass(frozen)]
+struct FrozenPoint {
+    #[pyo3(get)]
+    x: i32,
+}
+
+#[pymethods]
+impl FrozenPoint {
+    fn doubled(&self) -> i32 {
+        self.x * 2
+    }
+}
+
+#[test]
+fn frozen_class() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let point = Py::new(py, FrozenPoint { x: 21 }).unwrap();
+
+    assert_eq!(point.get(py).x, 21);
+    let err = point.try_borrow_mut(py).err().unwrap();
+    assert!(err.is_instance::<pyo3::exceptions::TypeError>(py));
+
+    py_run!(py, point, "assert point.x == 21 and point.doubled() == 42");
+    py_expect_exception!(py, point, "point.x = 1", AttributeError);
+    py_run!(py, point, "assert {point: 1}[point] == 1");
+    assert_eq!(point.get(py).x, 21);
+}
+
+#[test]
+fn frozen_class_implements_marker() {
+    fn assert_frozen<T: pyo3::PyFrozenClass>() {}
+    assert_frozen::<FrozenPoint>();
+}
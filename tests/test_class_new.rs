@@ -1,4 +1,7 @@
+use pyo3::class::PyObjectProtocol;
 use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyDict, PyTuple};
+use pyo3::{py_run, PyCell};
 
 #[pyclass]
 struct EmptyClassWithNew {}
@@ -77,3 +80,145 @@ fn new_with_two_args() {
     assert_eq!(obj._data1, 10);
     assert_eq!(obj._data2, 20);
 }
+
+#[pyclass]
+struct NewWithKeywords {
+    x: i32,
+    y: i32,
+}
+
+#[pymethods]
+impl NewWithKeywords {
+    #[new]
+    #[args(x = "0", y = "0")]
+    fn new(x: i32, y: i32) -> Self {
+        NewWithKeywords { x, y }
+    }
+}
+
+#[test]
+fn new_with_keywords() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let typeobj = py.get_type::<NewWithKeywords>();
+    let kwargs = [("y", 20)].into_py_dict(py);
+    let wrp = typeobj.call((), Some(kwargs)).unwrap();
+    let obj = wrp.cast_as::<NewWithKeywords>().unwrap();
+    assert_eq!(obj.x, 0);
+    assert_eq!(obj.y, 20);
+
+    let wrp = typeobj.call((10,), None).unwrap();
+    let obj = wrp.cast_as::<NewWithKeywords>().unwrap();
+    assert_eq!(obj.x, 10);
+    assert_eq!(obj.y, 0);
+
+    assert!(typeobj.call((), Some([("z", 1)].into_py_dict(py))).is_err());
+}
+
+#[pyclass]
+struct NewWithInit {
+    data: i32,
+    init_calls: usize,
+}
+
+#[pymethods]
+impl NewWithInit {
+    #[new]
+    #[args(args = "*", kwargs = "**")]
+    fn new(args: &PyTuple, kwargs: Option<&PyDict>) -> Self {
+        let _ = (args, kwargs);
+        NewWithInit {
+            data: 0,
+            init_calls: 0,
+        }
+    }
+
+    #[getter]
+    fn data(&self) -> i32 {
+        self.data
+    }
+
+    #[getter]
+    fn init_calls(&self) -> usize {
+        self.init_calls
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for NewWithInit {
+    fn __init__(slf: &PyCell<Self>, args: (i32,), kwargs: Option<&PyDict>) -> PyResult<()> {
+        let mut slf = slf.borrow_mut();
+        slf.data = args.0;
+        if let Some(offset) = kwargs
+            .map(|kwargs| kwargs.get_item_with_error("offset"))
+            .transpose()?
+            .and_then(|offset| offset)
+        {
+            slf.data += offset.extract::<i32>()?;
+        }
+        slf.init_calls += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn new_with_init() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let typeobj = py.get_type::<NewWithInit>();
+    let wrp = typeobj.call((10,), None).unwrap();
+    let obj = wrp.cast_as::<NewWithInit>().unwrap();
+    assert_eq!(obj.data, 10);
+    assert_eq!(obj.init_calls, 1);
+
+    py_run!(
+        py,
+        typeobj,
+        r#"
+obj = typeobj(1, offset=2)
+assert obj.data == 3
+obj.__init__(5)
+assert obj.data == 5
+assert obj.init_calls == 2
+"#
+    );
+    assert!(typeobj.call(("not an int",), None).is_err());
+}
+
+/// A class with both `#[init]` and `PyObjectProtocol::__init__`, where `#[init]` is used
+#[pyclass]
+struct BothInits {
+    #[pyo3(get)]
+    source: &'static str,
+}
+
+#[pymethods]
+impl BothInits {
+    #[new]
+    #[args(args = "*")]
+    fn new(args: &PyTuple) -> Self {
+        let _ = args;
+        BothInits { source: "new" }
+    }
+
+    #[init]
+    fn init(&mut self) {
+        self.source = "#[init]";
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for BothInits {
+    fn __init__(slf: &PyCell<Self>, _args: &PyTuple, _kwargs: Option<&PyDict>) -> PyResult<()> {
+        slf.borrow_mut().source = "PyObjectProtocol";
+        Ok(())
+    }
+}
+
+#[test]
+fn init_attribute_takes_precedence() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let typeobj = py.get_type::<BothInits>();
+    py_run!(py, typeobj, "assert typeobj().source == '#[init]'");
+}
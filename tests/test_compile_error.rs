@@ -3,6 +3,9 @@ fn test_compile_errors() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/invalid_pymethod_names.rs");
     t.compile_fail("tests/ui/missing_clone.rs");
+    t.compile_fail("tests/ui/reject_allow_threads_python.rs");
+    t.compile_fail("tests/ui/reject_frozen_mutation.rs");
+    t.compile_fail("tests/ui/reject_frozen_setter.rs");
     t.compile_fail("tests/ui/reject_generics.rs");
     t.compile_fail("tests/ui/too_many_args_to_getter.rs");
 }
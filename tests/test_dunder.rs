@@ -1,13 +1,17 @@
 #![feature(specialization)]
 
 use pyo3::class::{
-    PyContextProtocol, PyIterProtocol, PyMappingProtocol, PyObjectProtocol, PySequenceProtocol,
+    PyAsyncProtocol, PyContextProtocol, PyCoroutine, PyDescrProtocol, PyIterProtocol,
+    PyMappingProtocol, PyObjectProtocol, PySequenceProtocol, PyVectorcallProtocol,
 };
 use pyo3::exceptions::{IndexError, ValueError};
 use pyo3::prelude::*;
-use pyo3::types::{IntoPyDict, PyAny, PyBytes, PySlice, PyType};
-use pyo3::{ffi, py_run, AsPyPointer, PyClassShell};
+use pyo3::types::{IntoPyDict, PyAny, PyBytes, PySlice, PyTuple, PyType};
+use pyo3::{ffi, py_run, wrap_pyfunction, AsPyPointer, PyCell};
 use std::convert::TryFrom;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::{isize, iter};
 
 mod common;
@@ -53,12 +57,12 @@ struct Iterator {
 
 #[pyproto]
 impl<'p> PyIterProtocol for Iterator {
-    fn __iter__(slf: &mut PyClassShell<Self>) -> PyResult<Py<Iterator>> {
+    fn __iter__(slf: &PyCell<Self>) -> PyResult<Py<Iterator>> {
         Ok(slf.into())
     }
 
-    fn __next__(slf: &mut PyClassShell<Self>) -> PyResult<Option<i32>> {
-        Ok(slf.iter.next())
+    fn __next__(slf: &PyCell<Self>) -> PyResult<Option<i32>> {
+        Ok(slf.borrow_mut().iter.next())
     }
 }
 
@@ -78,6 +82,43 @@ fn iterator() {
     py_assert!(py, inst, "list(inst) == [5, 6, 7]");
 }
 
+#[pyclass]
+struct Countdown {
+    remaining: usize,
+}
+
+#[pyproto]
+impl PyIterProtocol for Countdown {
+    fn __iter__(slf: &PyCell<Self>) -> PyResult<Py<Countdown>> {
+        Ok(slf.into())
+    }
+
+    fn __next__(slf: &PyCell<Self>) -> PyResult<Option<usize>> {
+        let mut slf = slf.borrow_mut();
+        if slf.remaining == 0 {
+            return Ok(None);
+        }
+        slf.remaining -= 1;
+        Ok(Some(slf.remaining))
+    }
+
+    fn __length_hint__(&self) -> PyResult<usize> {
+        Ok(self.remaining)
+    }
+}
+
+#[test]
+fn length_hint() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let inst = Py::new(py, Countdown { remaining: 3 }).unwrap();
+    py_assert!(py, inst, "inst.__length_hint__() == 3");
+    py_assert!(py, inst, "__import__('operator').length_hint(inst) == 3");
+    py_assert!(py, inst, "list(inst) == [2, 1, 0]");
+    py_assert!(py, inst, "__import__('operator').length_hint(inst) == 0");
+}
+
 #[pyclass]
 struct StringMethods {}
 
@@ -113,6 +154,406 @@ fn string_methods() {
     py_assert!(py, obj, "bytes(obj) == b'bytes'");
 }
 
+#[pyclass]
+struct Temperature {
+    celsius: f64,
+}
+
+#[pyproto]
+impl PyObjectProtocol for Temperature {
+    // The format spec is borrowed from the Python string, without allocating a `String`
+    fn __format__(&self, format_spec: &str) -> PyResult<String> {
+        match format_spec {
+            "" | "C" => Ok(format!("{}°C", self.celsius)),
+            "F" => Ok(format!("{}°F", self.celsius * 9.0 / 5.0 + 32.0)),
+            _ => Err(ValueError::py_err(format!(
+                "unknown format spec {:?}",
+                format_spec
+            ))),
+        }
+    }
+}
+
+#[test]
+fn format_with_str_spec() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = Py::new(py, Temperature { celsius: 100.0 }).unwrap();
+    py_assert!(py, obj, "format(obj) == '100°C'");
+    py_assert!(py, obj, "f'{obj:F}' == '212°F'");
+    py_expect_exception!(py, obj, "format(obj, 'K')", ValueError);
+}
+
+#[pyclass]
+struct PathLike {
+    path: String,
+}
+
+#[pyproto]
+impl PyObjectProtocol for PathLike {
+    fn __fspath__(&self) -> PyResult<String> {
+        Ok(self.path.clone())
+    }
+}
+
+#[test]
+#[cfg(Py_3_6)]
+fn fspath() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = Py::new(
+        py,
+        PathLike {
+            path: "some/file.txt".to_string(),
+        },
+    )
+    .unwrap();
+    py_assert!(py, obj, "__import__('os').fspath(obj) == 'some/file.txt'");
+    py_assert!(
+        py,
+        obj,
+        "__import__('pathlib').PurePath(obj) == __import__('pathlib').PurePath('some/file.txt')"
+    );
+    let path: std::path::PathBuf = obj.to_object(py).extract(py).unwrap();
+    assert_eq!(path, std::path::PathBuf::from("some/file.txt"));
+}
+
+#[pyclass(debug, display)]
+#[derive(Debug)]
+struct FormattedPoint {
+    x: i32,
+    y: i32,
+}
+
+impl std::fmt::Display for FormattedPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[test]
+fn derived_string_methods() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = Py::new(py, FormattedPoint { x: 1, y: 2 }).unwrap();
+    py_assert!(py, obj, "repr(obj) == 'FormattedPoint { x: 1, y: 2 }'");
+    py_assert!(py, obj, "str(obj) == '(1, 2)'");
+}
+
+#[pyclass(debug)]
+#[derive(Debug)]
+struct DebugOnly {
+    #[pyo3(get)]
+    value: Vec<u8>,
+}
+
+#[test]
+fn derived_repr_only() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = Py::new(py, DebugOnly { value: vec![4, 2] }).unwrap();
+    py_assert!(py, obj, "repr(obj) == 'DebugOnly { value: [4, 2] }'");
+    // Without `display`, `str` falls back to `repr`
+    py_assert!(py, obj, "str(obj) == repr(obj)");
+}
+
+#[pyclass(hash)]
+#[derive(Hash)]
+struct HashedKey {
+    name: String,
+    id: u32,
+}
+
+#[test]
+fn derived_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let key = HashedKey {
+        name: "a".to_string(),
+        id: 1,
+    };
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    let expected = match hasher.finish() as isize {
+        -1 => -2,
+        hash => hash,
+    };
+
+    let a = Py::new(py, key).unwrap();
+    let b = Py::new(
+        py,
+        HashedKey {
+            name: "a".to_string(),
+            id: 1,
+        },
+    )
+    .unwrap();
+    let c = Py::new(
+        py,
+        HashedKey {
+            name: "b".to_string(),
+            id: 1,
+        },
+    )
+    .unwrap();
+    let d = [("a", &a), ("b", &b), ("c", &c)].into_py_dict(py);
+    py.run("assert hash(a) == hash(b) != hash(c)", None, Some(d))
+        .unwrap();
+    assert_eq!(a.to_object(py).as_ref(py).hash().unwrap(), expected);
+}
+
+#[pyclass(eq)]
+#[derive(PartialEq)]
+struct EqOnly {
+    value: i32,
+}
+
+#[pyclass(ord, hash)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+#[test]
+fn derived_comparisons() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let a = Py::new(py, EqOnly { value: 1 }).unwrap();
+    let b = Py::new(py, EqOnly { value: 1 }).unwrap();
+    let c = Py::new(py, EqOnly { value: 2 }).unwrap();
+    let d = [("a", &a), ("b", &b), ("c", &c)].into_py_dict(py);
+    py.run(
+        "assert a == b and not a != b\n\
+         assert a != c and not a == c\n\
+         assert a != 1 and not a == 1",
+        None,
+        Some(d),
+    )
+    .unwrap();
+    // Ordering isn't derived, and without `hash` the class is unhashable like in Python
+    py_expect_exception!(py, a, "a < a", TypeError);
+    py_expect_exception!(py, a, "hash(a)", TypeError);
+
+    let v1 = Py::new(py, Version { major: 1, minor: 2 }).unwrap();
+    let v2 = Py::new(
+        py,
+        Version {
+            major: 1,
+            minor: 10,
+        },
+    )
+    .unwrap();
+    let v3 = Py::new(py, Version { major: 1, minor: 2 }).unwrap();
+    let d = [("v1", &v1), ("v2", &v2), ("v3", &v3)].into_py_dict(py);
+    py.run(
+        "assert v1 < v2 and v1 <= v2 and v2 > v1 and v2 >= v1\n\
+         assert v1 == v3 and v1 <= v3 and v1 >= v3 and not v1 < v3\n\
+         assert len({v1, v2, v3}) == 2\n\
+         assert sorted([v2, v1]) == [v1, v2]",
+        None,
+        Some(d),
+    )
+    .unwrap();
+    py_expect_exception!(py, v1, "v1 < 1", TypeError);
+}
+
+/// `hash` and `eq` only set their slots, so the class can still implement `PyObjectProtocol`
+#[pyclass(hash, eq)]
+#[derive(PartialEq, Eq, Hash)]
+struct Tag {
+    name: String,
+}
+
+#[pyproto]
+impl PyObjectProtocol for Tag {
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("Tag({:?})", self.name))
+    }
+}
+
+#[test]
+fn derived_slots_with_pyproto() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let a = Py::new(py, Tag { name: "a".into() }).unwrap();
+    let b = Py::new(py, Tag { name: "a".into() }).unwrap();
+    let d = [("a", &a), ("b", &b)].into_py_dict(py);
+    py.run(
+        "assert repr(a) == 'Tag(\"a\")'\n\
+         assert a == b and hash(a) == hash(b)\n\
+         assert len({a, b}) == 1",
+        None,
+        Some(d),
+    )
+    .unwrap();
+}
+
+/// Stores its value upper-cased in a private attribute of the instance
+#[pyclass]
+struct UpperCase {
+    name: String,
+}
+
+#[pymethods]
+impl UpperCase {
+    #[new]
+    fn new() -> Self {
+        UpperCase {
+            name: String::new(),
+        }
+    }
+}
+
+#[pyproto]
+impl<'p> PyDescrProtocol<'p> for UpperCase {
+    fn __get__(&self, instance: Option<&PyAny>, _owner: Option<&PyType>) -> PyResult<String> {
+        match instance {
+            Some(instance) => instance.getattr(format!("_{}", self.name))?.extract(),
+            None => Ok(format!("descriptor {}", self.name)),
+        }
+    }
+
+    fn __set__(&self, instance: &PyAny, value: String) -> PyResult<()> {
+        instance.setattr(format!("_{}", self.name), value.to_uppercase())
+    }
+
+    fn __delete__(&self, instance: &PyAny) -> PyResult<()> {
+        instance.delattr(format!("_{}", self.name))
+    }
+
+    fn __set_name__(&mut self, _owner: &PyType, name: String) -> PyResult<()> {
+        self.name = name;
+        Ok(())
+    }
+}
+
+// `__set_name__` was added in Python 3.6
+#[test]
+#[cfg(Py_3_6)]
+fn descriptor() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let upper = py.get_type::<UpperCase>();
+    py_run!(
+        py,
+        upper,
+        r#"
+        class C:
+            x = upper()
+
+        assert C.x == "descriptor x"
+        c = C()
+        c.x = "hello"
+        assert c.x == "HELLO"
+        assert c._x == "HELLO"
+        del c.x
+        assert not hasattr(c, "_x")
+        "#
+    );
+}
+
+/// A descriptor that only implements `__set__`, so deleting the attribute must fail
+#[pyclass]
+struct SetOnly {}
+
+#[pyproto]
+impl<'p> PyDescrProtocol<'p> for SetOnly {
+    fn __set__(&self, instance: &PyAny, value: i32) -> PyResult<()> {
+        instance.setattr("_value", value)
+    }
+}
+
+#[test]
+fn descriptor_without_delete() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let descr = Py::new(py, SetOnly {}).unwrap();
+    py_run!(
+        py,
+        descr,
+        r#"
+        class C:
+            pass
+        C.x = descr
+        c = C()
+        c.x = 5
+        assert c._value == 5
+        try:
+            del c.x
+            assert False, "deleting the attribute should fail"
+        except AttributeError as e:
+            assert str(e) == "can't delete attribute"
+        "#
+    );
+}
+
+/// `__set_name__` also works as a regular method, without the rest of the descriptor protocol
+#[pyclass]
+struct NameRecorder {
+    name: Option<String>,
+    owner: Option<String>,
+}
+
+#[pymethods]
+impl NameRecorder {
+    #[new]
+    fn new() -> Self {
+        NameRecorder {
+            name: None,
+            owner: None,
+        }
+    }
+
+    fn __set_name__(&mut self, owner: &PyType, name: &str) -> PyResult<()> {
+        self.owner = Some(owner.name().into_owned());
+        self.name = Some(name.to_string());
+        Ok(())
+    }
+
+    #[getter]
+    fn assigned(&self) -> Option<(String, String)> {
+        match (&self.owner, &self.name) {
+            (Some(owner), Some(name)) => Some((owner.clone(), name.clone())),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+#[cfg(Py_3_6)]
+fn set_name_in_pymethods() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let recorder = py.get_type::<NameRecorder>();
+    py_run!(
+        py,
+        recorder,
+        r#"
+        unassigned = recorder()
+        assert unassigned.assigned is None
+
+        class Owner:
+            field = recorder()
+
+        assert Owner.field.assigned == ("Owner", "field")
+        "#
+    );
+}
+
 #[pyclass]
 struct Comparisons {
     val: i32,
@@ -230,6 +671,42 @@ fn callable() {
     py_assert!(py, nc, "not callable(nc)");
 }
 
+#[pyclass(vectorcall)]
+struct VectorCallable {}
+
+impl PyVectorcallProtocol for VectorCallable {
+    fn __vectorcall__(
+        &self,
+        py: Python,
+        args: &[&PyAny],
+        kwnames: Option<&PyTuple>,
+    ) -> PyResult<PyObject> {
+        let kwnames: Vec<String> = match kwnames {
+            Some(kwnames) => kwnames.extract()?,
+            None => Vec::new(),
+        };
+        let args: Vec<i32> = args
+            .iter()
+            .map(|arg| arg.extract())
+            .collect::<PyResult<_>>()?;
+        Ok((args, kwnames).to_object(py))
+    }
+}
+
+#[test]
+fn vectorcall() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = Py::new(py, VectorCallable {}).unwrap();
+    py_assert!(py, c, "callable(c)");
+    py_assert!(py, c, "c() == ([], [])");
+    py_assert!(py, c, "c(1, 2) == ([1, 2], [])");
+    py_assert!(py, c, "c(1, b=3, a=2) == ([1, 3, 2], ['b', 'a'])");
+    py_assert!(py, c, "c(*[1], **{'x': 2}) == ([1, 2], ['x'])");
+    py_assert!(py, c, "list(map(c, [1, 2])) == [([1], []), ([2], [])]");
+}
+
 #[pyclass]
 struct SetItem {
     key: i32,
@@ -250,10 +727,10 @@ fn setitem() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let c = PyClassShell::new_ref(py, SetItem { key: 0, val: 0 }).unwrap();
+    let c = PyCell::new_ref(py, SetItem { key: 0, val: 0 }).unwrap();
     py_run!(py, c, "c[1] = 2");
-    assert_eq!(c.key, 1);
-    assert_eq!(c.val, 2);
+    assert_eq!(c.borrow().key, 1);
+    assert_eq!(c.borrow().val, 2);
     py_expect_exception!(py, c, "del c[1]", NotImplementedError);
 }
 
@@ -275,9 +752,9 @@ fn delitem() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let c = PyClassShell::new_ref(py, DelItem { key: 0 }).unwrap();
+    let c = PyCell::new_ref(py, DelItem { key: 0 }).unwrap();
     py_run!(py, c, "del c[1]");
-    assert_eq!(c.key, 1);
+    assert_eq!(c.borrow().key, 1);
     py_expect_exception!(py, c, "c[1] = 2", NotImplementedError);
 }
 
@@ -304,11 +781,11 @@ fn setdelitem() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let c = PyClassShell::new_ref(py, SetDelItem { val: None }).unwrap();
+    let c = PyCell::new_ref(py, SetDelItem { val: None }).unwrap();
     py_run!(py, c, "c[1] = 2");
-    assert_eq!(c.val, Some(2));
+    assert_eq!(c.borrow().val, Some(2));
     py_run!(py, c, "del c[1]");
-    assert_eq!(c.val, None);
+    assert_eq!(c.borrow().val, None);
 }
 
 #[pyclass]
@@ -351,6 +828,27 @@ fn contains() {
     py_expect_exception!(py, c, "assert 'wrong type' not in c", TypeError);
 }
 
+#[pyclass]
+struct ContainsAny {}
+
+#[pyproto]
+impl PySequenceProtocol for ContainsAny {
+    fn __contains__(&self, item: &PyAny) -> PyResult<bool> {
+        Ok(item.extract::<i32>().map(|i| i >= 0).unwrap_or(false))
+    }
+}
+
+#[test]
+fn contains_any() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = Py::new(py, ContainsAny {}).unwrap();
+    py_run!(py, c, "assert 1 in c");
+    py_run!(py, c, "assert -1 not in c");
+    py_run!(py, c, "assert 'wrong type' not in c");
+}
+
 #[pyclass]
 struct ContextManager {
     exit_called: bool,
@@ -383,22 +881,22 @@ fn context_manager() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let c = PyClassShell::new_mut(py, ContextManager { exit_called: false }).unwrap();
+    let c = PyCell::new_ref(py, ContextManager { exit_called: false }).unwrap();
     py_run!(py, c, "with c as x: assert x == 42");
-    assert!(c.exit_called);
+    assert!(c.borrow().exit_called);
 
-    c.exit_called = false;
+    c.borrow_mut().exit_called = false;
     py_run!(py, c, "with c as x: raise ValueError");
-    assert!(c.exit_called);
+    assert!(c.borrow().exit_called);
 
-    c.exit_called = false;
+    c.borrow_mut().exit_called = false;
     py_expect_exception!(
         py,
         c,
         "with c as x: raise NotImplementedError",
         NotImplementedError
     );
-    assert!(c.exit_called);
+    assert!(c.borrow().exit_called);
 }
 
 #[test]
@@ -455,7 +953,7 @@ struct DunderDictSupport {}
 fn dunder_dict_support() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let inst = PyClassShell::new_ref(py, DunderDictSupport {}).unwrap();
+    let inst = PyCell::new_ref(py, DunderDictSupport {}).unwrap();
     py_run!(
         py,
         inst,
@@ -470,7 +968,7 @@ fn dunder_dict_support() {
 fn access_dunder_dict() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let inst = PyClassShell::new_ref(py, DunderDictSupport {}).unwrap();
+    let inst = PyCell::new_ref(py, DunderDictSupport {}).unwrap();
     py_run!(
         py,
         inst,
@@ -488,7 +986,7 @@ struct WeakRefDunderDictSupport {}
 fn weakref_dunder_dict_support() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let inst = PyClassShell::new_ref(py, WeakRefDunderDictSupport {}).unwrap();
+    let inst = PyCell::new_ref(py, WeakRefDunderDictSupport {}).unwrap();
     py_run!(
         py,
         inst,
@@ -513,7 +1011,120 @@ impl PyObjectProtocol for ClassWithGetAttr {
 fn getattr_doesnt_override_member() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let inst = PyClassShell::new_ref(py, ClassWithGetAttr { data: 4 }).unwrap();
+    let inst = PyCell::new_ref(py, ClassWithGetAttr { data: 4 }).unwrap();
     py_assert!(py, inst, "inst.data == 4");
     py_assert!(py, inst, "inst.a == 8");
 }
+
+/// Completes with `value` the second time it is polled
+struct YieldOnce {
+    value: PyResult<i32>,
+    polled: bool,
+}
+
+impl Future for YieldOnce {
+    type Output = PyResult<i32>;
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<PyResult<i32>> {
+        if self.polled {
+            let value = std::mem::replace(&mut self.value, Ok(0));
+            Poll::Ready(value)
+        } else {
+            self.polled = true;
+            Poll::Pending
+        }
+    }
+}
+
+#[pyclass]
+struct AsyncCounter {
+    count: i32,
+}
+
+#[pyproto]
+impl PyAsyncProtocol for AsyncCounter {
+    fn __anext__(&mut self) -> PyResult<Option<PyCoroutine<YieldOnce>>> {
+        if self.count == 3 {
+            return Ok(None);
+        }
+        self.count += 1;
+        Ok(Some(PyCoroutine::new(YieldOnce {
+            value: Ok(self.count),
+            polled: false,
+        })))
+    }
+}
+
+#[test]
+fn async_anext() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let counter = Py::new(py, AsyncCounter { count: 0 }).unwrap();
+    py_run!(
+        py,
+        counter,
+        r#"
+import asyncio
+
+async def collect(it):
+    values = []
+    while True:
+        try:
+            values.append(await it.__anext__())
+        except StopAsyncIteration:
+            return values
+
+loop = asyncio.new_event_loop()
+assert loop.run_until_complete(collect(counter)) == [1, 2, 3]
+loop.close()
+"#
+    );
+}
+
+#[pyfunction]
+fn yield_once(value: i32) -> PyCoroutine<YieldOnce> {
+    let value = if value < 0 {
+        Err(ValueError::py_err("negative"))
+    } else {
+        Ok(value)
+    };
+    PyCoroutine::new(YieldOnce {
+        value,
+        polled: false,
+    })
+}
+
+#[test]
+fn await_future() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let yield_once = wrap_pyfunction!(yield_once)(py);
+    py_run!(
+        py,
+        yield_once,
+        r#"
+import asyncio
+
+async def run():
+    assert await yield_once(5) == 5
+    try:
+        await yield_once(-1)
+    except ValueError as e:
+        assert str(e) == 'negative'
+    else:
+        assert False
+    coroutine = yield_once(1)
+    await coroutine
+    try:
+        await coroutine
+    except RuntimeError:
+        pass
+    else:
+        assert False
+
+loop = asyncio.new_event_loop()
+loop.run_until_complete(run())
+loop.close()
+"#
+    );
+}
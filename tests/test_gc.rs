@@ -1,9 +1,11 @@
+use pyo3::class::gc::PyGCTraverse;
 use pyo3::class::PyGCProtocol;
+use pyo3::class::PyObjectProtocol;
 use pyo3::class::PyTraverseError;
 use pyo3::class::PyVisit;
 use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyTuple};
-use pyo3::{ffi, py_run, AsPyPointer, PyClassShell};
+use pyo3::{ffi, py_run, AsPyPointer, PyCell};
 use std::cell::RefCell;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -81,6 +83,89 @@ fn data_is_dropped() {
     assert!(drop_called2.load(Ordering::Relaxed));
 }
 
+#[pyclass]
+struct ClassWithDel {
+    del_called: Arc<AtomicBool>,
+    member: TestDropCall,
+}
+
+#[pyproto]
+impl PyObjectProtocol for ClassWithDel {
+    fn __del__(&mut self) -> PyResult<()> {
+        // The Rust value is only dropped after the finalizer ran
+        assert!(!self.member.drop_called.load(Ordering::Relaxed));
+        self.del_called.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[test]
+fn finalizer_is_called_before_drop() {
+    let del_called = Arc::new(AtomicBool::new(false));
+    let drop_called = Arc::new(AtomicBool::new(false));
+
+    {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let inst = Py::new(
+            py,
+            ClassWithDel {
+                del_called: Arc::clone(&del_called),
+                member: TestDropCall {
+                    drop_called: Arc::clone(&drop_called),
+                },
+            },
+        )
+        .unwrap();
+        py_run!(py, inst, "assert hasattr(type(inst), '__del__')");
+        assert!(!del_called.load(Ordering::Relaxed));
+        drop(inst);
+    }
+
+    assert!(del_called.load(Ordering::Relaxed));
+    assert!(drop_called.load(Ordering::Relaxed));
+}
+
+#[pyclass(freelist = 2)]
+struct ClassWithDelAndFreelist {
+    del_called: Arc<AtomicBool>,
+    member: TestDropCall,
+}
+
+#[pyproto]
+impl PyObjectProtocol for ClassWithDelAndFreelist {
+    fn __del__(&mut self) -> PyResult<()> {
+        assert!(!self.member.drop_called.load(Ordering::Relaxed));
+        self.del_called.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[test]
+fn finalizer_is_called_before_drop_with_freelist() {
+    let del_called = Arc::new(AtomicBool::new(false));
+    let drop_called = Arc::new(AtomicBool::new(false));
+
+    {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let inst = Py::new(
+            py,
+            ClassWithDelAndFreelist {
+                del_called: Arc::clone(&del_called),
+                member: TestDropCall {
+                    drop_called: Arc::clone(&drop_called),
+                },
+            },
+        )
+        .unwrap();
+        drop(inst);
+    }
+
+    assert!(del_called.load(Ordering::Relaxed));
+    assert!(drop_called.load(Ordering::Relaxed));
+}
+
 #[pyclass]
 struct ClassWithDrop {}
 
@@ -153,7 +238,7 @@ fn gc_integration() {
     {
         let gil = Python::acquire_gil();
         let py = gil.python();
-        let inst = PyClassShell::new_mut(
+        let inst = PyCell::new_mut(
             py,
             GCIntegration {
                 self_ref: RefCell::new(py.None()),
@@ -164,7 +249,7 @@ fn gc_integration() {
         )
         .unwrap();
 
-        *inst.self_ref.borrow_mut() = inst.to_object(py);
+        *inst.borrow().self_ref.borrow_mut() = inst.to_object(py);
     }
 
     let gil = Python::acquire_gil();
@@ -188,10 +273,92 @@ impl PyGCProtocol for GCIntegration2 {
 fn gc_integration2() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let inst = PyClassShell::new_ref(py, GCIntegration2 {}).unwrap();
+    let inst = PyCell::new_ref(py, GCIntegration2 {}).unwrap();
     py_run!(py, inst, "import gc; assert inst in gc.get_objects()");
 }
 
+impl PyGCTraverse for TestDropCall {
+    fn traverse(&self, _visit: PyVisit) -> Result<(), PyTraverseError> {
+        Ok(())
+    }
+}
+
+#[allow(dead_code)]
+#[pyclass(gc)]
+#[derive(PyGCTraverse)]
+struct GCDerived {
+    value: i32,
+    self_ref: RefCell<Option<PyObject>>,
+    others: Vec<PyObject>,
+    dropped: TestDropCall,
+}
+
+#[test]
+fn gc_derived() {
+    let drop_called = Arc::new(AtomicBool::new(false));
+
+    {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let inst = PyCell::new_ref(
+            py,
+            GCDerived {
+                value: 0,
+                self_ref: RefCell::new(None),
+                others: vec![py.None()],
+                dropped: TestDropCall {
+                    drop_called: Arc::clone(&drop_called),
+                },
+            },
+        )
+        .unwrap();
+
+        *inst.borrow().self_ref.borrow_mut() = Some(inst.to_object(py));
+        py_run!(py, inst, "import gc; assert inst in gc.get_referrers(inst)");
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    py.run("import gc; gc.collect()", None, None).unwrap();
+    assert!(drop_called.load(Ordering::Relaxed));
+}
+
+#[allow(dead_code)]
+#[pyclass(slots = ["x"])]
+struct SlottedCycle {
+    dropped: TestDropCall,
+}
+
+#[test]
+fn gc_slots_cycle() {
+    let drop_called = Arc::new(AtomicBool::new(false));
+
+    {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let inst = PyCell::new_ref(
+            py,
+            SlottedCycle {
+                dropped: TestDropCall {
+                    drop_called: Arc::clone(&drop_called),
+                },
+            },
+        )
+        .unwrap();
+
+        py_run!(
+            py,
+            inst,
+            "import gc; inst.x = inst; assert inst in gc.get_referents(inst)"
+        );
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    py.run("import gc; gc.collect()", None, None).unwrap();
+    assert!(drop_called.load(Ordering::Relaxed));
+}
+
 #[pyclass(weakref)]
 struct WeakRefSupport {}
 
@@ -199,7 +366,7 @@ struct WeakRefSupport {}
 fn weakref_support() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let inst = PyClassShell::new_ref(py, WeakRefSupport {}).unwrap();
+    let inst = PyCell::new_ref(py, WeakRefSupport {}).unwrap();
     py_run!(
         py,
         inst,
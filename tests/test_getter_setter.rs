@@ -106,3 +106,34 @@ fn getter_setter_autogen() {
         "assert inst.text == 'Hello'; inst.text = 'There'; assert inst.text == 'There'"
     );
 }
+
+#[pyclass]
+struct ClassProxy {
+    wrapped: PyObject,
+}
+
+#[pymethods]
+impl ClassProxy {
+    #[getter(__class__)]
+    fn class(&self, py: Python) -> PyObject {
+        self.wrapped.getattr(py, "__class__").unwrap()
+    }
+}
+
+#[test]
+fn class_property_override() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let inst = Py::new(
+        py,
+        ClassProxy {
+            wrapped: 42.to_object(py),
+        },
+    )
+    .unwrap();
+
+    py_run!(py, inst, "assert inst.__class__ is int");
+    py_run!(py, inst, "assert isinstance(inst, int)");
+    py_run!(py, inst, "assert type(inst) is not int");
+}
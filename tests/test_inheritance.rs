@@ -3,7 +3,7 @@ use pyo3::py_run;
 
 use pyo3::types::IntoPyDict;
 
-use pyo3::types::{PyDict, PySet};
+use pyo3::types::{PyDict, PySet, PyTuple};
 mod common;
 
 #[pyclass]
@@ -30,6 +30,73 @@ fn subclass() {
     .unwrap();
 }
 
+#[pyclass(subclass)]
+struct Counter {
+    #[pyo3(get)]
+    count: i32,
+    #[pyo3(get)]
+    step: i32,
+}
+
+#[pymethods]
+impl Counter {
+    #[new]
+    #[args(args = "*", kwargs = "**")]
+    fn new(args: &PyTuple, kwargs: Option<&PyDict>) -> Self {
+        let _ = (args, kwargs);
+        Counter { count: 0, step: 1 }
+    }
+
+    #[init]
+    #[args(step = "1")]
+    fn init(&mut self, step: i32) {
+        self.step = step;
+    }
+
+    fn incr(&mut self) {
+        self.count += self.step;
+    }
+}
+
+#[test]
+fn subclass_new_and_init() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = [("Counter", py.get_type::<Counter>())].into_py_dict(py);
+
+    py.run(
+        r#"
+class DoubleCounter(Counter):
+    def __init__(self, step, name):
+        super().__init__(step * 2)
+        self.name = name
+
+    def incr(self):
+        super().incr()
+        super().incr()
+
+c = Counter(3)
+c.incr()
+assert type(c) is Counter
+assert c.count == 3
+
+d = DoubleCounter(3, name="double")
+assert type(d) is DoubleCounter
+assert isinstance(d, Counter)
+assert d.name == "double"
+assert d.step == 6
+d.incr()
+assert d.count == 12
+"#,
+        None,
+        Some(d),
+    )
+    .map_err(|e| e.print(py))
+    .unwrap();
+    let counter = py.get_type::<Counter>();
+    py_expect_exception!(py, counter, "counter(step='a')", TypeError);
+}
+
 #[pymethods]
 impl BaseClass {
     #[new]
@@ -126,7 +193,7 @@ impl SetWithName {
 fn inherit_set() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let set_sub = pyo3::pyclass::PyClassShell::new_ref(py, SetWithName::new()).unwrap();
+    let set_sub = pyo3::pyclass::PyCell::new_ref(py, SetWithName::new()).unwrap();
     py_run!(
         py,
         set_sub,
@@ -152,7 +219,7 @@ impl DictWithName {
 fn inherit_dict() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let dict_sub = pyo3::pyclass::PyClassShell::new_ref(py, DictWithName::new()).unwrap();
+    let dict_sub = pyo3::pyclass::PyCell::new_ref(py, DictWithName::new()).unwrap();
     py_run!(
         py,
         dict_sub,
@@ -5,7 +5,9 @@ use pyo3::exceptions::KeyError;
 use pyo3::prelude::*;
 use pyo3::types::IntoPyDict;
 use pyo3::types::PyList;
-use pyo3::PyMappingProtocol;
+use pyo3::{PyCell, PyIterProtocol, PyMappingProtocol};
+
+mod common;
 
 #[pyclass]
 struct Mapping {
@@ -127,3 +129,106 @@ fn test_reversed() {
 
     run("m = Mapping(['1', '2']); assert set(reversed(m)) == {'1', '2'}");
 }
+
+/// Like `Mapping`, but declared with `#[pyclass(mapping)]`
+#[cfg(Py_3_10)]
+#[pyclass(mapping)]
+struct FlaggedMapping {
+    index: HashMap<String, usize>,
+}
+
+#[cfg(Py_3_10)]
+#[pyproto]
+impl PyMappingProtocol for FlaggedMapping {
+    fn __len__(&self) -> PyResult<usize> {
+        Ok(self.index.len())
+    }
+
+    fn __getitem__(&self, query: String) -> PyResult<usize> {
+        self.index
+            .get(&query)
+            .copied()
+            .ok_or_else(|| KeyError::py_err("unknown key"))
+    }
+}
+
+#[test]
+#[cfg(Py_3_10)]
+fn test_mapping_pattern() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let index = [("1".to_string(), 0)].iter().cloned().collect();
+    let m = Py::new(py, FlaggedMapping { index }).unwrap();
+    let d = [("m", m)].into_py_dict(py);
+
+    py.run(
+        r#"
+match m:
+    case [*_]:
+        assert False
+    case {'1': 0}:
+        pass
+    case _:
+        assert False
+"#,
+        None,
+        Some(d),
+    )
+    .unwrap();
+}
+
+#[pyclass(mapping_mixin)]
+struct MixinMapping {
+    index: HashMap<String, usize>,
+}
+
+#[pyproto]
+impl PyMappingProtocol for MixinMapping {
+    fn __len__(&self) -> PyResult<usize> {
+        Ok(self.index.len())
+    }
+
+    fn __getitem__(&self, query: String) -> PyResult<usize> {
+        self.index
+            .get(&query)
+            .copied()
+            .ok_or_else(|| KeyError::py_err("unknown key"))
+    }
+}
+
+#[pyproto]
+impl PyIterProtocol for MixinMapping {
+    fn __iter__(slf: &PyCell<Self>) -> PyResult<PyObject> {
+        let py = unsafe { Python::assume_gil_acquired() };
+        let slf = slf.borrow();
+        let mut keys: Vec<&String> = slf.index.keys().collect();
+        keys.sort();
+        PyList::new(py, keys)
+            .call_method0("__iter__")
+            .map(Into::into)
+    }
+}
+
+#[test]
+fn test_mapping_mixin() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let index = [("a".to_string(), 1), ("b".to_string(), 2)]
+        .iter()
+        .cloned()
+        .collect();
+    let m = PyCell::new_ref(py, MixinMapping { index }).unwrap();
+
+    py_assert!(py, m, "list(m.keys()) == ['a', 'b']");
+    py_assert!(py, m, "list(m.values()) == [1, 2]");
+    py_assert!(py, m, "list(m.items()) == [('a', 1), ('b', 2)]");
+    py_assert!(py, m, "m.get('a') == 1 and m.get('c') is None");
+    py_assert!(py, m, "dict(m) == {'a': 1, 'b': 2}");
+    py_assert!(py, m, "{**m} == {'a': 1, 'b': 2}");
+    py_assert!(py, m, "'a' in m and 'c' not in m");
+    py_assert!(
+        py,
+        m,
+        "isinstance(m, __import__('collections.abc').abc.Mapping)"
+    );
+}
@@ -1,7 +1,7 @@
 use pyo3::prelude::*;
 use pyo3::py_run;
-use pyo3::types::{IntoPyDict, PyDict, PyList, PySet, PyString, PyTuple, PyType};
-use pyo3::PyClassShell;
+use pyo3::types::{IntoPyDict, PyAny, PyDict, PyList, PySet, PyString, PyTuple, PyType};
+use pyo3::PyCell;
 
 mod common;
 
@@ -23,8 +23,8 @@ fn instance_method() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let obj = PyClassShell::new_mut(py, InstanceMethod { member: 42 }).unwrap();
-    assert_eq!(obj.method().unwrap(), 42);
+    let obj = PyCell::new_mut(py, InstanceMethod { member: 42 }).unwrap();
+    assert_eq!(obj.borrow().method().unwrap(), 42);
     let d = [("obj", obj)].into_py_dict(py);
     py.run("assert obj.method() == 42", None, Some(d)).unwrap();
     py.run("assert obj.method.__doc__ == 'Test method'", None, Some(d))
@@ -49,8 +49,8 @@ fn instance_method_with_args() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let obj = PyClassShell::new_mut(py, InstanceMethodWithArgs { member: 7 }).unwrap();
-    assert_eq!(obj.method(6).unwrap(), 42);
+    let obj = PyCell::new_mut(py, InstanceMethodWithArgs { member: 7 }).unwrap();
+    assert_eq!(obj.borrow().method(6).unwrap(), 42);
     let d = [("obj", obj)].into_py_dict(py);
     py.run("assert obj.method(3) == 21", None, Some(d)).unwrap();
     py.run("assert obj.method(multiplier=6) == 42", None, Some(d))
@@ -131,6 +131,206 @@ fn class_method_with_args() {
     .unwrap();
 }
 
+#[pyclass]
+struct ClassWithGetItem {}
+
+#[pymethods]
+impl ClassWithGetItem {
+    fn __class_getitem__(cls: &PyType, item: &PyAny) -> PyResult<String> {
+        Ok(format!("{}[{}]", cls.name(), item.getattr("__name__")?))
+    }
+}
+
+#[test]
+fn class_getitem() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let d = [("C", py.get_type::<ClassWithGetItem>())].into_py_dict(py);
+    py.run(
+        "assert C.__class_getitem__(int) == 'ClassWithGetItem[int]'",
+        None,
+        Some(d),
+    )
+    .unwrap();
+    // `C[...]` syntax is only supported since Python 3.7 (PEP 560)
+    py.run(
+        "import sys\nassert sys.version_info < (3, 7) or C[str] == 'ClassWithGetItem[str]'",
+        None,
+        Some(d),
+    )
+    .unwrap();
+}
+
+#[pyclass(subclass)]
+struct PluginBase {}
+
+#[pymethods]
+impl PluginBase {
+    #[new]
+    fn new() -> Self {
+        PluginBase {}
+    }
+
+    #[args(kwargs = "**")]
+    fn __init_subclass__(cls: &PyType, kwargs: Option<&PyDict>) -> PyResult<()> {
+        let tag = kwargs
+            .map(|kwargs| kwargs.get_item_with_error("tag"))
+            .transpose()?
+            .and_then(|tag| tag);
+        cls.setattr("tag", tag)?;
+        cls.setattr("registered_name", cls.name().into_owned())
+    }
+}
+
+#[test]
+fn init_subclass() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let base = py.get_type::<PluginBase>();
+    py_run!(
+        py,
+        base,
+        r#"
+        assert not hasattr(base, "tag")
+
+        class Plugin(base, tag="json"):
+            pass
+
+        class Untagged(base):
+            pass
+
+        class Nested(Plugin):
+            pass
+
+        assert Plugin.tag == "json" and Plugin.registered_name == "Plugin"
+        assert Untagged.tag is None and Untagged.registered_name == "Untagged"
+        assert Nested.tag is None and Nested.registered_name == "Nested"
+        assert isinstance(Plugin(), base)
+        "#
+    );
+}
+
+#[pyclass]
+struct ManualCopy {
+    #[pyo3(get)]
+    value: u32,
+}
+
+#[pymethods]
+impl ManualCopy {
+    fn __copy__(&self, py: Python) -> PyResult<Py<Self>> {
+        Py::new(py, ManualCopy { value: self.value })
+    }
+
+    fn __deepcopy__(&self, py: Python, _memo: &PyDict) -> PyResult<Py<Self>> {
+        Py::new(
+            py,
+            ManualCopy {
+                value: self.value + 1,
+            },
+        )
+    }
+}
+
+#[pyclass(copy)]
+#[derive(Clone)]
+struct DerivedCopy {
+    #[pyo3(get)]
+    values: Vec<u32>,
+}
+
+#[test]
+fn copy_methods() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = Py::new(py, ManualCopy { value: 1 }).unwrap();
+    py_run!(
+        py,
+        obj,
+        r#"
+import copy
+assert copy.copy(obj) is not obj
+assert copy.copy(obj).value == 1
+assert copy.deepcopy(obj).value == 2
+"#
+    );
+
+    let obj = Py::new(py, DerivedCopy { values: vec![1, 2] }).unwrap();
+    py_run!(
+        py,
+        obj,
+        r#"
+import copy
+assert copy.copy(obj) is not obj
+assert copy.copy(obj).values == [1, 2]
+assert copy.deepcopy(obj) is not obj
+assert copy.deepcopy(obj).values == [1, 2]
+"#
+    );
+}
+
+#[pyclass(pickleable, module = "__main__")]
+struct Pickleable {
+    #[pyo3(get)]
+    value: u32,
+    #[pyo3(get)]
+    label: String,
+}
+
+#[pymethods]
+impl Pickleable {
+    #[new]
+    fn new(value: u32) -> Self {
+        Pickleable {
+            value,
+            label: String::new(),
+        }
+    }
+
+    fn __getnewargs__(&self) -> (u32,) {
+        (self.value,)
+    }
+
+    fn __getstate__(&self) -> String {
+        self.label.clone()
+    }
+
+    fn __setstate__(&mut self, state: String) {
+        self.label = state;
+    }
+}
+
+#[test]
+fn pickle_support() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = Py::new(
+        py,
+        Pickleable {
+            value: 5,
+            label: "five".to_string(),
+        },
+    )
+    .unwrap();
+    py_run!(
+        py,
+        obj,
+        r#"
+import pickle, __main__
+__main__.Pickleable = type(obj)
+assert obj.__reduce__() == (type(obj), (5,), 'five')
+copy = pickle.loads(pickle.dumps(obj))
+assert copy is not obj
+assert copy.value == 5
+assert copy.label == 'five'
+"#
+    );
+}
+
 #[pyclass]
 struct StaticMethod {}
 
@@ -269,6 +469,20 @@ impl MethArgs {
     fn args_as_vec(&self, args: Vec<i32>) -> i32 {
         args.iter().sum()
     }
+
+    fn get_kw_only(&self, a: i32, #[pyo3(kw_only)] b: i32, #[pyo3(kw_only)] c: Option<i32>) -> i32 {
+        a + b + c.unwrap_or(0)
+    }
+
+    fn get_args_attrs(
+        &self,
+        py: Python,
+        #[pyo3(args)] args: &PyTuple,
+        b: i32,
+        #[pyo3(kwargs)] kwargs: Option<&PyDict>,
+    ) -> PyObject {
+        [args.into(), b.to_object(py), kwargs.to_object(py)].to_object(py)
+    }
 }
 
 #[test]
@@ -324,9 +538,27 @@ fn meth_args() {
     py_expect_exception!(py, inst, "inst.get_pos_arg_kw(b=2)", TypeError);
 
     py_run!(py, inst, "assert inst.get_pos_kw(1, b=2) == [1, {'b': 2}]");
+
+    py_run!(
+        py,
+        inst,
+        "assert inst.get_args_attrs(1, 2, b=3) == [(1, 2), 3, None]"
+    );
+    py_run!(
+        py,
+        inst,
+        "assert inst.get_args_attrs(b=3, c=4) == [(), 3, {'c': 4}]"
+    );
+    py_expect_exception!(py, inst, "inst.get_args_attrs(1, 2)", TypeError);
     py_expect_exception!(py, inst, "inst.get_pos_kw(1,2)", TypeError);
 
     py_run!(py, inst, "assert inst.args_as_vec(1,2,3) == 6");
+
+    py_run!(py, inst, "assert inst.get_kw_only(1, b=2) == 3");
+    py_run!(py, inst, "assert inst.get_kw_only(a=1, b=2, c=3) == 6");
+    py_expect_exception!(py, inst, "inst.get_kw_only(1, 2)", TypeError);
+    py_expect_exception!(py, inst, "inst.get_kw_only(1, 2, b=2)", TypeError);
+    py_expect_exception!(py, inst, "inst.get_kw_only(1)", TypeError);
 }
 
 #[pyclass]
@@ -395,7 +627,7 @@ impl MethodWithLifeTime {
 fn method_with_lifetime() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let obj = PyClassShell::new_ref(py, MethodWithLifeTime {}).unwrap();
+    let obj = PyCell::new_ref(py, MethodWithLifeTime {}).unwrap();
     py_run!(
         py,
         obj,
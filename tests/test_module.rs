@@ -1,6 +1,7 @@
 use pyo3::prelude::*;
 
 use pyo3::types::{IntoPyDict, PyTuple};
+use std::collections::HashMap;
 
 mod common;
 
@@ -252,3 +253,150 @@ fn test_vararg_module() {
     py_assert!(py, m, "m.int_vararg_fn() == [5, ()]");
     py_assert!(py, m, "m.int_vararg_fn(1, 2) == [1, (2,)]");
 }
+
+#[pyfunction]
+fn kw_only_fn(a: i32, #[pyo3(kw_only)] b: i32) -> i32 {
+    a - b
+}
+
+#[test]
+fn test_kw_only_function() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let f = pyo3::wrap_pyfunction!(kw_only_fn)(py);
+
+    py_assert!(py, f, "f(3, b=1) == 2");
+    py_assert!(py, f, "f(b=1, a=3) == 2");
+    py_expect_exception!(py, f, "f(3, 1)", TypeError);
+}
+
+#[pyfunction]
+fn typed_vararg_fn(
+    a: i32,
+    #[pyo3(args)] args: Vec<i32>,
+    scale: Option<i32>,
+    #[pyo3(kwargs)] kwargs: HashMap<String, i32>,
+) -> (i32, Vec<i32>, HashMap<String, i32>) {
+    let scale = scale.unwrap_or(1);
+    (a * scale, args, kwargs)
+}
+
+#[test]
+fn test_typed_vararg_function() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let f = pyo3::wrap_pyfunction!(typed_vararg_fn)(py);
+
+    py_assert!(py, f, "f(1) == (1, [], {})");
+    py_assert!(py, f, "f(1, 2, 3) == (1, [2, 3], {})");
+    py_assert!(py, f, "f(1, 2, scale=3, x=4) == (3, [2], {'x': 4})");
+    py_expect_exception!(py, f, "f(1, 'a')", TypeError);
+    py_expect_exception!(py, f, "f(1, x='a')", TypeError);
+}
+
+unsafe extern "C" fn raw_answer(
+    _slf: *mut pyo3::ffi::PyObject,
+    _args: *mut pyo3::ffi::PyObject,
+) -> *mut pyo3::ffi::PyObject {
+    let py = Python::assume_gil_acquired();
+    42.to_object(py).into_ptr()
+}
+
+#[test]
+fn test_add_fn() {
+    use pyo3::class::{PyMethodDef, PyMethodType};
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let m = PyModule::new(py, "raw").unwrap();
+    m.add_fn(&PyMethodDef {
+        ml_name: "answer",
+        ml_meth: PyMethodType::PyCFunction(raw_answer),
+        ml_flags: pyo3::ffi::METH_NOARGS,
+        ml_doc: "Returns the answer.\0",
+    })
+    .unwrap();
+
+    py_assert!(py, m, "m.answer() == 42");
+    py_assert!(py, m, "m.answer.__module__ == 'raw'");
+    py_assert!(py, m, "m.answer.__doc__ == 'Returns the answer.'");
+    py_assert!(py, m, "m.__all__ == ['answer']");
+}
+
+#[pyclass]
+struct FirstClass {}
+
+#[pyclass]
+struct SecondClass {}
+
+#[test]
+fn test_add_classes() {
+    use pyo3::add_classes;
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let m = PyModule::new(py, "classes").unwrap();
+    add_classes!(m, [FirstClass, SecondClass]).unwrap();
+
+    py_assert!(py, m, "m.FirstClass.__name__ == 'FirstClass'");
+    py_assert!(py, m, "m.SecondClass.__name__ == 'SecondClass'");
+    py_assert!(py, m, "m.__all__ == ['FirstClass', 'SecondClass']");
+}
+
+#[pyfunction(allow_threads)]
+fn sum_without_gil(n: u64) -> (u64, bool) {
+    let gil_held = unsafe { pyo3::ffi::PyGILState_Check() } != 0;
+    ((0..=n).sum(), gil_held)
+}
+
+#[test]
+fn test_allow_threads() {
+    use pyo3::wrap_pyfunction;
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let f = wrap_pyfunction!(sum_without_gil)(py);
+
+    py_assert!(py, f, "f(100) == (5050, False)");
+    py_assert!(py, f, "f(n=4) == (10, False)");
+}
+
+#[pymodule]
+fn registry_root(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("name", "root")
+}
+
+#[pymodule(parent = "registry_root")]
+fn registry_child(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("name", "child")
+}
+
+#[pymodule(registry_renamed, parent = "registry_root")]
+fn registry_other_child(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("name", "other child")
+}
+
+/// Only added to a `registry_root` imported from `other_package`
+#[pymodule(parent = "other_package.registry_root")]
+fn registry_foreign_child(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("name", "foreign child")
+}
+
+#[test]
+fn test_registered_submodules() {
+    use pyo3::wrap_pymodule;
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let root = wrap_pymodule!(registry_root)(py);
+
+    py_assert!(py, root, "root.name == 'root'");
+    py_assert!(py, root, "root.registry_child.name == 'child'");
+    py_assert!(py, root, "root.registry_renamed.name == 'other child'");
+    py_assert!(
+        py,
+        root,
+        "{'registry_child', 'registry_renamed'} <= set(root.__all__)"
+    );
+    py_assert!(py, root, "not hasattr(root, 'registry_foreign_child')");
+}
@@ -2,7 +2,7 @@
 use pyo3;
 use pyo3::prelude::*;
 use pyo3::types::{PyBytes, PyString};
-use pyo3::{AsPyRef, PyClassShell, PyIterProtocol};
+use pyo3::{AsPyRef, PyCell, PyIterProtocol};
 use std::collections::HashMap;
 
 mod common;
@@ -17,29 +17,22 @@ struct Reader {
 
 #[pymethods]
 impl Reader {
-    fn clone_ref(slf: &PyClassShell<Self>) -> &PyClassShell<Self> {
+    fn clone_ref(slf: &PyCell<Self>) -> &PyCell<Self> {
         slf
     }
-    fn clone_ref_with_py<'py>(
-        slf: &'py PyClassShell<Self>,
-        _py: Python<'py>,
-    ) -> &'py PyClassShell<Self> {
+    fn clone_ref_with_py<'py>(slf: &'py PyCell<Self>, _py: Python<'py>) -> &'py PyCell<Self> {
         slf
     }
-    fn get_iter(slf: &PyClassShell<Self>, keys: Py<PyBytes>) -> PyResult<Iter> {
+    fn get_iter(slf: &PyCell<Self>, keys: Py<PyBytes>) -> PyResult<Iter> {
         Ok(Iter {
             reader: slf.into(),
             keys,
             idx: 0,
         })
     }
-    fn get_iter_and_reset(
-        slf: &mut PyClassShell<Self>,
-        keys: Py<PyBytes>,
-        py: Python,
-    ) -> PyResult<Iter> {
-        let reader = Py::new(py, slf.clone())?;
-        slf.inner.clear();
+    fn get_iter_and_reset(slf: &PyCell<Self>, keys: Py<PyBytes>, py: Python) -> PyResult<Iter> {
+        let reader = Py::new(py, Reader::clone(&slf.borrow()))?;
+        slf.borrow_mut().inner.clear();
         Ok(Iter {
             reader,
             keys,
@@ -57,19 +50,21 @@ struct Iter {
 
 #[pyproto]
 impl PyIterProtocol for Iter {
-    fn __iter__(slf: &mut PyClassShell<Self>) -> PyResult<PyObject> {
+    fn __iter__(slf: &PyCell<Self>) -> PyResult<PyObject> {
         let py = unsafe { Python::assume_gil_acquired() };
         Ok(slf.to_object(py))
     }
 
-    fn __next__(slf: &mut PyClassShell<Self>) -> PyResult<Option<PyObject>> {
+    fn __next__(slf: &PyCell<Self>) -> PyResult<Option<PyObject>> {
         let py = unsafe { Python::assume_gil_acquired() };
+        let mut slf = slf.borrow_mut();
         let bytes = slf.keys.as_ref(py).as_bytes();
         match bytes.get(slf.idx) {
             Some(&b) => {
                 let res = slf
                     .reader
                     .as_ref(py)
+                    .borrow()
                     .inner
                     .get(&b)
                     .map(|s| PyString::new(py, s).into());
@@ -113,11 +108,11 @@ fn test_clone_ref() {
 fn test_nested_iter_reset() {
     let gil = Python::acquire_gil();
     let py = gil.python();
-    let reader = PyClassShell::new_ref(py, reader()).unwrap();
+    let reader = PyCell::new_ref(py, reader()).unwrap();
     py_assert!(
         py,
         reader,
         "list(reader.get_iter_and_reset(bytes([3, 5, 2]))) == ['c', 'e', 'b']"
     );
-    assert!(reader.inner.is_empty());
+    assert!(reader.borrow().inner.is_empty());
 }
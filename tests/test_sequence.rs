@@ -195,3 +195,49 @@ fn test_inplace_repeat() {
     run("s = ByteSequence([1, 2]); s *= 3; assert list(s) == [1, 2, 1, 2, 1, 2]");
     err("s = ByteSequence([1, 2); s *= -1");
 }
+
+/// Like `ByteSequence`, but declared with `#[pyclass(sequence)]`
+#[cfg(Py_3_10)]
+#[pyclass(sequence)]
+struct FlaggedSequence {
+    elements: Vec<u8>,
+}
+
+#[cfg(Py_3_10)]
+#[pyproto]
+impl PySequenceProtocol for FlaggedSequence {
+    fn __len__(&self) -> PyResult<usize> {
+        Ok(self.elements.len())
+    }
+
+    fn __getitem__(&self, idx: isize) -> PyResult<u8> {
+        self.elements
+            .get(idx as usize)
+            .copied()
+            .ok_or_else(|| IndexError::py_err("list index out of range"))
+    }
+}
+
+#[test]
+#[cfg(Py_3_10)]
+fn test_sequence_pattern() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let s = Py::new(py, FlaggedSequence { elements: vec![1] }).unwrap();
+    let d = [("s", s)].into_py_dict(py);
+
+    py.run(
+        r#"
+match s:
+    case {}:
+        assert False
+    case [x]:
+        assert x == 1
+    case _:
+        assert False
+"#,
+        None,
+        Some(d),
+    )
+    .unwrap();
+}
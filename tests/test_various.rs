@@ -1,7 +1,7 @@
 use pyo3::prelude::*;
 use pyo3::types::IntoPyDict;
 use pyo3::types::{PyDict, PyTuple};
-use pyo3::{py_run, wrap_pyfunction, AsPyRef, PyClassShell};
+use pyo3::{py_run, wrap_pyfunction, AsPyRef, PyCell};
 
 mod common;
 
@@ -31,7 +31,7 @@ fn mut_ref_arg() {
     let d = [("inst1", &inst1), ("inst2", &inst2)].into_py_dict(py);
 
     py.run("inst1.set_other(inst2)", None, Some(d)).unwrap();
-    assert_eq!(inst2.as_ref(py).n, 100);
+    assert_eq!(inst2.as_ref(py).borrow().n, 100);
 }
 
 #[pyclass]
@@ -81,8 +81,8 @@ fn intopytuple_pyclass() {
     let py = gil.python();
 
     let tup = (
-        PyClassShell::new_ref(py, SimplePyClass {}).unwrap(),
-        PyClassShell::new_ref(py, SimplePyClass {}).unwrap(),
+        PyCell::new_ref(py, SimplePyClass {}).unwrap(),
+        PyCell::new_ref(py, SimplePyClass {}).unwrap(),
     );
     py_assert!(py, tup, "type(tup[0]).__name__ == 'SimplePyClass'");
     py_assert!(py, tup, "type(tup[0]).__name__ == type(tup[1]).__name__");
@@ -106,8 +106,8 @@ fn pytuple_pyclass_iter() {
     let tup = PyTuple::new(
         py,
         [
-            PyClassShell::new_ref(py, SimplePyClass {}).unwrap(),
-            PyClassShell::new_ref(py, SimplePyClass {}).unwrap(),
+            PyCell::new_ref(py, SimplePyClass {}).unwrap(),
+            PyCell::new_ref(py, SimplePyClass {}).unwrap(),
         ]
         .iter(),
     );
@@ -127,7 +127,7 @@ impl PickleSupport {
     }
 
     pub fn __reduce__<'py>(
-        slf: &'py PyClassShell<Self>,
+        slf: &'py PyCell<Self>,
         py: Python<'py>,
     ) -> PyResult<(PyObject, &'py PyTuple, PyObject)> {
         let cls = slf.to_object(py).getattr(py, "__class__")?;
@@ -139,7 +139,7 @@ impl PickleSupport {
 fn add_module(py: Python, module: &PyModule) -> PyResult<()> {
     py.import("sys")?
         .dict()
-        .get_item("modules")
+        .get_item_with_error("modules")?
         .unwrap()
         .downcast_mut::<PyDict>()?
         .set_item(module.name()?, module)
@@ -152,7 +152,7 @@ fn test_pickle() {
     let module = PyModule::new(py, "test_module").unwrap();
     module.add_class::<PickleSupport>().unwrap();
     add_module(py, module).unwrap();
-    let inst = PyClassShell::new_ref(py, PickleSupport {}).unwrap();
+    let inst = PyCell::new_ref(py, PickleSupport {}).unwrap();
     py_run!(
         py,
         inst,
@@ -180,3 +180,43 @@ fn incorrect_iter() {
         .eval("print('Exception state should not be set.')", None, None)
         .is_ok());
 }
+
+#[pyclass(slots = ["x", "y"])]
+struct Slotted {
+    #[pyo3(get)]
+    id: u32,
+}
+
+#[test]
+fn class_with_slots() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let typeobj = py.get_type::<Slotted>();
+    let inst = PyCell::new_ref(py, Slotted { id: 7 }).unwrap();
+    py_assert!(py, typeobj, "typeobj.__slots__ == ('x', 'y')");
+    py_run!(
+        py,
+        inst,
+        r#"
+        assert inst.id == 7
+        assert not hasattr(inst, '__dict__')
+        try:
+            inst.x
+        except AttributeError:
+            pass
+        else:
+            assert False, "unset slot must raise AttributeError"
+        inst.x = [1, 2]
+        inst.y = 'y'
+        assert inst.x == [1, 2] and inst.y == 'y'
+        del inst.x
+        assert not hasattr(inst, 'x')
+        try:
+            inst.z = 1
+        except AttributeError:
+            pass
+        else:
+            assert False, "only slot names can be assigned"
+        "#
+    );
+}
@@ -0,0 +1,11 @@
+use pyo3::prelude::*;
+
+#[pyfunction(allow_threads)]
+fn slow(py: Python, n: u64) -> u64 {
+    py.None();
+    n
+}
+
+fn main() {
+    let _ = slow;
+}
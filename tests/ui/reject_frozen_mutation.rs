@@ -0,0 +1,24 @@
+use pyo3::class::PyMappingProtocol;
+use pyo3::prelude::*;
+
+#[pyclass(frozen)]
+struct FrozenMap {
+    value: i32,
+}
+
+#[pymethods]
+impl FrozenMap {
+    fn reset(&mut self) {
+        self.value = 0;
+    }
+}
+
+#[pyproto]
+impl PyMappingProtocol for FrozenMap {
+    fn __setitem__(&mut self, _key: i32, value: i32) -> PyResult<()> {
+        self.value = value;
+        Ok(())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pyo3::prelude::*;
+
+#[pyclass(frozen)]
+struct FrozenPoint {
+    #[pyo3(get, set)]
+    x: i32,
+}
+
+fn main() {}